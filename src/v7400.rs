@@ -62,10 +62,14 @@ pub use self::{
     global_settings::GlobalSettings,
 };
 
+// `object` should be declared first, to make its macros available to other modules.
+#[macro_use]
+pub mod object;
+
 pub(crate) mod connection;
 pub mod data;
 mod definition;
 mod document;
 pub(crate) mod error;
 mod global_settings;
-pub mod object;
+mod math;
@@ -1,5 +1,13 @@
 //! Data types.
 
+pub mod animation;
+pub mod axis;
+pub mod deformer;
 pub mod material;
 pub mod mesh;
+pub mod model;
+pub mod pose;
+pub mod skeleton;
 pub mod texture;
+pub mod time;
+pub mod unit;
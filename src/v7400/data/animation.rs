@@ -0,0 +1,691 @@
+//! Animation data.
+
+use anyhow::{bail, Error};
+
+use crate::v7400::data::time::FbxTime;
+
+/// Default tangent weight.
+const DEFAULT_WEIGHT: f64 = 1.0 / 3.0;
+
+/// Raw key attribute flags.
+///
+/// These correspond to the constants in `FbxAnimCurveDef` of FBX SDK.
+mod flags {
+    /// Constant interpolation.
+    pub(super) const INTERPOLATION_CONSTANT: i32 = 0x0000_0002;
+    /// Linear interpolation.
+    pub(super) const INTERPOLATION_LINEAR: i32 = 0x0000_0004;
+    /// Cubic interpolation.
+    pub(super) const INTERPOLATION_CUBIC: i32 = 0x0000_0008;
+    /// Mask for interpolation flags.
+    pub(super) const INTERPOLATION_MASK: i32 =
+        INTERPOLATION_CONSTANT | INTERPOLATION_LINEAR | INTERPOLATION_CUBIC;
+
+    /// "Next" constant mode.
+    pub(super) const CONSTANT_NEXT: i32 = 0x0000_0100;
+
+    /// TCB tangent.
+    pub(super) const TANGENT_TCB: i32 = 0x0000_0200;
+    /// User tangent.
+    pub(super) const TANGENT_USER: i32 = 0x0000_0400;
+    /// Break flag for tangents.
+    pub(super) const TANGENT_GENERIC_BREAK: i32 = 0x0000_0800;
+    /// Clamp flag for tangents.
+    pub(super) const TANGENT_GENERIC_CLAMP: i32 = 0x0000_1000;
+    /// Time-independent flag for tangents.
+    pub(super) const TANGENT_GENERIC_TIME_INDEPENDENT: i32 = 0x0000_2000;
+    /// Progressive clamp flag for tangents.
+    ///
+    /// This implies the time-independent flag.
+    pub(super) const TANGENT_GENERIC_CLAMP_PROGRESSIVE: i32 =
+        0x0000_4000 | TANGENT_GENERIC_TIME_INDEPENDENT;
+
+    /// Right side is weighted.
+    pub(super) const WEIGHTED_RIGHT: i32 = 0x0100_0000;
+    /// Next left side is weighted.
+    pub(super) const WEIGHTED_NEXT_LEFT: i32 = 0x0200_0000;
+
+    /// Right side has velocity.
+    pub(super) const VELOCITY_RIGHT: i32 = 0x1000_0000;
+    /// Next left side has velocity.
+    pub(super) const VELOCITY_NEXT_LEFT: i32 = 0x2000_0000;
+}
+
+/// Interpolation mode of a key.
+///
+/// This specifies the interpolation between the key and the next key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InterpolationMode {
+    /// Constant value until the next key.
+    Constant,
+    /// Linear interpolation.
+    Linear,
+    /// Cubic interpolation.
+    Cubic,
+}
+
+/// Constant mode of a key.
+///
+/// This is meaningful only for keys with constant interpolation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConstantMode {
+    /// Use the value of the key.
+    Standard,
+    /// Use the value of the next key.
+    Next,
+}
+
+/// Tangent mode of a key.
+///
+/// This is meaningful only for keys with cubic interpolation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TangentMode {
+    /// Auto tangent, calculated from the neighbor keys by the exporting
+    /// application.
+    Auto,
+    /// Auto tangent with independent left and right slopes.
+    AutoBreak,
+    /// Kochanek-Bartels (tension, continuity, and bias) tangent.
+    Tcb,
+    /// User-specified slope, shared by left and right.
+    User,
+    /// User-specified slopes, independent for left and right.
+    Break,
+}
+
+/// Decoded key attributes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyAttributes {
+    /// Raw flags.
+    flags: i32,
+    /// Raw data.
+    data: [f32; 4],
+}
+
+impl KeyAttributes {
+    /// Creates a new `KeyAttributes` from the raw flags and data.
+    ///
+    /// `data` corresponds to four elements of `KeyAttrDataFloat`.
+    pub fn new(flags: i32, data: [f32; 4]) -> Self {
+        Self { flags, data }
+    }
+
+    /// Returns the raw flags.
+    pub fn raw_flags(&self) -> i32 {
+        self.flags
+    }
+
+    /// Returns the raw data.
+    pub fn raw_data(&self) -> [f32; 4] {
+        self.data
+    }
+
+    /// Returns the interpolation mode.
+    ///
+    /// Returns `Cubic` if no interpolation flags are set, as FBX SDK does.
+    pub fn interpolation(&self) -> InterpolationMode {
+        match self.flags & flags::INTERPOLATION_MASK {
+            flags::INTERPOLATION_CONSTANT => InterpolationMode::Constant,
+            flags::INTERPOLATION_LINEAR => InterpolationMode::Linear,
+            _ => InterpolationMode::Cubic,
+        }
+    }
+
+    /// Returns the constant mode.
+    pub fn constant_mode(&self) -> ConstantMode {
+        if self.flags & flags::CONSTANT_NEXT != 0 {
+            ConstantMode::Next
+        } else {
+            ConstantMode::Standard
+        }
+    }
+
+    /// Returns the tangent mode.
+    pub fn tangent_mode(&self) -> TangentMode {
+        let is_break = self.flags & flags::TANGENT_GENERIC_BREAK != 0;
+        if self.flags & flags::TANGENT_TCB != 0 {
+            TangentMode::Tcb
+        } else if self.flags & flags::TANGENT_USER != 0 {
+            if is_break {
+                TangentMode::Break
+            } else {
+                TangentMode::User
+            }
+        } else if is_break {
+            TangentMode::AutoBreak
+        } else {
+            TangentMode::Auto
+        }
+    }
+
+    /// Returns whether the tangent is clamped.
+    ///
+    /// Clamped tangents are flat if the previous or the next key has the same
+    /// value.
+    pub fn is_clamped(&self) -> bool {
+        self.flags & flags::TANGENT_GENERIC_CLAMP != 0
+    }
+
+    /// Returns whether the tangent is progressively clamped.
+    ///
+    /// Progressively clamped tangents are flat at local extrema.
+    pub fn is_clamped_progressive(&self) -> bool {
+        self.flags & flags::TANGENT_GENERIC_CLAMP_PROGRESSIVE
+            == flags::TANGENT_GENERIC_CLAMP_PROGRESSIVE
+    }
+
+    /// Returns whether the tangent is time independent.
+    pub fn is_time_independent(&self) -> bool {
+        self.flags & flags::TANGENT_GENERIC_TIME_INDEPENDENT != 0
+    }
+
+    /// Returns the right slope (value per second).
+    ///
+    /// This is meaningful only for non-TCB tangents.
+    pub fn right_slope(&self) -> f32 {
+        self.data[0]
+    }
+
+    /// Returns the left slope of the next key (value per second).
+    ///
+    /// This is meaningful only for non-TCB tangents.
+    pub fn next_left_slope(&self) -> f32 {
+        self.data[1]
+    }
+
+    /// Returns tension, continuity, and bias.
+    ///
+    /// This is meaningful only for TCB tangents.
+    pub fn tcb(&self) -> [f32; 3] {
+        [self.data[0], self.data[1], self.data[2]]
+    }
+
+    /// Returns the right weight, if the right side is weighted.
+    pub fn right_weight(&self) -> Option<f64> {
+        if self.flags & flags::WEIGHTED_RIGHT != 0 {
+            Some(unpack_i16_pair(self.data[2]).0)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the left weight of the next key, if it is weighted.
+    pub fn next_left_weight(&self) -> Option<f64> {
+        if self.flags & flags::WEIGHTED_NEXT_LEFT != 0 {
+            Some(unpack_i16_pair(self.data[2]).1)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the right velocity, if available.
+    pub fn right_velocity(&self) -> Option<f64> {
+        if self.flags & flags::VELOCITY_RIGHT != 0 {
+            Some(unpack_i16_pair(self.data[3]).0)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the left velocity of the next key, if available.
+    pub fn next_left_velocity(&self) -> Option<f64> {
+        if self.flags & flags::VELOCITY_NEXT_LEFT != 0 {
+            Some(unpack_i16_pair(self.data[3]).1)
+        } else {
+            None
+        }
+    }
+}
+
+/// Unpacks two fixed point values (in 1/10000 unit) packed into bits of `f32`.
+///
+/// The first value is stored in the lower 16 bits.
+fn unpack_i16_pair(v: f32) -> (f64, f64) {
+    let bits = v.to_bits();
+    let low = (bits & 0xffff) as u16 as i16;
+    let high = (bits >> 16) as u16 as i16;
+    (f64::from(low) / 10000.0, f64::from(high) / 10000.0)
+}
+
+/// Animation curve key.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationKey {
+    /// Time.
+    time: FbxTime,
+    /// Value.
+    value: f32,
+    /// Attributes.
+    attributes: KeyAttributes,
+}
+
+impl AnimationKey {
+    /// Returns the time.
+    pub fn time(&self) -> FbxTime {
+        self.time
+    }
+
+    /// Returns the value.
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Returns the attributes.
+    pub fn attributes(&self) -> KeyAttributes {
+        self.attributes
+    }
+}
+
+/// Animation curve data.
+///
+/// This is a validated set of `KeyTime`, `KeyValueFloat`, `KeyAttrFlags`,
+/// `KeyAttrDataFloat`, and `KeyAttrRefCount` arrays of an animation curve.
+#[derive(Debug, Clone)]
+pub struct AnimationCurveData<'a> {
+    /// Key times.
+    key_time: &'a [i64],
+    /// Key values.
+    key_value: &'a [f32],
+    /// Key attribute flags.
+    key_attr_flags: &'a [i32],
+    /// Key attribute data.
+    key_attr_data: &'a [f32],
+    /// Key attribute index for each key.
+    key_attr_indices: Vec<usize>,
+}
+
+impl<'a> AnimationCurveData<'a> {
+    /// Creates a new `AnimationCurveData` from the raw arrays.
+    pub fn new(
+        key_time: &'a [i64],
+        key_value_float: &'a [f32],
+        key_attr_flags: &'a [i32],
+        key_attr_data_float: &'a [f32],
+        key_attr_ref_count: &'a [i32],
+    ) -> Result<Self, Error> {
+        if key_time.len() != key_value_float.len() {
+            bail!(
+                "Key time and value count mismatch: times={}, values={}",
+                key_time.len(),
+                key_value_float.len()
+            );
+        }
+        if key_time.windows(2).any(|w| w[0] > w[1]) {
+            bail!("Key times are not sorted");
+        }
+        if key_attr_flags.len() != key_attr_ref_count.len()
+            || key_attr_flags.len() * 4 != key_attr_data_float.len()
+        {
+            bail!(
+                "Key attribute count mismatch: flags={}, data={}, ref_count={}",
+                key_attr_flags.len(),
+                key_attr_data_float.len(),
+                key_attr_ref_count.len()
+            );
+        }
+        let mut key_attr_indices = Vec::with_capacity(key_time.len());
+        for (attr_i, &count) in key_attr_ref_count.iter().enumerate() {
+            if count < 0 {
+                bail!("Negative key attribute reference count: {}", count);
+            }
+            key_attr_indices.extend(std::iter::repeat(attr_i).take(count as usize));
+        }
+        if key_attr_indices.len() < key_time.len() {
+            bail!(
+                "Not enough key attributes: expected {} keys, but only {} are referred",
+                key_time.len(),
+                key_attr_indices.len()
+            );
+        }
+        key_attr_indices.truncate(key_time.len());
+
+        Ok(Self {
+            key_time,
+            key_value: key_value_float,
+            key_attr_flags,
+            key_attr_data: key_attr_data_float,
+            key_attr_indices,
+        })
+    }
+
+    /// Returns the number of keys.
+    pub fn len(&self) -> usize {
+        self.key_time.len()
+    }
+
+    /// Returns whether the curve has no keys.
+    pub fn is_empty(&self) -> bool {
+        self.key_time.is_empty()
+    }
+
+    /// Returns the key at the given index.
+    pub fn key(&self, i: usize) -> Option<AnimationKey> {
+        let time = FbxTime::new(*self.key_time.get(i)?);
+        let value = self.key_value[i];
+        let attr_i = self.key_attr_indices[i];
+        let data = &self.key_attr_data[(attr_i * 4)..(attr_i * 4 + 4)];
+        Some(AnimationKey {
+            time,
+            value,
+            attributes: KeyAttributes::new(
+                self.key_attr_flags[attr_i],
+                [data[0], data[1], data[2], data[3]],
+            ),
+        })
+    }
+
+    /// Returns an iterator of keys.
+    pub fn keys(&self) -> impl Iterator<Item = AnimationKey> + '_ {
+        (0..self.len()).filter_map(move |i| self.key(i))
+    }
+
+    /// Evaluates the curve at the given time.
+    ///
+    /// Returns `None` if the curve has no keys.
+    ///
+    /// Before the first key and after the last key, the values of the first
+    /// and the last key are used respectively.
+    ///
+    /// Cubic segments are evaluated as FBX SDK does:
+    ///
+    /// * Slopes of `Auto`, `AutoBreak`, `User`, and `Break` tangents are the
+    ///   ones stored in the key attributes, i.e. the right slope of the key and
+    ///   the next left slope stored in the same key.
+    ///   Auto tangents are calculated by the exporting application, so they
+    ///   are not recalculated.
+    /// * Tangents of clamped keys are flat if the previous or the next key has
+    ///   the same value, and tangents of progressively clamped keys are flat
+    ///   if the key is a local extremum.
+    /// * Slopes of `Tcb` tangents are calculated as Kochanek-Bartels spline,
+    ///   because TCB keys store tension, continuity, and bias instead of
+    ///   slopes.
+    /// * The segment is a 2D cubic Bezier curve in (time, value) space.
+    ///   The inner control points are on the tangents, at the tangent weights
+    ///   of the segment duration from the keys.
+    ///   Unweighted tangents have the weight of 1/3, which is equivalent to
+    ///   cubic Hermite interpolation.
+    /// * Velocities change the speed of the curve at the keys, by remapping
+    ///   the normalized time of the segment with the cubic Bezier curve
+    ///   `(0, (1 + v0) / 3, 1 - (1 + v1) / 3, 1)`, where `v0` is the right
+    ///   velocity and `v1` is the next left velocity.
+    ///   FBX SDK does not document its velocity formula, so this is an
+    ///   approximation which keeps the values at the keys, and is identical
+    ///   to no velocities when they are zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel_dom::v7400::data::{animation::AnimationCurveData, time::FbxTime};
+    /// const SECOND: i64 = FbxTime::TICKS_PER_SECOND;
+    /// const LINEAR: i32 = 0x0000_0004;
+    /// const CUBIC_USER: i32 = 0x0000_0008 | 0x0000_0400;
+    /// const CONSTANT_NEXT: i32 = 0x0000_0002 | 0x0000_0100;
+    ///
+    /// let times = [0, SECOND, 2 * SECOND];
+    /// let values = [0.0, 1.0, 3.0];
+    ///
+    /// // Linear.
+    /// let data = [0.0; 4];
+    /// let curve = AnimationCurveData::new(&times, &values, &[LINEAR], &data, &[3])?;
+    /// assert_eq!(curve.evaluate(FbxTime::new(SECOND / 2)), Some(0.5));
+    /// assert_eq!(curve.evaluate(FbxTime::new(3 * SECOND / 2)), Some(2.0));
+    /// // Out of range.
+    /// assert_eq!(curve.evaluate(FbxTime::new(-SECOND)), Some(0.0));
+    /// assert_eq!(curve.evaluate(FbxTime::new(3 * SECOND)), Some(3.0));
+    ///
+    /// // Constant (next).
+    /// let curve = AnimationCurveData::new(&times, &values, &[CONSTANT_NEXT], &data, &[3])?;
+    /// assert_eq!(curve.evaluate(FbxTime::new(SECOND / 2)), Some(1.0));
+    ///
+    /// // Cubic with flat user tangents: smoothstep.
+    /// let curve = AnimationCurveData::new(&times, &values, &[CUBIC_USER], &data, &[3])?;
+    /// let v = curve.evaluate(FbxTime::new(SECOND / 4)).unwrap();
+    /// assert!((v - 0.15625).abs() < 1e-9);
+    ///
+    /// // Cubic with user tangents, slope 1.0 (per second) at all keys: linear.
+    /// let data = [1.0, 1.0, 0.0, 0.0];
+    /// let curve = AnimationCurveData::new(&times[..2], &values[..2], &[CUBIC_USER], &data, &[2])?;
+    /// let v = curve.evaluate(FbxTime::new(SECOND / 4)).unwrap();
+    /// assert!((v - 0.25).abs() < 1e-9);
+    ///
+    /// // Auto tangents use the slopes stored by the exporting application.
+    /// const CUBIC_AUTO: i32 = 0x0000_0008 | 0x0000_0100;
+    /// let curve = AnimationCurveData::new(&times[..2], &values[..2], &[CUBIC_AUTO], &data, &[2])?;
+    /// let v = curve.evaluate(FbxTime::new(SECOND / 4)).unwrap();
+    /// assert!((v - 0.25).abs() < 1e-9);
+    ///
+    /// // Weights of 1/3 (stored as 3333 in 1/10000 unit) are almost identical to
+    /// // unweighted tangents.
+    /// const WEIGHTED: i32 = 0x0100_0000 | 0x0200_0000;
+    /// let data = [0.0, 0.0, f32::from_bits(0x0D05_0D05), 0.0];
+    /// let weighted = AnimationCurveData::new(&times, &values, &[CUBIC_USER | WEIGHTED], &data, &[3])?;
+    /// let v = weighted.evaluate(FbxTime::new(SECOND / 4)).unwrap();
+    /// assert!((v - 0.15625).abs() < 1e-4);
+    /// # Ok::<_, anyhow::Error>(())
+    /// ```
+    pub fn evaluate(&self, time: FbxTime) -> Option<f64> {
+        let time = time.ticks();
+        let last = self.len().checked_sub(1)?;
+        if time <= self.key_time[0] {
+            return Some(f64::from(self.key_value[0]));
+        }
+        if time >= self.key_time[last] {
+            return Some(f64::from(self.key_value[last]));
+        }
+
+        // `i` is the index of the last key at or before `time`.
+        let i = self.key_time.partition_point(|&t| t <= time) - 1;
+        let key0 = self.key(i)?;
+        let key1 = self.key(i + 1)?;
+        let v0 = f64::from(key0.value);
+        let v1 = f64::from(key1.value);
+        let attrs = key0.attributes;
+
+        let value = match attrs.interpolation() {
+            InterpolationMode::Constant => match attrs.constant_mode() {
+                ConstantMode::Standard => v0,
+                ConstantMode::Next => v1,
+            },
+            InterpolationMode::Linear => {
+                let u = (time - key0.time.ticks()) as f64 / (key1.time - key0.time).ticks() as f64;
+                v0 + (v1 - v0) * u
+            }
+            InterpolationMode::Cubic => self.evaluate_cubic(i, time),
+        };
+        Some(value)
+    }
+
+    /// Evaluates the cubic segment starting from the given key.
+    fn evaluate_cubic(&self, i: usize, time: i64) -> f64 {
+        let key0 = self.key(i).expect("Should never fail: the key exists");
+        let key1 = self.key(i + 1).expect("Should never fail: the key exists");
+        let attrs = key0.attributes;
+
+        let dt = (key1.time - key0.time).seconds();
+        let v0 = f64::from(key0.value);
+        let v1 = f64::from(key1.value);
+        let slope0 = self.right_slope(i);
+        let slope1 = self.left_slope(i + 1);
+        // Weights out of `[0, 1]` would make the curve non-monotonic in time.
+        let w0 = attrs
+            .right_weight()
+            .unwrap_or(DEFAULT_WEIGHT)
+            .clamp(0.0, 1.0);
+        let w1 = attrs
+            .next_left_weight()
+            .unwrap_or(DEFAULT_WEIGHT)
+            .clamp(0.0, 1.0);
+
+        // Control points in normalized time (0 to 1) and value space.
+        let x1 = w0;
+        let y1 = v0 + slope0 * w0 * dt;
+        let x2 = 1.0 - w1;
+        let y2 = v1 - slope1 * w1 * dt;
+
+        let x = (time - key0.time.ticks()) as f64 / (key1.time - key0.time).ticks() as f64;
+        // Velocities out of `[-1, 2]` would make the time remapping
+        // non-monotonic.
+        let vel0 = attrs.right_velocity().unwrap_or(0.0).clamp(-1.0, 2.0);
+        let vel1 = attrs.next_left_velocity().unwrap_or(0.0).clamp(-1.0, 2.0);
+        let x = bezier(0.0, (1.0 + vel0) / 3.0, 1.0 - (1.0 + vel1) / 3.0, 1.0, x);
+        let u = solve_bezier_parameter(x1, x2, x);
+
+        bezier(v0, y1, y2, v1, u)
+    }
+
+    /// Returns the right slope (value per second) of the given key.
+    fn right_slope(&self, i: usize) -> f64 {
+        let attrs = self
+            .key(i)
+            .expect("Should never fail: the key exists")
+            .attributes;
+        if self.is_flattened(i) {
+            return 0.0;
+        }
+        match attrs.tangent_mode() {
+            TangentMode::Tcb => self.tcb_slopes(i).1,
+            _ => f64::from(attrs.right_slope()),
+        }
+    }
+
+    /// Returns the left slope (value per second) of the given key.
+    ///
+    /// The key should not be the first key.
+    fn left_slope(&self, i: usize) -> f64 {
+        let attrs = self
+            .key(i)
+            .expect("Should never fail: the key exists")
+            .attributes;
+        let prev_attrs = self
+            .key(i - 1)
+            .expect("Should never fail: the previous key exists")
+            .attributes;
+        if self.is_flattened(i) {
+            0.0
+        } else if attrs.tangent_mode() == TangentMode::Tcb {
+            self.tcb_slopes(i).0
+        } else if prev_attrs.tangent_mode() == TangentMode::Tcb {
+            // The previous key stores TCB parameters instead of the next left
+            // slope. Use the right slope, which is identical to the left slope
+            // unless the tangent is broken.
+            f64::from(attrs.right_slope())
+        } else {
+            f64::from(prev_attrs.next_left_slope())
+        }
+    }
+
+    /// Returns whether the tangent of the given key is flattened by the
+    /// clamp flags.
+    fn is_flattened(&self, i: usize) -> bool {
+        let attrs = self
+            .key(i)
+            .expect("Should never fail: the key exists")
+            .attributes;
+        let value = self.key_value[i];
+        let prev = i.checked_sub(1).map(|i| self.key_value[i]);
+        let next = self.key_value.get(i + 1).copied();
+        if attrs.is_clamped_progressive() {
+            if let (Some(prev), Some(next)) = (prev, next) {
+                if (value - prev) * (next - value) <= 0.0 {
+                    return true;
+                }
+            }
+        }
+        attrs.is_clamped() && (prev == Some(value) || next == Some(value))
+    }
+
+    /// Returns the time (in seconds) and the value of the given key.
+    fn time_value(&self, i: usize) -> (f64, f64) {
+        (
+            FbxTime::new(self.key_time[i]).seconds(),
+            f64::from(self.key_value[i]),
+        )
+    }
+
+    /// Calculates the TCB tangent slopes (left and right) of the given key.
+    fn tcb_slopes(&self, i: usize) -> (f64, f64) {
+        let attrs = self
+            .key(i)
+            .expect("Should never fail: the key exists")
+            .attributes;
+        let [tension, continuity, bias] = attrs.tcb();
+        let (tension, continuity, bias) =
+            (f64::from(tension), f64::from(continuity), f64::from(bias));
+
+        let (t, v) = self.time_value(i);
+        let (t_prev, v_prev) = if i > 0 {
+            self.time_value(i - 1)
+        } else {
+            (t, v)
+        };
+        let (t_next, v_next) = if i + 1 < self.len() {
+            self.time_value(i + 1)
+        } else {
+            (t, v)
+        };
+        let span = t_next - t_prev;
+        if span <= 0.0 {
+            return (0.0, 0.0);
+        }
+        let delta_prev = v - v_prev;
+        let delta_next = v_next - v;
+
+        let incoming = (1.0 - tension) * (1.0 - continuity) * (1.0 + bias) / 2.0 * delta_prev
+            + (1.0 - tension) * (1.0 + continuity) * (1.0 - bias) / 2.0 * delta_next;
+        let outgoing = (1.0 - tension) * (1.0 + continuity) * (1.0 + bias) / 2.0 * delta_prev
+            + (1.0 - tension) * (1.0 - continuity) * (1.0 - bias) / 2.0 * delta_next;
+
+        // Convert tangents into slopes, adjusting for non-uniform key spacing.
+        (incoming * 2.0 / span, outgoing * 2.0 / span)
+    }
+}
+
+/// Evaluates 1D cubic Bezier curve.
+fn bezier(p0: f64, p1: f64, p2: f64, p3: f64, u: f64) -> f64 {
+    let v = 1.0 - u;
+    v * v * v * p0 + 3.0 * v * v * u * p1 + 3.0 * v * u * u * p2 + u * u * u * p3
+}
+
+/// Returns the parameter `u` where the Bezier curve `(0, x1, x2, 1)` is `x`.
+///
+/// `x1` and `x2` are expected to be in `[0, 1]`, so that the curve is
+/// monotonic.
+fn solve_bezier_parameter(x1: f64, x2: f64, x: f64) -> f64 {
+    if (x1 - DEFAULT_WEIGHT).abs() < 1e-12 && (x2 - (1.0 - DEFAULT_WEIGHT)).abs() < 1e-12 {
+        // The curve is linear.
+        return x;
+    }
+
+    // Newton's method, falling back to bisection.
+    let mut u = x;
+    for _ in 0..8 {
+        let err = bezier(0.0, x1, x2, 1.0, u) - x;
+        if err.abs() < 1e-12 {
+            return u;
+        }
+        let v = 1.0 - u;
+        let derivative = 3.0 * v * v * x1 + 6.0 * v * u * (x2 - x1) + 3.0 * u * u * (1.0 - x2);
+        if derivative.abs() < 1e-12 {
+            break;
+        }
+        u -= err / derivative;
+        if !(0.0..=1.0).contains(&u) {
+            break;
+        }
+    }
+
+    let (mut low, mut high) = (0.0, 1.0);
+    u = x;
+    for _ in 0..64 {
+        let err = bezier(0.0, x1, x2, 1.0, u) - x;
+        if err.abs() < 1e-12 {
+            break;
+        }
+        if err < 0.0 {
+            low = u;
+        } else {
+            high = u;
+        }
+        u = (low + high) / 2.0;
+    }
+    u
+}
@@ -0,0 +1,529 @@
+//! Axis data.
+
+use std::convert::TryFrom;
+
+use anyhow::{bail, Error};
+use mint::{ColumnMatrix4, Point3, Vector3};
+
+use crate::v7400::{
+    data::model::RotationOrder,
+    math::{cross, dot, scale, Mat4},
+    object::property::{loaders::PrimitiveLoader, LoadProperty, PropertyHandle},
+};
+
+/// Axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Axis {
+    /// X axis.
+    X,
+    /// Y axis.
+    Y,
+    /// Z axis.
+    Z,
+}
+
+impl Axis {
+    /// Returns the index of the axis, i.e. 0 for X, 1 for Y, and 2 for Z.
+    pub fn index(self) -> usize {
+        match self {
+            Axis::X => 0,
+            Axis::Y => 1,
+            Axis::Z => 2,
+        }
+    }
+
+    /// Returns the component of the given vector.
+    fn component(self, v: Vector3<f64>) -> f64 {
+        match self {
+            Axis::X => v.x,
+            Axis::Y => v.y,
+            Axis::Z => v.z,
+        }
+    }
+}
+
+impl TryFrom<i32> for Axis {
+    type Error = Error;
+
+    fn try_from(v: i32) -> Result<Self, Self::Error> {
+        match v {
+            0 => Ok(Axis::X),
+            1 => Ok(Axis::Y),
+            2 => Ok(Axis::Z),
+            v => bail!("Unexpected `Axis` value: {:?}", v),
+        }
+    }
+}
+
+/// `Axis` property loader.
+#[derive(Default, Debug, Clone, Copy)]
+pub(crate) struct AxisLoader;
+
+impl<'a> LoadProperty<'a> for AxisLoader {
+    type Value = Axis;
+    type Error = Error;
+
+    fn expecting(&self) -> String {
+        "`i32` value as axis".into()
+    }
+
+    fn load(self, node: &PropertyHandle<'a>) -> Result<Self::Value, Self::Error> {
+        node.load_value(PrimitiveLoader::<i32>::new())
+            .and_then(TryFrom::try_from)
+    }
+}
+
+/// Sign of an axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AxisSign {
+    /// Positive.
+    Positive,
+    /// Negative.
+    Negative,
+}
+
+impl AxisSign {
+    /// Returns the sign as a number, i.e. `1.0` or `-1.0`.
+    pub fn to_f64(self) -> f64 {
+        match self {
+            AxisSign::Positive => 1.0,
+            AxisSign::Negative => -1.0,
+        }
+    }
+}
+
+impl TryFrom<i32> for AxisSign {
+    type Error = Error;
+
+    fn try_from(v: i32) -> Result<Self, Self::Error> {
+        match v {
+            1 => Ok(AxisSign::Positive),
+            -1 => Ok(AxisSign::Negative),
+            v => bail!("Unexpected `AxisSign` value: {:?}", v),
+        }
+    }
+}
+
+/// `AxisSign` property loader.
+#[derive(Default, Debug, Clone, Copy)]
+pub(crate) struct AxisSignLoader;
+
+impl<'a> LoadProperty<'a> for AxisSignLoader {
+    type Value = AxisSign;
+    type Error = Error;
+
+    fn expecting(&self) -> String {
+        "`i32` value as axis sign".into()
+    }
+
+    fn load(self, node: &PropertyHandle<'a>) -> Result<Self::Value, Self::Error> {
+        node.load_value(PrimitiveLoader::<i32>::new())
+            .and_then(TryFrom::try_from)
+    }
+}
+
+/// Axis with sign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignedAxis {
+    /// Axis.
+    axis: Axis,
+    /// Sign.
+    sign: AxisSign,
+}
+
+impl SignedAxis {
+    /// +X.
+    pub const POSITIVE_X: Self = Self::new(Axis::X, AxisSign::Positive);
+    /// -X.
+    pub const NEGATIVE_X: Self = Self::new(Axis::X, AxisSign::Negative);
+    /// +Y.
+    pub const POSITIVE_Y: Self = Self::new(Axis::Y, AxisSign::Positive);
+    /// -Y.
+    pub const NEGATIVE_Y: Self = Self::new(Axis::Y, AxisSign::Negative);
+    /// +Z.
+    pub const POSITIVE_Z: Self = Self::new(Axis::Z, AxisSign::Positive);
+    /// -Z.
+    pub const NEGATIVE_Z: Self = Self::new(Axis::Z, AxisSign::Negative);
+
+    /// Creates a new `SignedAxis`.
+    pub const fn new(axis: Axis, sign: AxisSign) -> Self {
+        Self { axis, sign }
+    }
+
+    /// Returns the axis.
+    pub fn axis(self) -> Axis {
+        self.axis
+    }
+
+    /// Returns the sign.
+    pub fn sign(self) -> AxisSign {
+        self.sign
+    }
+
+    /// Returns the unit vector of the axis direction.
+    pub fn unit_vector(self) -> Vector3<f64> {
+        let s = self.sign.to_f64();
+        match self.axis {
+            Axis::X => Vector3 {
+                x: s,
+                y: 0.0,
+                z: 0.0,
+            },
+            Axis::Y => Vector3 {
+                x: 0.0,
+                y: s,
+                z: 0.0,
+            },
+            Axis::Z => Vector3 {
+                x: 0.0,
+                y: 0.0,
+                z: s,
+            },
+        }
+    }
+}
+
+/// Axis system.
+///
+/// This consists of three directions: up, front, and coord (right).
+///
+/// This corresponds to `FbxAxisSystem` of FBX SDK.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AxisSystem {
+    /// Up axis.
+    up: SignedAxis,
+    /// Front axis.
+    front: SignedAxis,
+    /// Coord axis.
+    coord: SignedAxis,
+}
+
+impl AxisSystem {
+    /// Creates a new `AxisSystem`.
+    ///
+    /// Returns `None` if any two of the axes are the same.
+    pub fn new(up: SignedAxis, front: SignedAxis, coord: SignedAxis) -> Option<Self> {
+        if up.axis == front.axis || front.axis == coord.axis || coord.axis == up.axis {
+            return None;
+        }
+        Some(Self { up, front, coord })
+    }
+
+    /// Returns the up axis.
+    pub fn up(&self) -> SignedAxis {
+        self.up
+    }
+
+    /// Returns the front axis.
+    pub fn front(&self) -> SignedAxis {
+        self.front
+    }
+
+    /// Returns the coord axis.
+    pub fn coord(&self) -> SignedAxis {
+        self.coord
+    }
+
+    /// Returns the conversion from this axis system to the given one.
+    pub fn conversion_to(&self, target: &AxisSystem) -> AxisConversion {
+        AxisConversion::new(self, target)
+    }
+
+    /// Returns whether the axis system is right-handed.
+    pub fn is_right_handed(&self) -> bool {
+        let coord = self.coord.unit_vector();
+        let up = self.up.unit_vector();
+        let front = self.front.unit_vector();
+        dot(cross(coord, up), front) > 0.0
+    }
+}
+
+impl Default for AxisSystem {
+    /// Returns the default axis system of FBX: Y-up, Z-front, and X-coord.
+    fn default() -> Self {
+        Self {
+            up: SignedAxis::POSITIVE_Y,
+            front: SignedAxis::POSITIVE_Z,
+            coord: SignedAxis::POSITIVE_X,
+        }
+    }
+}
+
+/// Component of a transform.
+///
+/// This is used to specify how animation curve values are converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TransformComponent {
+    /// Translation.
+    Translation,
+    /// Rotation (euler angles).
+    Rotation,
+    /// Scaling.
+    Scaling,
+}
+
+/// Conversion from an axis system to another.
+///
+/// Axis systems are axis aligned, so the basis change matrix `C` is a
+/// permutation of the axes with signs.
+/// Values are converted as below:
+///
+/// * Points, vectors, and normals are transformed by `C`.
+///   Note that `C` is orthogonal, so normals need no special treatment.
+/// * Transform matrices (model transforms, bind matrices, etc.) are
+///   transformed to `C * M * C^-1`, so that they map converted points to
+///   converted points.
+/// * Euler rotations are remapped to the converted axes, with the converted
+///   rotation order.
+/// * Scalings are remapped to the converted axes without signs.
+///
+/// If the conversion changes handedness (see [`flips_winding()`]), the
+/// winding order of polygons should also be reversed to keep front faces.
+///
+/// [`flips_winding()`]: #method.flips_winding
+///
+/// # Examples
+///
+/// ```
+/// # use fbxcel_dom::v7400::data::axis::{AxisSystem, SignedAxis};
+/// use mint::Point3;
+///
+/// // Z-up right-handed (such as Blender) to Y-up right-handed (such as glTF).
+/// let source = AxisSystem::new(
+///     SignedAxis::POSITIVE_Z,
+///     SignedAxis::NEGATIVE_Y,
+///     SignedAxis::POSITIVE_X,
+/// )
+/// .unwrap();
+/// let conversion = source.conversion_to(&AxisSystem::default());
+/// assert!(!conversion.flips_winding());
+///
+/// // Up is up.
+/// let p = conversion.convert_point(Point3 { x: 0.0, y: 0.0, z: 1.0 });
+/// assert_eq!(p, Point3 { x: 0.0, y: 1.0, z: 0.0 });
+/// // Front (-Y) is front (+Z).
+/// let p = conversion.convert_point(Point3 { x: 0.0, y: -1.0, z: 0.0 });
+/// assert_eq!(p, Point3 { x: 0.0, y: 0.0, z: 1.0 });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisConversion {
+    /// Target axis and sign for each source axis (X, Y, and Z).
+    mapping: [(Axis, f64); 3],
+    /// Basis change matrix.
+    matrix: Mat4,
+}
+
+impl AxisConversion {
+    /// Creates a new conversion from `source` axis system to `target`.
+    fn new(source: &AxisSystem, target: &AxisSystem) -> Self {
+        let mut mapping = [(Axis::X, 1.0), (Axis::Y, 1.0), (Axis::Z, 1.0)];
+        let mut matrix = Mat4::IDENTITY;
+        for (src, dest) in [
+            (source.up, target.up),
+            (source.front, target.front),
+            (source.coord, target.coord),
+        ] {
+            let sign = src.sign.to_f64() * dest.sign.to_f64();
+            mapping[src.axis.index()] = (dest.axis, sign);
+            let mut column = [0.0; 4];
+            column[dest.axis.index()] = sign;
+            matrix.0[src.axis.index()] = column;
+        }
+        Self { mapping, matrix }
+    }
+
+    /// Returns the identity conversion.
+    pub fn identity() -> Self {
+        let default = AxisSystem::default();
+        Self::new(&default, &default)
+    }
+
+    /// Returns whether the conversion does nothing.
+    pub fn is_identity(&self) -> bool {
+        self.matrix == Mat4::IDENTITY
+    }
+
+    /// Returns whether the conversion changes handedness.
+    ///
+    /// If this is true, the winding order of polygons should be reversed.
+    pub fn flips_winding(&self) -> bool {
+        self.determinant() < 0.0
+    }
+
+    /// Returns the determinant of the basis change matrix (`1` or `-1`).
+    fn determinant(&self) -> f64 {
+        let column = |(axis, sign): (Axis, f64)| {
+            scale(
+                SignedAxis::new(axis, AxisSign::Positive).unit_vector(),
+                sign,
+            )
+        };
+        dot(
+            cross(column(self.mapping[0]), column(self.mapping[1])),
+            column(self.mapping[2]),
+        )
+    }
+
+    /// Returns the basis change matrix.
+    pub fn matrix(&self) -> ColumnMatrix4<f64> {
+        self.matrix.into()
+    }
+
+    /// Returns the target axis and sign for the given source axis.
+    pub fn convert_axis(&self, axis: Axis) -> (Axis, f64) {
+        self.mapping[axis.index()]
+    }
+
+    /// Returns the source axis and sign for the given target axis.
+    pub fn source_axis(&self, target: Axis) -> (Axis, f64) {
+        let (index, (_, sign)) = self
+            .mapping
+            .iter()
+            .enumerate()
+            .find(|(_, (axis, _))| *axis == target)
+            .expect("Should never fail: the mapping is a permutation");
+        let source = [Axis::X, Axis::Y, Axis::Z][index];
+        (source, *sign)
+    }
+
+    /// Converts a point.
+    ///
+    /// Use this for control points and shape vertices.
+    pub fn convert_point(&self, p: Point3<f64>) -> Point3<f64> {
+        self.matrix.transform_point(p)
+    }
+
+    /// Converts a vector.
+    ///
+    /// Use this for translations and directions.
+    pub fn convert_vector(&self, v: Vector3<f64>) -> Vector3<f64> {
+        let p = self.convert_point(Point3 {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        });
+        Vector3 {
+            x: p.x,
+            y: p.y,
+            z: p.z,
+        }
+    }
+
+    /// Converts a normal (or tangent, binormal).
+    pub fn convert_normal(&self, v: Vector3<f64>) -> Vector3<f64> {
+        self.convert_vector(v)
+    }
+
+    /// Converts a scaling.
+    pub fn convert_scaling(&self, v: Vector3<f64>) -> Vector3<f64> {
+        let component = |target: Axis| self.source_axis(target).0.component(v);
+        Vector3 {
+            x: component(Axis::X),
+            y: component(Axis::Y),
+            z: component(Axis::Z),
+        }
+    }
+
+    /// Converts euler angles (in degrees) and the rotation order.
+    ///
+    /// The resulting euler angles and rotation order represents the
+    /// converted rotation matrix `C * R * C^-1`.
+    /// `SphericXyz` is treated as `Xyz`.
+    pub fn convert_euler(
+        &self,
+        degrees: Vector3<f64>,
+        order: RotationOrder,
+    ) -> (Vector3<f64>, RotationOrder) {
+        let det = self.determinant();
+        let mut converted = [0.0; 3];
+        for axis in [Axis::X, Axis::Y, Axis::Z] {
+            let (target, sign) = self.convert_axis(axis);
+            converted[target.index()] = axis.component(degrees) * sign * det;
+        }
+        let axes = rotation_order_axes(order);
+        let converted_order = rotation_order_from_axes([
+            self.convert_axis(axes[0]).0,
+            self.convert_axis(axes[1]).0,
+            self.convert_axis(axes[2]).0,
+        ]);
+        (Vector3::from(converted), converted_order)
+    }
+
+    /// Converts a transform matrix.
+    ///
+    /// Use this for model transforms (both local and world) and bind
+    /// matrices.
+    pub fn convert_matrix(&self, m: ColumnMatrix4<f64>) -> ColumnMatrix4<f64> {
+        // The basis change matrix is orthogonal.
+        (self.matrix * Mat4::from(m) * self.matrix.transpose()).into()
+    }
+
+    /// Returns the source channel and the multiplier for the given target
+    /// channel of the animated transform component.
+    ///
+    /// The converted curve value for `target` channel is the value of the
+    /// returned source channel multiplied by the returned factor.
+    ///
+    /// For rotation, this is correct only if the rotation order is also
+    /// converted by [`convert_euler()`].
+    ///
+    /// [`convert_euler()`]: #method.convert_euler
+    pub fn curve_channel_source(&self, component: TransformComponent, target: Axis) -> (Axis, f64) {
+        let (source, sign) = self.source_axis(target);
+        let factor = match component {
+            TransformComponent::Translation => sign,
+            TransformComponent::Rotation => sign * self.determinant(),
+            TransformComponent::Scaling => 1.0,
+        };
+        (source, factor)
+    }
+
+    /// Converts an animated value of the transform component.
+    ///
+    /// This is equivalent to remapping the channels by
+    /// [`curve_channel_source()`].
+    ///
+    /// [`curve_channel_source()`]: #method.curve_channel_source
+    pub fn convert_animated(&self, component: TransformComponent, v: Vector3<f64>) -> Vector3<f64> {
+        let channel = |target: Axis| {
+            let (source, factor) = self.curve_channel_source(component, target);
+            source.component(v) * factor
+        };
+        Vector3 {
+            x: channel(Axis::X),
+            y: channel(Axis::Y),
+            z: channel(Axis::Z),
+        }
+    }
+}
+
+impl Default for AxisConversion {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Returns the axes of the rotation order, in the order of application.
+fn rotation_order_axes(order: RotationOrder) -> [Axis; 3] {
+    match order {
+        RotationOrder::Xyz | RotationOrder::SphericXyz => [Axis::X, Axis::Y, Axis::Z],
+        RotationOrder::Xzy => [Axis::X, Axis::Z, Axis::Y],
+        RotationOrder::Yzx => [Axis::Y, Axis::Z, Axis::X],
+        RotationOrder::Yxz => [Axis::Y, Axis::X, Axis::Z],
+        RotationOrder::Zxy => [Axis::Z, Axis::X, Axis::Y],
+        RotationOrder::Zyx => [Axis::Z, Axis::Y, Axis::X],
+    }
+}
+
+/// Returns the rotation order from the axes in the order of application.
+fn rotation_order_from_axes(axes: [Axis; 3]) -> RotationOrder {
+    match axes {
+        [Axis::X, Axis::Y, _] => RotationOrder::Xyz,
+        [Axis::X, Axis::Z, _] => RotationOrder::Xzy,
+        [Axis::Y, Axis::Z, _] => RotationOrder::Yzx,
+        [Axis::Y, Axis::X, _] => RotationOrder::Yxz,
+        [Axis::Z, Axis::X, _] => RotationOrder::Zxy,
+        [Axis::Z, Axis::Y, _] => RotationOrder::Zyx,
+        _ => unreachable!("Should never happen: axes should be a permutation"),
+    }
+}
@@ -0,0 +1,107 @@
+//! Deformer data.
+
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::{bail, Error};
+
+pub use self::{
+    blendshape::{target_weights, BlendShapeDeformation, MorphTargetBuffers},
+    skin::{Influence, JointBuffers, SkinInfluences},
+    skinning::{SkinDeformation, SkinDeformationBuilder},
+};
+
+mod blendshape;
+mod skin;
+mod skinning;
+
+/// Link mode of a skin cluster.
+///
+/// This specifies how the weights of the cluster are combined with other
+/// clusters.
+///
+/// This corresponds to `FbxCluster::ELinkMode` of FBX SDK.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LinkMode {
+    /// Weights are normalized so that the sum is 1.
+    Normalize,
+    /// Weights are added as is.
+    Additive,
+    /// Weights are expected to sum to 1, and the remainder is assigned to
+    /// the original (undeformed) position.
+    TotalOne,
+}
+
+impl TryFrom<&str> for LinkMode {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "Normalize" => Ok(LinkMode::Normalize),
+            "Additive" => Ok(LinkMode::Additive),
+            "Total1" | "TotalOne" => Ok(LinkMode::TotalOne),
+            s => bail!("Unexpected `LinkMode` value: {:?}", s),
+        }
+    }
+}
+
+impl std::str::FromStr for LinkMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.try_into()
+    }
+}
+
+impl Default for LinkMode {
+    fn default() -> Self {
+        LinkMode::Normalize
+    }
+}
+
+/// Skinning type of a skin.
+///
+/// This corresponds to `FbxSkin::EType` of FBX SDK.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SkinningType {
+    /// Rigid skinning.
+    ///
+    /// This is evaluated same as linear skinning.
+    Rigid,
+    /// Linear blend skinning.
+    Linear,
+    /// Dual quaternion skinning.
+    DualQuaternion,
+    /// Blend of linear and dual quaternion skinning.
+    ///
+    /// Per-control-point blend weights (`BlendWeights`) specify the weight
+    /// of dual quaternion skinning.
+    Blend,
+}
+
+impl TryFrom<&str> for SkinningType {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "Rigid" => Ok(SkinningType::Rigid),
+            "Linear" => Ok(SkinningType::Linear),
+            "DualQuaternion" => Ok(SkinningType::DualQuaternion),
+            "Blend" => Ok(SkinningType::Blend),
+            s => bail!("Unexpected `SkinningType` value: {:?}", s),
+        }
+    }
+}
+
+impl std::str::FromStr for SkinningType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.try_into()
+    }
+}
+
+impl Default for SkinningType {
+    fn default() -> Self {
+        SkinningType::Linear
+    }
+}
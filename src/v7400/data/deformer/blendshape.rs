@@ -0,0 +1,455 @@
+//! Blend shape evaluation.
+
+use anyhow::{bail, format_err, Error};
+use mint::{Point3, Vector3};
+
+use crate::v7400::{
+    data::{
+        axis::AxisConversion,
+        mesh::{layer::normal::Normals, ControlPointIndex, TriangleVertices},
+        unit::UnitConversion,
+    },
+    math::normalize,
+    object::{
+        deformer::{BlendShapeChannelHandle, BlendShapeHandle},
+        geometry::ShapeHandle,
+    },
+};
+
+/// Blend shape deformation of control points.
+///
+/// Channel weights are deform percents (usually in the range of 0 to 100).
+/// Each channel is evaluated by [`target_weights()`], i.e. the deformation
+/// is linearly interpolated between the two target shapes (or the base
+/// geometry and the first target shape) around the weight.
+///
+/// [`target_weights()`]: fn.target_weights.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # use fbxcel_dom::v7400::object::deformer::BlendShapeHandle;
+/// use fbxcel_dom::v7400::data::deformer::BlendShapeDeformation;
+///
+/// # fn get_blendshape<'a>() -> BlendShapeHandle<'a> { unimplemented!() }
+/// let blendshape: BlendShapeHandle<'_> = get_blendshape();
+/// // Use the current `DeformPercent` of each channel.
+/// let weights = blendshape
+///     .blendshape_channels()
+///     .map(|channel| channel.deform_percent())
+///     .collect::<Result<Vec<_>, _>>()?;
+/// let deformation = BlendShapeDeformation::from_blendshape(&blendshape, &weights)?;
+/// println!("{:?}", deformation.control_points());
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct BlendShapeDeformation {
+    /// Deformed control points.
+    control_points: Vec<Point3<f64>>,
+    /// Normal deltas for each control point, if any target shape has normals.
+    normal_deltas: Option<Vec<Vector3<f64>>>,
+}
+
+impl BlendShapeDeformation {
+    /// Deforms the mesh by the blendshape.
+    ///
+    /// `weights` are deform percents of the channels, in the order of
+    /// [`BlendShapeHandle::blendshape_channels()`].
+    ///
+    /// [`BlendShapeHandle::blendshape_channels()`]:
+    /// ../../object/deformer/struct.BlendShapeHandle.html#method.blendshape_channels
+    pub fn from_blendshape(
+        blendshape: &BlendShapeHandle<'_>,
+        weights: &[f64],
+    ) -> Result<Self, Error> {
+        let channel_count = blendshape.blendshape_channels().count();
+        if weights.len() != channel_count {
+            bail!(
+                "Weights count mismatch: expected {} channels, but got {} weights",
+                channel_count,
+                weights.len()
+            );
+        }
+
+        let mut control_points = blendshape
+            .mesh()?
+            .control_points()?
+            .iter()?
+            .collect::<Vec<_>>();
+        let mut normal_deltas: Option<Vec<Vector3<f64>>> = None;
+        for (channel, &weight) in blendshape.blendshape_channels().zip(weights) {
+            let target_weights = target_weights(weight, &channel.target_full_weights()?);
+            for (shape, target_weight) in channel.shapes().zip(target_weights) {
+                if target_weight == 0.0 {
+                    continue;
+                }
+                for (cpi, delta) in shape.vertex_deltas()? {
+                    let point = control_points
+                        .get_mut(cpi.to_u32() as usize)
+                        .ok_or_else(|| out_of_range(cpi))?;
+                    point.x += delta.x * target_weight;
+                    point.y += delta.y * target_weight;
+                    point.z += delta.z * target_weight;
+                }
+                if let Some(deltas) = shape.normal_deltas()? {
+                    let cp_count = control_points.len();
+                    let normals = normal_deltas.get_or_insert_with(|| {
+                        vec![
+                            Vector3 {
+                                x: 0.0,
+                                y: 0.0,
+                                z: 0.0
+                            };
+                            cp_count
+                        ]
+                    });
+                    for (cpi, delta) in deltas {
+                        let normal = normals
+                            .get_mut(cpi.to_u32() as usize)
+                            .ok_or_else(|| out_of_range(cpi))?;
+                        normal.x += delta.x * target_weight;
+                        normal.y += delta.y * target_weight;
+                        normal.z += delta.z * target_weight;
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            control_points,
+            normal_deltas,
+        })
+    }
+
+    /// Returns the deformed control points.
+    pub fn control_points(&self) -> &[Point3<f64>] {
+        &self.control_points
+    }
+
+    /// Returns the deformed control point at the given index.
+    pub fn control_point(&self, cpi: ControlPointIndex) -> Option<Point3<f64>> {
+        self.control_points.get(cpi.to_u32() as usize).copied()
+    }
+
+    /// Returns whether any target shape has normals.
+    pub fn has_normals(&self) -> bool {
+        self.normal_deltas.is_some()
+    }
+
+    /// Returns the normal delta at the given control point.
+    ///
+    /// Returns zero vector if no target shapes have normals.
+    pub fn normal_delta(&self, cpi: ControlPointIndex) -> Option<Vector3<f64>> {
+        let zero = Vector3 {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        match &self.normal_deltas {
+            Some(deltas) => deltas.get(cpi.to_u32() as usize).copied(),
+            None if (cpi.to_u32() as usize) < self.control_points.len() => Some(zero),
+            None => None,
+        }
+    }
+
+    /// Deforms the normal at the given control point.
+    ///
+    /// The result is normalized.
+    pub fn deform_normal(
+        &self,
+        cpi: ControlPointIndex,
+        normal: Vector3<f64>,
+    ) -> Option<Vector3<f64>> {
+        let delta = self.normal_delta(cpi)?;
+        Some(normalize(Vector3 {
+            x: normal.x + delta.x,
+            y: normal.y + delta.y,
+            z: normal.z + delta.z,
+        }))
+    }
+
+    /// Returns deformed normals for each triangle vertex.
+    pub fn deform_normals(
+        &self,
+        tris: &TriangleVertices<'_>,
+        normals: &Normals<'_>,
+    ) -> Result<Vec<Vector3<f64>>, Error> {
+        tris.triangle_vertex_indices()
+            .map(|tri_vi| {
+                let cpi = tris.control_point_index(tri_vi).ok_or_else(|| {
+                    format_err!("Failed to get control point index: {:?}", tri_vi)
+                })?;
+                let normal = normals.normal(tris, tri_vi)?;
+                self.deform_normal(cpi, normal)
+                    .ok_or_else(|| out_of_range(cpi))
+            })
+            .collect()
+    }
+}
+
+/// Returns the weights of the target shapes for the given channel weight.
+///
+/// `weight` is a deform percent, and `full_weights` are full weights of the
+/// target shapes (see [`BlendShapeChannelHandle::target_full_weights()`]),
+/// which should be sorted in ascending order.
+///
+/// * If the weight is below the first full weight, the deformation is
+///   interpolated between the base geometry and the first target shape.
+///   Negative weights extrapolate it.
+/// * If the weight is between two full weights, the deformation is
+///   interpolated between the two in-between target shapes.
+/// * If the weight is above the last full weight, the deformation is
+///   extrapolated from the last two target shapes (or the base geometry and
+///   the target shape if there is only one).
+///
+/// Note that the `ViewScene` sample of FBX SDK applies no deformation for
+/// weights out of the range from 0 (exclusive) to the last full weight
+/// (inclusive). This function extrapolates them instead, so that
+/// exaggerated deformations (for example `DeformPercent` above 100) are
+/// reproduced. Clamp the weight beforehand to get the same result as the
+/// sample.
+///
+/// [`BlendShapeChannelHandle::target_full_weights()`]:
+/// ../../object/deformer/struct.BlendShapeChannelHandle.html#method.target_full_weights
+///
+/// # Examples
+///
+/// ```
+/// # use fbxcel_dom::v7400::data::deformer::target_weights;
+/// assert_eq!(target_weights(50.0, &[100.0]), vec![0.5]);
+/// assert_eq!(target_weights(25.0, &[50.0, 100.0]), vec![0.5, 0.0]);
+/// assert_eq!(target_weights(75.0, &[50.0, 100.0]), vec![0.5, 0.5]);
+/// assert_eq!(target_weights(150.0, &[100.0]), vec![1.5]);
+/// ```
+pub fn target_weights(weight: f64, full_weights: &[f64]) -> Vec<f64> {
+    let mut weights = vec![0.0; full_weights.len()];
+    if weight == 0.0 || full_weights.is_empty() {
+        return weights;
+    }
+    // Index of the segment end; the segment start is the base geometry if
+    // the end index is 0.
+    let end = if weight <= full_weights[0] {
+        0
+    } else {
+        full_weights
+            .iter()
+            .position(|&full| weight <= full)
+            .unwrap_or(full_weights.len() - 1)
+    };
+    let (start_weight, end_weight) = match end {
+        0 => (0.0, full_weights[0]),
+        end => (full_weights[end - 1], full_weights[end]),
+    };
+    let range = end_weight - start_weight;
+    if range == 0.0 {
+        weights[end] = 1.0;
+        return weights;
+    }
+    let t = (weight - start_weight) / range;
+    weights[end] = t;
+    if end > 0 {
+        weights[end - 1] = 1.0 - t;
+    }
+    weights
+}
+
+/// Dense morph target buffers.
+///
+/// Position and normal deltas are stored for each vertex of a vertex buffer,
+/// such as triangle vertices.
+/// This is suitable for GPU morph targets (such as glTF morph targets).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use fbxcel_dom::v7400::{data::mesh::TriangleVertices, object::deformer::BlendShapeHandle};
+/// use fbxcel_dom::v7400::data::deformer::MorphTargetBuffers;
+///
+/// # fn get_blendshape<'a>() -> BlendShapeHandle<'a> { unimplemented!() }
+/// # fn get_triangle_vertices<'a>() -> TriangleVertices<'a> { unimplemented!() }
+/// let blendshape: BlendShapeHandle<'_> = get_blendshape();
+/// // Triangle vertices of `blendshape.mesh()?`.
+/// let tris: TriangleVertices<'_> = get_triangle_vertices();
+/// for channel in blendshape.blendshape_channels() {
+///     // Morph target at 100% of the channel.
+///     let target = MorphTargetBuffers::from_channel_for_triangle_vertices(&channel, 100.0, &tris)?;
+///     assert_eq!(target.positions().len(), tris.len());
+/// }
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct MorphTargetBuffers {
+    /// Position deltas.
+    positions: Vec<[f32; 3]>,
+    /// Normal deltas.
+    normals: Option<Vec<[f32; 3]>>,
+}
+
+impl MorphTargetBuffers {
+    /// Creates morph target buffers from the shape.
+    ///
+    /// `vertex_control_points` are control point indices of the vertices in
+    /// the vertex buffer. Control points not listed in the shape have zero
+    /// deltas.
+    pub fn from_shape(
+        shape: &ShapeHandle<'_>,
+        vertex_control_points: &[ControlPointIndex],
+    ) -> Result<Self, Error> {
+        Self::from_weighted_shapes(std::iter::once((*shape, 1.0)), vertex_control_points)
+    }
+
+    /// Creates morph target buffers from the channel at the given weight
+    /// (deform percent).
+    ///
+    /// In-between target shapes are interpolated as [`target_weights()`] does.
+    ///
+    /// `vertex_control_points` are control point indices of the vertices in
+    /// the vertex buffer.
+    ///
+    /// [`target_weights()`]: fn.target_weights.html
+    pub fn from_channel(
+        channel: &BlendShapeChannelHandle<'_>,
+        weight: f64,
+        vertex_control_points: &[ControlPointIndex],
+    ) -> Result<Self, Error> {
+        let target_weights = target_weights(weight, &channel.target_full_weights()?);
+        Self::from_weighted_shapes(channel.shapes().zip(target_weights), vertex_control_points)
+    }
+
+    /// Creates morph target buffers aligned with the triangle vertices, from
+    /// the channel at the given weight (deform percent).
+    pub fn from_channel_for_triangle_vertices(
+        channel: &BlendShapeChannelHandle<'_>,
+        weight: f64,
+        tris: &TriangleVertices<'_>,
+    ) -> Result<Self, Error> {
+        let vertex_control_points = tris
+            .iter_control_point_indices()
+            .enumerate()
+            .map(|(i, cpi)| {
+                cpi.ok_or_else(|| {
+                    format_err!(
+                        "Failed to get control point index for triangle vertex {}",
+                        i
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_channel(channel, weight, &vertex_control_points)
+    }
+
+    /// Creates morph target buffers from the shapes and weights.
+    fn from_weighted_shapes<'a>(
+        shapes: impl Iterator<Item = (ShapeHandle<'a>, f64)>,
+        vertex_control_points: &[ControlPointIndex],
+    ) -> Result<Self, Error> {
+        let cp_count = vertex_control_points
+            .iter()
+            .map(|cpi| cpi.to_u32() as usize + 1)
+            .max()
+            .unwrap_or(0);
+        let mut position_deltas = vec![[0.0; 3]; cp_count];
+        let mut normal_deltas: Option<Vec<[f64; 3]>> = None;
+        for (shape, weight) in shapes {
+            if weight == 0.0 {
+                continue;
+            }
+            accumulate_deltas(&mut position_deltas, shape.vertex_deltas()?, weight);
+            if let Some(deltas) = shape.normal_deltas()? {
+                let normals = normal_deltas.get_or_insert_with(|| vec![[0.0; 3]; cp_count]);
+                accumulate_deltas(normals, deltas, weight);
+            }
+        }
+
+        let gather = |deltas: &[[f64; 3]]| {
+            vertex_control_points
+                .iter()
+                .map(|cpi| {
+                    let [x, y, z] = deltas[cpi.to_u32() as usize];
+                    [x as f32, y as f32, z as f32]
+                })
+                .collect::<Vec<_>>()
+        };
+        Ok(Self {
+            positions: gather(&position_deltas),
+            normals: normal_deltas.as_deref().map(gather),
+        })
+    }
+
+    /// Converts position and normal deltas to the target axis system.
+    ///
+    /// Use the same conversion as the vertex buffer (for example
+    /// [`IndexedMeshBuilder::axis_conversion()`]).
+    ///
+    /// [`IndexedMeshBuilder::axis_conversion()`]:
+    /// ../mesh/struct.IndexedMeshBuilder.html#method.axis_conversion
+    pub fn convert_axis(&mut self, conversion: &AxisConversion) {
+        let convert = |[x, y, z]: [f32; 3]| {
+            let v = conversion.convert_vector(Vector3 {
+                x: f64::from(x),
+                y: f64::from(y),
+                z: f64::from(z),
+            });
+            [v.x as f32, v.y as f32, v.z as f32]
+        };
+        for delta in self
+            .positions
+            .iter_mut()
+            .chain(self.normals.iter_mut().flatten())
+        {
+            *delta = convert(*delta);
+        }
+    }
+
+    /// Converts position deltas to the target unit.
+    ///
+    /// Use the same conversion as the vertex buffer (for example
+    /// [`IndexedMeshBuilder::unit_conversion()`]).
+    ///
+    /// [`IndexedMeshBuilder::unit_conversion()`]:
+    /// ../mesh/struct.IndexedMeshBuilder.html#method.unit_conversion
+    pub fn convert_unit(&mut self, conversion: &UnitConversion) {
+        let factor = conversion.factor() as f32;
+        for delta in self.positions.iter_mut().flatten() {
+            *delta *= factor;
+        }
+    }
+
+    /// Returns position deltas.
+    pub fn positions(&self) -> &[[f32; 3]] {
+        &self.positions
+    }
+
+    /// Returns normal deltas, if available.
+    pub fn normals(&self) -> Option<&[[f32; 3]]> {
+        self.normals.as_deref()
+    }
+
+    /// Returns position and normal deltas, consuming the buffers.
+    pub fn into_inner(self) -> (Vec<[f32; 3]>, Option<Vec<[f32; 3]>>) {
+        (self.positions, self.normals)
+    }
+}
+
+/// Adds weighted sparse deltas to the dense deltas.
+///
+/// Deltas for control points out of range of the dense deltas are ignored,
+/// since no vertices refer them.
+fn accumulate_deltas(
+    dense: &mut [[f64; 3]],
+    sparse: impl Iterator<Item = (ControlPointIndex, Vector3<f64>)>,
+    weight: f64,
+) {
+    for (cpi, delta) in sparse {
+        if let Some(target) = dense.get_mut(cpi.to_u32() as usize) {
+            target[0] += delta.x * weight;
+            target[1] += delta.y * weight;
+            target[2] += delta.z * weight;
+        }
+    }
+}
+
+/// Returns an error for out-of-range control point index.
+fn out_of_range(cpi: ControlPointIndex) -> Error {
+    format_err!("Control point index out of range: {:?}", cpi)
+}
@@ -0,0 +1,266 @@
+//! Skin data.
+
+use anyhow::{bail, Error};
+
+use crate::v7400::{
+    data::{
+        deformer::LinkMode,
+        mesh::{ControlPointIndex, TriangleVertices},
+    },
+    object::deformer::SkinHandle,
+};
+
+/// Influence of a cluster (joint) on a control point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Influence {
+    /// Cluster index.
+    cluster: usize,
+    /// Weight.
+    weight: f64,
+}
+
+impl Influence {
+    /// Returns the cluster index.
+    ///
+    /// This is the index in the order of [`SkinHandle::clusters()`].
+    ///
+    /// [`SkinHandle::clusters()`]:
+    /// ../../object/deformer/struct.SkinHandle.html#method.clusters
+    pub fn cluster(&self) -> usize {
+        self.cluster
+    }
+
+    /// Returns the weight.
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+}
+
+/// Per-control-point skin influences.
+///
+/// Influences for each control point are sorted by descending weight.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use fbxcel_dom::v7400::{data::mesh::TriangleVertices, object::deformer::SkinHandle};
+/// # fn get_skin<'a>() -> SkinHandle<'a> { unimplemented!() }
+/// # fn get_triangle_vertices<'a>() -> TriangleVertices<'a> { unimplemented!() }
+/// let skin: SkinHandle<'_> = get_skin();
+/// let mut influences = skin.influences()?;
+/// influences.remove_small(1e-4);
+/// influences.limit(4);
+/// influences.normalize();
+///
+/// // Triangle vertices of `skin.mesh()?`.
+/// let tris: TriangleVertices<'_> = get_triangle_vertices();
+/// let buffers = influences.triangle_vertex_buffers::<4>(&tris)?;
+/// assert_eq!(buffers.joints().len(), tris.len());
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SkinInfluences {
+    /// Influences for each control point.
+    influences: Vec<Vec<Influence>>,
+    /// Number of clusters.
+    cluster_count: usize,
+    /// Link mode.
+    link_mode: LinkMode,
+}
+
+impl SkinInfluences {
+    /// Creates a new `SkinInfluences` from the skin.
+    ///
+    /// The link mode of the first cluster is used for the whole skin, as
+    /// FBX SDK does.
+    pub fn from_skin(skin: &SkinHandle<'_>) -> Result<Self, Error> {
+        let control_point_count = skin.mesh()?.control_points()?.iter()?.count();
+        let mut influences = vec![Vec::new(); control_point_count];
+        let mut link_mode = None;
+        let mut cluster_count = 0;
+        for (cluster_i, cluster) in skin.clusters().enumerate() {
+            cluster_count += 1;
+            if link_mode.is_none() {
+                link_mode = Some(cluster.link_mode()?);
+            }
+            for (cpi, weight) in cluster.influences()? {
+                let target = match influences.get_mut(cpi.to_u32() as usize) {
+                    Some(v) => v,
+                    None => bail!(
+                        "Control point index out of range: index={:?}, count={}",
+                        cpi,
+                        control_point_count
+                    ),
+                };
+                if weight != 0.0 {
+                    target.push(Influence {
+                        cluster: cluster_i,
+                        weight,
+                    });
+                }
+            }
+        }
+        for cp_influences in &mut influences {
+            sort_influences(cp_influences);
+        }
+
+        Ok(Self {
+            influences,
+            cluster_count,
+            link_mode: link_mode.unwrap_or_default(),
+        })
+    }
+
+    /// Returns the number of control points.
+    pub fn control_point_count(&self) -> usize {
+        self.influences.len()
+    }
+
+    /// Returns the number of clusters.
+    pub fn cluster_count(&self) -> usize {
+        self.cluster_count
+    }
+
+    /// Returns the link mode.
+    pub fn link_mode(&self) -> LinkMode {
+        self.link_mode
+    }
+
+    /// Returns the influences on the given control point.
+    ///
+    /// Returns an empty slice for out-of-range index.
+    pub fn influences(&self, cpi: ControlPointIndex) -> &[Influence] {
+        self.influences
+            .get(cpi.to_u32() as usize)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the maximum number of influences on a control point.
+    pub fn max_influence_count(&self) -> usize {
+        self.influences.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// Returns the weight not assigned to any clusters, for the given control
+    /// point.
+    ///
+    /// This is meaningful for `TotalOne` link mode, where the remainder is
+    /// assigned to the original (undeformed) position.
+    pub fn rest_weight(&self, cpi: ControlPointIndex) -> f64 {
+        let sum: f64 = self.influences(cpi).iter().map(|i| i.weight).sum();
+        (1.0 - sum).max(0.0)
+    }
+
+    /// Normalizes the weights according to the link mode.
+    ///
+    /// * `Normalize`: weights are scaled so that the sum is 1.
+    /// * `Additive`: weights are left as is.
+    /// * `TotalOne`: weights are scaled only if the sum exceeds 1, so that
+    ///   [`rest_weight()`] is kept.
+    ///
+    /// [`rest_weight()`]: #method.rest_weight
+    pub fn normalize(&mut self) {
+        let link_mode = self.link_mode;
+        for cp_influences in &mut self.influences {
+            let sum: f64 = cp_influences.iter().map(|i| i.weight).sum();
+            let should_scale = match link_mode {
+                LinkMode::Normalize => sum > 0.0,
+                LinkMode::Additive => false,
+                LinkMode::TotalOne => sum > 1.0,
+            };
+            if should_scale {
+                cp_influences.iter_mut().for_each(|i| i.weight /= sum);
+            }
+        }
+    }
+
+    /// Removes influences with weights smaller than the given threshold.
+    pub fn remove_small(&mut self, min_weight: f64) {
+        for cp_influences in &mut self.influences {
+            cp_influences.retain(|i| i.weight.abs() >= min_weight);
+        }
+    }
+
+    /// Limits the number of influences per control point.
+    ///
+    /// Influences with larger weights are kept.
+    /// Call [`normalize()`] after this to redistribute the removed weights.
+    ///
+    /// [`normalize()`]: #method.normalize
+    pub fn limit(&mut self, max_influences: usize) {
+        for cp_influences in &mut self.influences {
+            cp_influences.truncate(max_influences);
+        }
+    }
+
+    /// Creates joint index and weight buffers for the triangle vertices.
+    ///
+    /// Each element corresponds to the triangle vertex of the same index.
+    /// Control points with less than `N` influences are padded with cluster
+    /// index 0 and weight 0.
+    /// Influences exceeding `N` are dropped, so [`limit()`] and
+    /// [`normalize()`] should be called beforehand.
+    ///
+    /// [`limit()`]: #method.limit
+    /// [`normalize()`]: #method.normalize
+    pub fn triangle_vertex_buffers<const N: usize>(
+        &self,
+        tris: &TriangleVertices<'_>,
+    ) -> Result<JointBuffers<N>, Error> {
+        let mut joints = Vec::with_capacity(tris.len());
+        let mut weights = Vec::with_capacity(tris.len());
+        for tri_vi in tris.triangle_vertex_indices() {
+            let cpi = match tris.control_point_index(tri_vi) {
+                Some(v) => v,
+                None => bail!(
+                    "Failed to get control point index for triangle vertex {:?}",
+                    tri_vi
+                ),
+            };
+            let mut vertex_joints = [0; N];
+            let mut vertex_weights = [0.0; N];
+            for (i, influence) in self.influences(cpi).iter().take(N).enumerate() {
+                vertex_joints[i] = influence.cluster as u32;
+                vertex_weights[i] = influence.weight as f32;
+            }
+            joints.push(vertex_joints);
+            weights.push(vertex_weights);
+        }
+        Ok(JointBuffers { joints, weights })
+    }
+}
+
+/// Joint index and weight buffers, with `N` influences per vertex.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JointBuffers<const N: usize> {
+    /// Joint (cluster) indices.
+    joints: Vec<[u32; N]>,
+    /// Weights.
+    weights: Vec<[f32; N]>,
+}
+
+impl<const N: usize> JointBuffers<N> {
+    /// Returns joint (cluster) indices.
+    pub fn joints(&self) -> &[[u32; N]] {
+        &self.joints
+    }
+
+    /// Returns weights.
+    pub fn weights(&self) -> &[[f32; N]] {
+        &self.weights
+    }
+
+    /// Returns joint indices and weights, consuming the buffers.
+    pub fn into_inner(self) -> (Vec<[u32; N]>, Vec<[f32; N]>) {
+        (self.joints, self.weights)
+    }
+}
+
+/// Sorts influences by descending weight, and then by ascending cluster index.
+fn sort_influences(influences: &mut [Influence]) {
+    influences.sort_by(|a, b| {
+        b.weight
+            .partial_cmp(&a.weight)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.cluster.cmp(&b.cluster))
+    });
+}
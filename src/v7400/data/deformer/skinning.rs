@@ -0,0 +1,634 @@
+//! Skinning evaluation.
+
+use anyhow::{bail, format_err, Error};
+use mint::{ColumnMatrix4, Point3, Vector3};
+
+use crate::v7400::{
+    data::{
+        axis::AxisConversion,
+        deformer::{LinkMode, SkinInfluences, SkinningType},
+        mesh::{layer::normal::Normals, ControlPointIndex, TriangleVertices},
+        unit::UnitConversion,
+    },
+    math::{normalize, Mat4},
+    object::{deformer::SkinHandle, geometry, model::ModelHandle},
+};
+
+/// Skin deformation of control points at a pose.
+///
+/// This is the result of CPU skinning, which holds the deformed control points
+/// and per-control-point deformation matrices.
+///
+/// The deformation of each cluster is calculated as FBX SDK does:
+///
+/// ```text
+/// (mesh_world * geometric_transform)⁻¹ * joint_world * bind_matrix⁻¹
+///     * transform * geometric_transform
+/// ```
+///
+/// where `bind_matrix` is [`ClusterHandle::bind_matrix()`], `transform` is
+/// [`ClusterHandle::transform()`], and `geometric_transform` is the geometric
+/// transform of the mesh model.
+/// The deformed control points are in the same space as the original control
+/// points, i.e. they are not deformed at the bind pose.
+///
+/// The skinning method is selected by [`SkinHandle::skinning_type()`].
+/// Note that dual quaternion skinning ignores scaling of joints.
+/// Skins with `LinkMode::Additive` are not supported.
+///
+/// To deform in another axis system or unit, use [`SkinDeformationBuilder`].
+///
+/// [`SkinDeformationBuilder`]: struct.SkinDeformationBuilder.html
+/// [`ClusterHandle::bind_matrix()`]:
+/// ../../object/deformer/struct.ClusterHandle.html#method.bind_matrix
+/// [`ClusterHandle::transform()`]:
+/// ../../object/deformer/struct.ClusterHandle.html#method.transform
+/// [`SkinHandle::skinning_type()`]:
+/// ../../object/deformer/struct.SkinHandle.html#method.skinning_type
+///
+/// # Examples
+///
+/// ```no_run
+/// # use fbxcel_dom::v7400::object::geometry::MeshHandle;
+/// use fbxcel_dom::v7400::{data::deformer::SkinDeformation, object::model::TransformEvaluator};
+///
+/// # fn get_mesh<'a>() -> MeshHandle<'a> { unimplemented!() }
+/// let mesh: MeshHandle<'_> = get_mesh();
+/// let mesh_model = mesh.models().next().expect("Mesh should have a model");
+///
+/// // Deform at the rest pose.
+/// let mut evaluator = TransformEvaluator::new(mesh.document());
+/// let mesh_world = evaluator.world_transform(&mesh_model)?;
+/// let deformation =
+///     SkinDeformation::from_mesh(&mesh, mesh_world, |joint| evaluator.world_transform(&joint))?;
+/// println!("{:?}", deformation.control_points());
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SkinDeformation {
+    /// Deformed control points.
+    control_points: Vec<Point3<f64>>,
+    /// Deformation matrices for each control point.
+    matrices: Vec<Mat4>,
+    /// Axis conversion applied to source normals.
+    axis_conversion: Option<AxisConversion>,
+}
+
+impl SkinDeformation {
+    /// Deforms the mesh by the first skin.
+    ///
+    /// If the mesh has no skins, control points are not deformed.
+    ///
+    /// This is a shorthand for
+    /// `SkinDeformationBuilder::new().build(mesh, mesh_world, joint_world)`.
+    /// See [`from_skin()`] for the arguments.
+    ///
+    /// [`from_skin()`]: #method.from_skin
+    pub fn from_mesh<'a, F>(
+        mesh: &geometry::MeshHandle<'a>,
+        mesh_world: ColumnMatrix4<f64>,
+        joint_world: F,
+    ) -> Result<Self, Error>
+    where
+        F: FnMut(ModelHandle<'a>) -> Result<ColumnMatrix4<f64>, Error>,
+    {
+        SkinDeformationBuilder::new().build(mesh, mesh_world, joint_world)
+    }
+
+    /// Deforms the mesh by the skin.
+    ///
+    /// `mesh_world` is the current world transform of the mesh model, and
+    /// `joint_world` should return the current world transform of the given
+    /// joint (linked model).
+    /// To deform at the rest pose, use world transforms at rest (for example
+    /// calculated by [`TransformEvaluator`]).
+    ///
+    /// This is a shorthand for
+    /// `SkinDeformationBuilder::new().build_for_skin(skin, mesh_world, joint_world)`.
+    ///
+    /// [`TransformEvaluator`]: ../../object/model/struct.TransformEvaluator.html
+    pub fn from_skin<'a, F>(
+        skin: &SkinHandle<'a>,
+        mesh_world: ColumnMatrix4<f64>,
+        joint_world: F,
+    ) -> Result<Self, Error>
+    where
+        F: FnMut(ModelHandle<'a>) -> Result<ColumnMatrix4<f64>, Error>,
+    {
+        SkinDeformationBuilder::new().build_for_skin(skin, mesh_world, joint_world)
+    }
+
+    /// Returns the deformed control points.
+    pub fn control_points(&self) -> &[Point3<f64>] {
+        &self.control_points
+    }
+
+    /// Returns the deformed control point at the given index.
+    pub fn control_point(&self, cpi: ControlPointIndex) -> Option<Point3<f64>> {
+        self.control_points.get(cpi.to_u32() as usize).copied()
+    }
+
+    /// Returns the deformation matrix of the control point.
+    pub fn matrix(&self, cpi: ControlPointIndex) -> Option<ColumnMatrix4<f64>> {
+        self.matrices.get(cpi.to_u32() as usize).map(|&m| m.into())
+    }
+
+    /// Deforms the normal at the given control point.
+    ///
+    /// `normal` is in the axis system of the document, and is converted by the
+    /// axis conversion of [`SkinDeformationBuilder`] before deformation.
+    /// The result is normalized.
+    ///
+    /// [`SkinDeformationBuilder`]: struct.SkinDeformationBuilder.html
+    pub fn deform_normal(
+        &self,
+        cpi: ControlPointIndex,
+        normal: Vector3<f64>,
+    ) -> Option<Vector3<f64>> {
+        let m = self.matrices.get(cpi.to_u32() as usize)?;
+        let normal = self
+            .axis_conversion
+            .map_or(normal, |c| c.convert_normal(normal));
+        let normal_matrix = m.linear_part().inverse()?.transpose();
+        Some(normalize(normal_matrix.transform_vector(normal)))
+    }
+
+    /// Returns deformed normals for each triangle vertex.
+    ///
+    /// See [`deform_normal()`] for the conversion of the normals.
+    ///
+    /// [`deform_normal()`]: #method.deform_normal
+    pub fn deform_normals(
+        &self,
+        tris: &TriangleVertices<'_>,
+        normals: &Normals<'_>,
+    ) -> Result<Vec<Vector3<f64>>, Error> {
+        tris.triangle_vertex_indices()
+            .map(|tri_vi| {
+                let cpi = tris.control_point_index(tri_vi).ok_or_else(|| {
+                    format_err!("Failed to get control point index: {:?}", tri_vi)
+                })?;
+                let normal = normals.normal(tris, tri_vi)?;
+                self.deform_normal(cpi, normal).ok_or_else(|| {
+                    format_err!("Failed to deform normal at control point {:?}", cpi)
+                })
+            })
+            .collect()
+    }
+}
+
+/// Builder of a skin deformation.
+///
+/// If an axis conversion or a unit conversion is set, world transforms passed
+/// to the builder should be in the target axis system and unit (for example
+/// calculated by [`TransformEvaluator`] with the same conversions), and the
+/// deformed control points and deformation matrices are in the target axis
+/// system and unit.
+/// Bind matrices, cluster transforms, and the geometric transform are
+/// converted by the builder, and source normals are converted by
+/// [`SkinDeformation::deform_normal()`].
+///
+/// [`TransformEvaluator`]: ../../object/model/struct.TransformEvaluator.html
+/// [`SkinDeformation::deform_normal()`]: struct.SkinDeformation.html#method.deform_normal
+///
+/// # Examples
+///
+/// ```no_run
+/// # use fbxcel_dom::v7400::{
+/// #     data::{axis::AxisSystem, unit::Unit},
+/// #     object::geometry::MeshHandle,
+/// # };
+/// use fbxcel_dom::v7400::{
+///     data::deformer::SkinDeformationBuilder, object::model::TransformEvaluator,
+/// };
+///
+/// # fn get_mesh<'a>() -> MeshHandle<'a> { unimplemented!() }
+/// let mesh: MeshHandle<'_> = get_mesh();
+/// let mesh_model = mesh.models().next().expect("Mesh should have a model");
+/// let settings = mesh
+///     .document()
+///     .global_settings()
+///     .expect("Document should have global settings");
+/// let axis_conversion = settings.axis_conversion_to(&AxisSystem::default())?;
+/// let unit_conversion = settings.unit_conversion_to(Unit::Meter)?;
+///
+/// let mut evaluator = TransformEvaluator::new(mesh.document());
+/// evaluator
+///     .set_axis_conversion(Some(axis_conversion))
+///     .set_unit_conversion(Some(unit_conversion));
+/// let mesh_world = evaluator.world_transform(&mesh_model)?;
+/// let deformation = SkinDeformationBuilder::new()
+///     .axis_conversion(Some(axis_conversion))
+///     .unit_conversion(Some(unit_conversion))
+///     .build(&mesh, mesh_world, |joint| evaluator.world_transform(&joint))?;
+/// println!("{:?}", deformation.control_points());
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct SkinDeformationBuilder {
+    /// Axis conversion.
+    axis_conversion: Option<AxisConversion>,
+    /// Unit conversion.
+    unit_conversion: Option<UnitConversion>,
+}
+
+impl SkinDeformationBuilder {
+    /// Creates a new `SkinDeformationBuilder`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the axis conversion.
+    pub fn axis_conversion(&mut self, conversion: Option<AxisConversion>) -> &mut Self {
+        self.axis_conversion = conversion;
+        self
+    }
+
+    /// Sets the unit conversion.
+    pub fn unit_conversion(&mut self, conversion: Option<UnitConversion>) -> &mut Self {
+        self.unit_conversion = conversion;
+        self
+    }
+
+    /// Deforms the mesh by the first skin.
+    ///
+    /// If the mesh has no skins, control points are not deformed.
+    ///
+    /// See [`SkinDeformation::from_skin()`] for the arguments.
+    ///
+    /// [`SkinDeformation::from_skin()`]: struct.SkinDeformation.html#method.from_skin
+    pub fn build<'a, F>(
+        &self,
+        mesh: &geometry::MeshHandle<'a>,
+        mesh_world: ColumnMatrix4<f64>,
+        joint_world: F,
+    ) -> Result<SkinDeformation, Error>
+    where
+        F: FnMut(ModelHandle<'a>) -> Result<ColumnMatrix4<f64>, Error>,
+    {
+        match mesh.skins().next() {
+            Some(skin) => self.build_for_skin(&skin, mesh_world, joint_world),
+            None => {
+                let control_points = mesh
+                    .control_points()?
+                    .iter()?
+                    .map(|p| self.convert_point(p))
+                    .collect::<Vec<_>>();
+                let matrices = vec![Mat4::IDENTITY; control_points.len()];
+                Ok(SkinDeformation {
+                    control_points,
+                    matrices,
+                    axis_conversion: self.axis_conversion,
+                })
+            }
+        }
+    }
+
+    /// Deforms the mesh by the skin.
+    ///
+    /// See [`SkinDeformation::from_skin()`] for the arguments.
+    ///
+    /// [`SkinDeformation::from_skin()`]: struct.SkinDeformation.html#method.from_skin
+    pub fn build_for_skin<'a, F>(
+        &self,
+        skin: &SkinHandle<'a>,
+        mesh_world: ColumnMatrix4<f64>,
+        mut joint_world: F,
+    ) -> Result<SkinDeformation, Error>
+    where
+        F: FnMut(ModelHandle<'a>) -> Result<ColumnMatrix4<f64>, Error>,
+    {
+        let influences = SkinInfluences::from_skin(skin)?;
+        let normalize = match influences.link_mode() {
+            LinkMode::Normalize => true,
+            LinkMode::TotalOne => false,
+            LinkMode::Additive => bail!("Additive link mode is not supported: skin={:?}", skin),
+        };
+
+        let mesh = skin.mesh()?;
+        let geometric = match mesh.models().next() {
+            Some(model) => self.convert_matrix(model.properties().geometric_transform()?),
+            None => Mat4::IDENTITY,
+        };
+        let mesh_world_inv = (Mat4::from(mesh_world) * geometric)
+            .inverse()
+            .ok_or_else(|| format_err!("Singular world transform of the mesh"))?;
+
+        let cluster_matrices = skin
+            .clusters()
+            .map(|cluster| {
+                let joint = match cluster.link_model() {
+                    Some(v) => v,
+                    None => return Ok(None),
+                };
+                let bind = match cluster.bind_matrix()? {
+                    Some(v) => self.convert_matrix(v),
+                    None => bail!("Bind matrix not found for cluster: object={:?}", cluster),
+                };
+                let bind_inv = bind
+                    .inverse()
+                    .ok_or_else(|| format_err!("Singular bind matrix: object={:?}", cluster))?;
+                let transform = cluster
+                    .transform()?
+                    .map_or(Mat4::IDENTITY, |m| self.convert_matrix(m));
+                let current = Mat4::from(joint_world(joint)?);
+                Ok(Some(
+                    mesh_world_inv * current * bind_inv * transform * geometric,
+                ))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let cluster_dual_quats = cluster_matrices
+            .iter()
+            .map(|m| m.as_ref().map(DualQuat::from_matrix))
+            .collect::<Vec<_>>();
+
+        let skinning_type = skin.skinning_type()?;
+        let mut blend_weights = vec![0.0; influences.control_point_count()];
+        if skinning_type == SkinningType::Blend {
+            for (cpi, weight) in skin.control_point_blend_weights()? {
+                if let Some(v) = blend_weights.get_mut(cpi.to_u32() as usize) {
+                    *v = weight;
+                }
+            }
+        }
+
+        let original = mesh.control_points()?.iter()?.collect::<Vec<_>>();
+        let mut control_points = Vec::with_capacity(original.len());
+        let mut matrices = Vec::with_capacity(original.len());
+        for (i, point) in original.into_iter().enumerate() {
+            let cp_influences = influences
+                .influences(ControlPointIndex::new(i as u32))
+                .iter()
+                .filter_map(|inf| {
+                    let m = cluster_matrices.get(inf.cluster())?.as_ref()?;
+                    let dq = cluster_dual_quats.get(inf.cluster())?.as_ref()?;
+                    Some((m, dq, inf.weight()))
+                });
+            let linear = || linear_blend(cp_influences.clone(), normalize);
+            let dual_quat = || dual_quaternion_blend(cp_influences.clone(), normalize).to_matrix();
+            let matrix = match skinning_type {
+                SkinningType::Rigid | SkinningType::Linear => linear(),
+                SkinningType::DualQuaternion => dual_quat(),
+                SkinningType::Blend => {
+                    let blend = blend_weights[i];
+                    if blend <= 0.0 {
+                        linear()
+                    } else if blend >= 1.0 {
+                        dual_quat()
+                    } else {
+                        lerp_matrix(&linear(), &dual_quat(), blend)
+                    }
+                }
+            };
+            control_points.push(matrix.transform_point(self.convert_point(point)));
+            matrices.push(matrix);
+        }
+
+        Ok(SkinDeformation {
+            control_points,
+            matrices,
+            axis_conversion: self.axis_conversion,
+        })
+    }
+
+    /// Converts the transform matrix in the axis system and the unit of the
+    /// document.
+    fn convert_matrix(&self, m: ColumnMatrix4<f64>) -> Mat4 {
+        let mut m = m;
+        if let Some(conversion) = &self.unit_conversion {
+            m = conversion.convert_matrix(m);
+        }
+        if let Some(conversion) = &self.axis_conversion {
+            m = conversion.convert_matrix(m);
+        }
+        m.into()
+    }
+
+    /// Converts the point in the axis system and the unit of the document.
+    fn convert_point(&self, p: Point3<f64>) -> Point3<f64> {
+        let mut p = p;
+        if let Some(conversion) = &self.unit_conversion {
+            p = conversion.convert_point(p);
+        }
+        if let Some(conversion) = &self.axis_conversion {
+            p = conversion.convert_point(p);
+        }
+        p
+    }
+}
+
+/// Blends the cluster matrices linearly.
+///
+/// If `normalize` is false, the remainder of the weights is assigned to the
+/// identity (i.e. `LinkMode::TotalOne`).
+fn linear_blend<'b>(
+    influences: impl Iterator<Item = (&'b Mat4, &'b DualQuat, f64)>,
+    normalize: bool,
+) -> Mat4 {
+    let mut sum = Mat4([[0.0; 4]; 4]);
+    let mut total_weight = 0.0;
+    for (m, _, weight) in influences {
+        add_scaled_matrix(&mut sum, m, weight);
+        total_weight += weight;
+    }
+    if total_weight == 0.0 {
+        return Mat4::IDENTITY;
+    }
+    if normalize {
+        let mut normalized = Mat4([[0.0; 4]; 4]);
+        add_scaled_matrix(&mut normalized, &sum, 1.0 / total_weight);
+        normalized
+    } else {
+        add_scaled_matrix(&mut sum, &Mat4::IDENTITY, 1.0 - total_weight);
+        sum
+    }
+}
+
+/// Blends the cluster dual quaternions.
+///
+/// If `normalize` is false, the remainder of the weights is assigned to the
+/// identity (i.e. `LinkMode::TotalOne`).
+fn dual_quaternion_blend<'b>(
+    influences: impl Iterator<Item = (&'b Mat4, &'b DualQuat, f64)>,
+    normalize: bool,
+) -> DualQuat {
+    let mut sum = DualQuat::ZERO;
+    let mut first = None;
+    let mut total_weight = 0.0;
+    for (_, dq, weight) in influences {
+        // Use the shortest path relative to the first influence.
+        let pivot = *first.get_or_insert(dq.real);
+        let sign = if quat_dot(&pivot, &dq.real) < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        sum.add_scaled(dq, weight * sign);
+        total_weight += weight;
+    }
+    if total_weight == 0.0 {
+        return DualQuat::IDENTITY;
+    }
+    // If normalized, the result is normalized on conversion to a matrix.
+    if !normalize {
+        let identity = DualQuat::IDENTITY;
+        let sign = if quat_dot(&sum.real, &identity.real) < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        sum.add_scaled(&identity, (1.0 - total_weight) * sign);
+    }
+    sum
+}
+
+/// Adds `m * weight` to `sum`.
+fn add_scaled_matrix(sum: &mut Mat4, m: &Mat4, weight: f64) {
+    for (sum_col, m_col) in sum.0.iter_mut().zip(&m.0) {
+        for (sum_v, m_v) in sum_col.iter_mut().zip(m_col) {
+            *sum_v += m_v * weight;
+        }
+    }
+}
+
+/// Linearly interpolates matrices.
+fn lerp_matrix(a: &Mat4, b: &Mat4, t: f64) -> Mat4 {
+    let mut m = Mat4([[0.0; 4]; 4]);
+    add_scaled_matrix(&mut m, a, 1.0 - t);
+    add_scaled_matrix(&mut m, b, t);
+    m
+}
+
+/// Quaternion, in `[x, y, z, w]` order.
+type Quat = [f64; 4];
+
+/// Returns the dot product of the quaternions.
+fn quat_dot(a: &Quat, b: &Quat) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+/// Returns the Hamilton product of the quaternions.
+fn quat_mul(a: &Quat, b: &Quat) -> Quat {
+    let [ax, ay, az, aw] = *a;
+    let [bx, by, bz, bw] = *b;
+    [
+        aw * bx + ax * bw + ay * bz - az * by,
+        aw * by - ax * bz + ay * bw + az * bx,
+        aw * bz + ax * by - ay * bx + az * bw,
+        aw * bw - ax * bx - ay * by - az * bz,
+    ]
+}
+
+/// Unit dual quaternion, representing a rigid transform.
+#[derive(Debug, Clone, Copy)]
+struct DualQuat {
+    /// Real part (rotation).
+    real: Quat,
+    /// Dual part (translation).
+    dual: Quat,
+}
+
+impl DualQuat {
+    /// Zero.
+    const ZERO: Self = Self {
+        real: [0.0; 4],
+        dual: [0.0; 4],
+    };
+
+    /// Identity.
+    const IDENTITY: Self = Self {
+        real: [0.0, 0.0, 0.0, 1.0],
+        dual: [0.0; 4],
+    };
+
+    /// Creates a dual quaternion from the rotation and translation of the
+    /// matrix.
+    ///
+    /// Scaling and shear are ignored.
+    fn from_matrix(m: &Mat4) -> Self {
+        let r = m.rotation_part();
+        // `r(row, col)`.
+        let r = |row: usize, col: usize| r.0[col][row];
+        let trace = r(0, 0) + r(1, 1) + r(2, 2);
+        let real = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            [
+                (r(2, 1) - r(1, 2)) / s,
+                (r(0, 2) - r(2, 0)) / s,
+                (r(1, 0) - r(0, 1)) / s,
+                0.25 * s,
+            ]
+        } else if r(0, 0) > r(1, 1) && r(0, 0) > r(2, 2) {
+            let s = (1.0 + r(0, 0) - r(1, 1) - r(2, 2)).sqrt() * 2.0;
+            [
+                0.25 * s,
+                (r(0, 1) + r(1, 0)) / s,
+                (r(0, 2) + r(2, 0)) / s,
+                (r(2, 1) - r(1, 2)) / s,
+            ]
+        } else if r(1, 1) > r(2, 2) {
+            let s = (1.0 + r(1, 1) - r(0, 0) - r(2, 2)).sqrt() * 2.0;
+            [
+                (r(0, 1) + r(1, 0)) / s,
+                0.25 * s,
+                (r(1, 2) + r(2, 1)) / s,
+                (r(0, 2) - r(2, 0)) / s,
+            ]
+        } else {
+            let s = (1.0 + r(2, 2) - r(0, 0) - r(1, 1)).sqrt() * 2.0;
+            [
+                (r(0, 2) + r(2, 0)) / s,
+                (r(1, 2) + r(2, 1)) / s,
+                0.25 * s,
+                (r(1, 0) - r(0, 1)) / s,
+            ]
+        };
+        let t = m.translation_part();
+        let dual = quat_mul(&[t.x, t.y, t.z, 0.0], &real);
+        Self {
+            real,
+            dual: [dual[0] * 0.5, dual[1] * 0.5, dual[2] * 0.5, dual[3] * 0.5],
+        }
+    }
+
+    /// Adds `dq * weight`.
+    fn add_scaled(&mut self, dq: &Self, weight: f64) {
+        for i in 0..4 {
+            self.real[i] += dq.real[i] * weight;
+            self.dual[i] += dq.dual[i] * weight;
+        }
+    }
+
+    /// Converts the (possibly non-normalized) dual quaternion to a matrix.
+    fn to_matrix(self) -> Mat4 {
+        let norm = quat_dot(&self.real, &self.real).sqrt();
+        if norm == 0.0 {
+            return Mat4::IDENTITY;
+        }
+        let [x, y, z, w] = self.real.map(|v| v / norm);
+        let dual = self.dual.map(|v| v / norm);
+        let t = quat_mul(&dual, &[-x, -y, -z, w]);
+
+        let mut m = Mat4::IDENTITY;
+        m.0[0] = [
+            1.0 - 2.0 * (y * y + z * z),
+            2.0 * (x * y + z * w),
+            2.0 * (x * z - y * w),
+            0.0,
+        ];
+        m.0[1] = [
+            2.0 * (x * y - z * w),
+            1.0 - 2.0 * (x * x + z * z),
+            2.0 * (y * z + x * w),
+            0.0,
+        ];
+        m.0[2] = [
+            2.0 * (x * z + y * w),
+            2.0 * (y * z - x * w),
+            1.0 - 2.0 * (x * x + y * y),
+            0.0,
+        ];
+        m.0[3] = [2.0 * t[0], 2.0 * t[1], 2.0 * t[2], 1.0];
+        m
+    }
+}
@@ -2,17 +2,32 @@
 
 pub use self::{
     control_point::ControlPointIndex,
+    edge::{EdgeIndex, Edges},
+    indexed::{IndexedMesh, IndexedMeshBuilder},
+    normal_generator::{GeneratedNormals, NormalGenerator, NormalWeighting},
     polygon_vertex_index::{
-        IntoCpiWithPolyVerts, PolygonIndex, PolygonVertex, PolygonVertexIndex, PolygonVertices,
+        IntoCpiWithPolyVerts, PolygonIndex, PolygonVertex, PolygonVertexIndex, PolygonVertexRange,
+        PolygonVertices,
     },
+    submesh::{PolygonSubmesh, Submesh},
+    tangent_generator::GeneratedTangents,
     triangle_vertex_index::{
         IntoCpiWithTriVerts, IntoPvWithTriVerts, TriangleIndex, TriangleVertexIndex,
         TriangleVertices,
     },
 };
-pub(crate) use self::{control_point::ControlPoints, polygon_vertex_index::RawPolygonVertices};
+pub(crate) use self::{
+    control_point::{control_point_indices, ControlPoints},
+    polygon_vertex_index::RawPolygonVertices,
+};
 
 mod control_point;
+mod edge;
+mod indexed;
 pub mod layer;
+mod normal_generator;
 mod polygon_vertex_index;
+mod submesh;
+mod tangent_generator;
 mod triangle_vertex_index;
+pub mod triangulator;
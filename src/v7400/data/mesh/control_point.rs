@@ -1,5 +1,6 @@
 //! Control point.
 
+use anyhow::{bail, Error};
 use mint::Point3;
 
 /// Control point index (in other words, polygon vertex).
@@ -24,6 +25,20 @@ impl ControlPointIndex {
     }
 }
 
+/// Returns an iterator of control point indices from the raw indices.
+///
+/// This fails if any of the indices is negative.
+/// `source` is used for the error message.
+pub(crate) fn control_point_indices<'a>(
+    raw: &'a [i32],
+    source: &str,
+) -> Result<impl Iterator<Item = ControlPointIndex> + 'a, Error> {
+    if let Some(&i) = raw.iter().find(|&&i| i < 0) {
+        bail!("Negative control point index in {}: {}", source, i);
+    }
+    Ok(raw.iter().map(|&i| ControlPointIndex::new(i as u32)))
+}
+
 /// Control points.
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct ControlPoints<'a> {
@@ -0,0 +1,217 @@
+//! Edge.
+
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::{bail, format_err, Error};
+
+use crate::v7400::data::mesh::{
+    ControlPointIndex, PolygonIndex, PolygonVertex, PolygonVertexIndex, PolygonVertices,
+};
+
+/// Edge index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EdgeIndex(usize);
+
+impl EdgeIndex {
+    /// Creates a new `EdgeIndex`.
+    pub(crate) fn new(i: usize) -> Self {
+        Self(i)
+    }
+
+    /// Returns the edge index.
+    pub fn to_usize(self) -> usize {
+        self.0
+    }
+}
+
+/// Edges of a mesh.
+///
+/// An edge is represented by a polygon vertex, and it connects the polygon
+/// vertex and the next polygon vertex in the same polygon.
+/// An edge shared by multiple polygons appears only once.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use fbxcel_dom::v7400::object::geometry::MeshHandle;
+/// # fn get_mesh<'a>() -> MeshHandle<'a> { unimplemented!() }
+/// let mesh: MeshHandle<'_> = get_mesh();
+/// let edges = mesh.edges()?;
+/// for edge in edges.iter() {
+///     let [start, end] = edges.control_point_indices(edge).expect("Should never fail");
+///     println!(
+///         "{:?} -> {:?}: polygons={:?}",
+///         start,
+///         end,
+///         edges.polygons(edge)
+///     );
+/// }
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Edges<'a> {
+    /// Polygon vertices.
+    polygon_vertices: PolygonVertices<'a>,
+    /// Raw edges (polygon vertex indices).
+    edges: &'a [i32],
+    /// Lookup tables.
+    tables: Arc<EdgeTables>,
+}
+
+impl<'a> Edges<'a> {
+    /// Creates a new `Edges`.
+    pub(crate) fn new(polygon_vertices: &PolygonVertices<'a>) -> Result<Self, Error> {
+        let edges = polygon_vertices
+            .raw_edges()
+            .ok_or_else(|| format_err!("`Edges` is not available for the mesh"))?;
+        let raw_pvs = polygon_vertices.raw_polygon_vertices();
+
+        let mut next_pvis = Vec::with_capacity(raw_pvs.len());
+        let mut pv_polys = Vec::with_capacity(raw_pvs.len());
+        for (poly_i, range) in polygon_vertices.polygon_ranges()?.into_iter().enumerate() {
+            let start = range.start;
+            let end = range.end;
+            next_pvis.extend(
+                range.map(|pvi| {
+                    PolygonVertexIndex::new(if pvi + 1 == end { start } else { pvi + 1 })
+                }),
+            );
+            pv_polys.extend((start..end).map(|_| PolygonIndex::new(poly_i)));
+        }
+
+        let cp_pair = |pvi: usize| {
+            let start = PolygonVertex::new(raw_pvs[pvi]).to_u32();
+            let end = PolygonVertex::new(raw_pvs[next_pvis[pvi].to_usize()]).to_u32();
+            (start.min(end), start.max(end))
+        };
+        let mut edges_by_pvi = HashMap::new();
+        let mut edges_by_cp = HashMap::new();
+        for (edge_i, &pvi) in edges.iter().enumerate() {
+            if pvi < 0 || pvi as usize >= raw_pvs.len() {
+                bail!(
+                    "Polygon vertex index out of range in `Edges`: edge={}, pvi={}",
+                    edge_i,
+                    pvi
+                );
+            }
+            let edge = EdgeIndex::new(edge_i);
+            edges_by_pvi.entry(pvi as usize).or_insert(edge);
+            edges_by_cp.entry(cp_pair(pvi as usize)).or_insert(edge);
+        }
+
+        let mut edge_polygons = vec![Vec::new(); edges.len()];
+        let pv_edges = (0..raw_pvs.len())
+            .map(|pvi| {
+                let edge = edges_by_pvi
+                    .get(&pvi)
+                    .or_else(|| edges_by_cp.get(&cp_pair(pvi)))
+                    .copied();
+                if let Some(edge) = edge {
+                    let polys: &mut Vec<PolygonIndex> = &mut edge_polygons[edge.to_usize()];
+                    if polys.last() != Some(&pv_polys[pvi]) {
+                        polys.push(pv_polys[pvi]);
+                    }
+                }
+                edge
+            })
+            .collect();
+
+        Ok(Self {
+            polygon_vertices: *polygon_vertices,
+            edges,
+            tables: Arc::new(EdgeTables {
+                next_pvis,
+                pv_edges,
+                edge_polygons,
+            }),
+        })
+    }
+
+    /// Creates a new `Edges` from the lookup tables built before.
+    pub(crate) fn with_tables(
+        polygon_vertices: &PolygonVertices<'a>,
+        tables: Arc<EdgeTables>,
+    ) -> Self {
+        Self {
+            polygon_vertices: *polygon_vertices,
+            edges: polygon_vertices
+                .raw_edges()
+                .expect("Should never fail: the tables are built from the edges"),
+            tables,
+        }
+    }
+
+    /// Returns the lookup tables.
+    pub(crate) fn tables(&self) -> &Arc<EdgeTables> {
+        &self.tables
+    }
+
+    /// Returns the number of edges.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns whether there are no edges.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Returns an iterator of edge indices.
+    pub fn iter(&self) -> impl Iterator<Item = EdgeIndex> {
+        (0..self.len()).map(EdgeIndex::new)
+    }
+
+    /// Returns a slice of the raw edges (polygon vertex indices).
+    pub fn raw_edges(&self) -> &'a [i32] {
+        self.edges
+    }
+
+    /// Returns the polygon vertex indices of the start and the end of the
+    /// edge.
+    pub fn polygon_vertex_indices(&self, edge: EdgeIndex) -> Option<[PolygonVertexIndex; 2]> {
+        let start = *self.edges.get(edge.to_usize())? as usize;
+        Some([PolygonVertexIndex::new(start), self.tables.next_pvis[start]])
+    }
+
+    /// Returns the control point indices of the start and the end of the
+    /// edge.
+    pub fn control_point_indices(&self, edge: EdgeIndex) -> Option<[ControlPointIndex; 2]> {
+        let [start, end] = self.polygon_vertex_indices(edge)?;
+        Some([
+            self.polygon_vertices.polygon_vertex(start)?.into(),
+            self.polygon_vertices.polygon_vertex(end)?.into(),
+        ])
+    }
+
+    /// Returns the edge from the given polygon vertex to the next polygon
+    /// vertex in the same polygon.
+    ///
+    /// Returns `None` if the edge is not in the edges.
+    pub fn edge_index(&self, pvi: PolygonVertexIndex) -> Option<EdgeIndex> {
+        self.tables.pv_edges.get(pvi.to_usize()).copied().flatten()
+    }
+
+    /// Returns the polygons adjacent to the edge, in ascending order.
+    ///
+    /// Returns an empty slice if the edge index is out of range.
+    pub fn polygons(&self, edge: EdgeIndex) -> &[PolygonIndex] {
+        self.tables
+            .edge_polygons
+            .get(edge.to_usize())
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Lookup tables of edges.
+///
+/// They have no references to the document, so they can be shared by
+/// `Edges` and caches.
+#[derive(Debug)]
+pub(crate) struct EdgeTables {
+    /// Next polygon vertex index of each polygon vertex in the same polygon.
+    next_pvis: Vec<PolygonVertexIndex>,
+    /// Edge of each polygon vertex.
+    pv_edges: Vec<Option<EdgeIndex>>,
+    /// Polygons adjacent to each edge.
+    edge_polygons: Vec<Vec<PolygonIndex>>,
+}
@@ -0,0 +1,498 @@
+//! Indexed mesh.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, format_err, Error};
+
+use crate::v7400::{
+    data::{
+        axis::AxisConversion,
+        mesh::{
+            layer::{
+                binormal::Binormals, color::Colors, normal::Normals, tangent::Tangents, uv::Uv,
+                LayerElementType, TypedLayerElementHandle,
+            },
+            ControlPointIndex, GeneratedTangents, TriangleVertices,
+        },
+        unit::UnitConversion,
+    },
+    math::{cross, dot},
+    object::geometry::MeshHandle,
+};
+
+/// Layer element selection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Selection {
+    /// No layer elements.
+    None,
+    /// Layer elements with the given type-local indices.
+    Indices(Vec<u32>),
+    /// All layer elements.
+    All,
+}
+
+/// Builder of an indexed mesh.
+///
+/// Vertices with the same control point and the same attributes are welded
+/// into a single vertex.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use fbxcel_dom::v7400::object::geometry::MeshHandle;
+/// use fbxcel_dom::v7400::data::mesh::triangulator;
+///
+/// # fn get_mesh<'a>() -> MeshHandle<'a> { unimplemented!() }
+/// let mesh: MeshHandle<'_> = get_mesh();
+/// let tris = mesh
+///     .polygon_vertices()?
+///     .triangulate_each(triangulator::ear_clipping)?;
+/// let indexed = mesh
+///     .indexed_mesh_builder()
+///     .normals(true)
+///     .all_uv_sets()
+///     .build(&tris)?;
+/// assert_eq!(indexed.indices().len(), tris.len());
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct IndexedMeshBuilder<'a> {
+    /// Mesh.
+    mesh: MeshHandle<'a>,
+    /// Whether to include normals.
+    normals: bool,
+    /// Whether to include tangents.
+    tangents: bool,
+    /// UV sets to include.
+    uv_sets: Selection,
+    /// Color sets to include.
+    color_sets: Selection,
+    /// Axis conversion.
+    axis_conversion: Option<AxisConversion>,
+    /// Unit conversion.
+    unit_conversion: Option<UnitConversion>,
+}
+
+impl<'a> IndexedMeshBuilder<'a> {
+    /// Creates a new `IndexedMeshBuilder`.
+    ///
+    /// By default, only positions are included.
+    pub fn new(mesh: &MeshHandle<'a>) -> Self {
+        Self {
+            mesh: *mesh,
+            normals: false,
+            tangents: false,
+            uv_sets: Selection::None,
+            color_sets: Selection::None,
+            axis_conversion: None,
+            unit_conversion: None,
+        }
+    }
+
+    /// Sets whether to include normals (of the first normal layer element).
+    ///
+    /// If the mesh has no normal layer elements, smooth normals are generated
+    /// by [`MeshHandle::normal_generator()`].
+    ///
+    /// [`MeshHandle::normal_generator()`]:
+    /// ../../object/geometry/struct.MeshHandle.html#method.normal_generator
+    pub fn normals(&mut self, enabled: bool) -> &mut Self {
+        self.normals = enabled;
+        self
+    }
+
+    /// Sets whether to include tangents.
+    ///
+    /// Tangents have the sign of the binormal as `w`, i.e. the binormal is
+    /// `w * cross(normal, tangent)`.
+    ///
+    /// Tangents of the first tangent and binormal layer elements are used if
+    /// available.
+    /// Otherwise, tangents are generated by [`GeneratedTangents`] from the
+    /// normals (see [`normals()`]) and the first included UV set (or the UV
+    /// set 0 if no UV sets are included).
+    ///
+    /// [`GeneratedTangents`]: struct.GeneratedTangents.html
+    /// [`normals()`]: #method.normals
+    pub fn tangents(&mut self, enabled: bool) -> &mut Self {
+        self.tangents = enabled;
+        self
+    }
+
+    /// Adds the UV set with the given type-local layer element index.
+    pub fn uv_set(&mut self, index: u32) -> &mut Self {
+        add_selection(&mut self.uv_sets, index);
+        self
+    }
+
+    /// Includes all UV sets.
+    pub fn all_uv_sets(&mut self) -> &mut Self {
+        self.uv_sets = Selection::All;
+        self
+    }
+
+    /// Adds the color set with the given type-local layer element index.
+    pub fn color_set(&mut self, index: u32) -> &mut Self {
+        add_selection(&mut self.color_sets, index);
+        self
+    }
+
+    /// Includes all color sets.
+    pub fn all_color_sets(&mut self) -> &mut Self {
+        self.color_sets = Selection::All;
+        self
+    }
+
+    /// Sets the axis conversion applied to positions, normals, and tangents.
+    ///
+    /// If the conversion changes handedness, the winding order of triangles
+    /// is also reversed (see [`IndexedMesh::indices()`]).
+    ///
+    /// [`IndexedMesh::indices()`]: struct.IndexedMesh.html#method.indices
+    pub fn axis_conversion(&mut self, conversion: Option<AxisConversion>) -> &mut Self {
+        self.axis_conversion = conversion;
+        self
+    }
+
+    /// Sets the unit conversion applied to positions.
+    pub fn unit_conversion(&mut self, conversion: Option<UnitConversion>) -> &mut Self {
+        self.unit_conversion = conversion;
+        self
+    }
+
+    /// Builds the indexed mesh from the triangle vertices of the mesh.
+    pub fn build(&self, tris: &TriangleVertices<'a>) -> Result<IndexedMesh, Error> {
+        let generated_normals;
+        let normals = if self.normals || self.tangents {
+            let normal_elem = self
+                .mesh
+                .layer_elements(LayerElementType::Normal)
+                .find_map(|elem| match elem {
+                    TypedLayerElementHandle::Normal(v) => Some(v),
+                    _ => None,
+                });
+            let normals = match normal_elem {
+                Some(elem) => elem.normals()?,
+                None => {
+                    generated_normals = self.mesh.normal_generator()?.generate()?;
+                    generated_normals.normals()
+                }
+            };
+            Some(normals)
+        } else {
+            None
+        };
+        let uvs = self
+            .select(LayerElementType::Uv, &self.uv_sets)?
+            .into_iter()
+            .map(|elem| match elem {
+                TypedLayerElementHandle::Uv(v) => v.uv(),
+                _ => unreachable!("Should never fail: selected by the type"),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let colors = self
+            .select(LayerElementType::Color, &self.color_sets)?
+            .into_iter()
+            .map(|elem| match elem {
+                TypedLayerElementHandle::Color(v) => v.color(),
+                _ => unreachable!("Should never fail: selected by the type"),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let generated_tangents;
+        let tangents = match (self.tangents, &normals) {
+            (true, Some(normals)) => {
+                let tangent_elem = self
+                    .mesh
+                    .layer_elements(LayerElementType::Tangent)
+                    .find_map(|elem| match elem {
+                        TypedLayerElementHandle::Tangent(v) => Some(v),
+                        _ => None,
+                    });
+                let binormal_elem = self
+                    .mesh
+                    .layer_elements(LayerElementType::Binormal)
+                    .find_map(|elem| match elem {
+                        TypedLayerElementHandle::Binormal(v) => Some(v),
+                        _ => None,
+                    });
+                let (tangents, binormals) = match (tangent_elem, binormal_elem) {
+                    (Some(tangent_elem), Some(binormal_elem)) => {
+                        (tangent_elem.tangents()?, binormal_elem.binormals()?)
+                    }
+                    _ => {
+                        let uv = match uvs.first() {
+                            Some(uv) => *uv,
+                            None => match self
+                                .select(LayerElementType::Uv, &Selection::Indices(vec![0]))?
+                                .first()
+                            {
+                                Some(TypedLayerElementHandle::Uv(v)) => v.uv()?,
+                                _ => unreachable!("Should never fail: selected by the type"),
+                            },
+                        };
+                        generated_tangents =
+                            GeneratedTangents::from_triangle_vertices(tris, normals, &uv)?;
+                        (
+                            generated_tangents.tangents(),
+                            generated_tangents.binormals(),
+                        )
+                    }
+                };
+                Some((*normals, tangents, binormals))
+            }
+            _ => None,
+        };
+        let normals = if self.normals { normals } else { None };
+
+        IndexedMesh::build(
+            tris,
+            normals.as_ref(),
+            tangents.as_ref(),
+            &uvs,
+            &colors,
+            self.axis_conversion.as_ref(),
+            self.unit_conversion.as_ref(),
+        )
+    }
+
+    /// Returns selected layer elements of the given type, sorted by the
+    /// type-local index.
+    fn select(
+        &self,
+        ty: LayerElementType<'a>,
+        selection: &Selection,
+    ) -> Result<Vec<TypedLayerElementHandle<'a>>, Error> {
+        let mut elements = self
+            .mesh
+            .layer_elements(ty)
+            .map(|elem| Ok((elem.typed_index()?.to_u32(), elem)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        elements.sort_by_key(|(index, _)| *index);
+        match selection {
+            Selection::None => Ok(Vec::new()),
+            Selection::All => Ok(elements.into_iter().map(|(_, elem)| elem).collect()),
+            Selection::Indices(indices) => indices
+                .iter()
+                .map(|&index| {
+                    elements
+                        .iter()
+                        .find(|(i, _)| *i == index)
+                        .map(|(_, elem)| *elem)
+                        .ok_or_else(|| {
+                            format_err!("Layer element not found: type={:?}, index={}", ty, index)
+                        })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Adds the index to the selection.
+fn add_selection(selection: &mut Selection, index: u32) {
+    match selection {
+        Selection::None => *selection = Selection::Indices(vec![index]),
+        Selection::Indices(indices) => {
+            if !indices.contains(&index) {
+                indices.push(index);
+            }
+        }
+        Selection::All => {}
+    }
+}
+
+/// Indexed mesh.
+///
+/// This is a deduplicated vertex buffer and an index buffer of triangles.
+/// All attributes of a vertex are stored at the same index of the arrays.
+#[derive(Debug, Clone)]
+pub struct IndexedMesh {
+    /// Indices of triangle vertices.
+    indices: Vec<u32>,
+    /// Control point indices.
+    control_point_indices: Vec<ControlPointIndex>,
+    /// Positions.
+    positions: Vec<[f32; 3]>,
+    /// Normals.
+    normals: Option<Vec<[f32; 3]>>,
+    /// Tangents.
+    tangents: Option<Vec<[f32; 4]>>,
+    /// UV sets.
+    uv_sets: Vec<Vec<[f32; 2]>>,
+    /// Color sets.
+    color_sets: Vec<Vec<[f32; 4]>>,
+}
+
+impl IndexedMesh {
+    /// Builds the indexed mesh.
+    fn build(
+        tris: &TriangleVertices<'_>,
+        normals: Option<&Normals<'_>>,
+        tangents: Option<&(Normals<'_>, Tangents<'_>, Binormals<'_>)>,
+        uvs: &[Uv<'_>],
+        colors: &[Colors<'_>],
+        axis_conversion: Option<&AxisConversion>,
+        unit_conversion: Option<&UnitConversion>,
+    ) -> Result<Self, Error> {
+        let convert_point = |p| {
+            let p = unit_conversion.map_or(p, |c| c.convert_point(p));
+            axis_conversion.map_or(p, |c| c.convert_point(p))
+        };
+        let convert_normal = |v| axis_conversion.map_or(v, |c| c.convert_normal(v));
+        let mut mesh = Self {
+            indices: Vec::with_capacity(tris.len()),
+            control_point_indices: Vec::new(),
+            positions: Vec::new(),
+            normals: normals.map(|_| Vec::new()),
+            tangents: tangents.map(|_| Vec::new()),
+            uv_sets: vec![Vec::new(); uvs.len()],
+            color_sets: vec![Vec::new(); colors.len()],
+        };
+        let mut vertex_indices = HashMap::new();
+        let mut key = Vec::new();
+        for tri_vi in tris.triangle_vertex_indices() {
+            let cpi = tris
+                .control_point_index(tri_vi)
+                .ok_or_else(|| format_err!("Failed to get control point index: {:?}", tri_vi))?;
+            let position = tris
+                .control_point(cpi)
+                .ok_or_else(|| format_err!("Failed to get control point: {:?}", cpi))?;
+            let position = convert_point(position);
+            let position = [position.x as f32, position.y as f32, position.z as f32];
+            let normal = normals
+                .map(|normals| {
+                    normals
+                        .normal(tris, tri_vi)
+                        .map(convert_normal)
+                        .map(|n| [n.x as f32, n.y as f32, n.z as f32])
+                })
+                .transpose()?;
+            let tangent = tangents
+                .map(|(normals, tangents, binormals)| {
+                    let n = convert_normal(normals.normal(tris, tri_vi)?);
+                    let t = convert_normal(tangents.tangent(tris, tri_vi)?);
+                    let b = convert_normal(binormals.binormal(tris, tri_vi)?);
+                    let w = if dot(cross(n, t), b) < 0.0 { -1.0 } else { 1.0 };
+                    Ok::<_, Error>([t.x as f32, t.y as f32, t.z as f32, w])
+                })
+                .transpose()?;
+            let uv_values = uvs
+                .iter()
+                .map(|uv| uv.uv(tris, tri_vi).map(|v| [v.x as f32, v.y as f32]))
+                .collect::<Result<Vec<_>, _>>()?;
+            let color_values = colors
+                .iter()
+                .map(|color| {
+                    color
+                        .color(tris, tri_vi)
+                        .map(|[r, g, b, a]| [r as f32, g as f32, b as f32, a as f32])
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            key.clear();
+            key.push(cpi.to_u32());
+            key.extend(position.iter().copied().map(float_key));
+            key.extend(normal.iter().flatten().copied().map(float_key));
+            key.extend(tangent.iter().flatten().copied().map(float_key));
+            key.extend(uv_values.iter().flatten().copied().map(float_key));
+            key.extend(color_values.iter().flatten().copied().map(float_key));
+
+            let next_index = mesh.positions.len();
+            let index = *vertex_indices.entry(key.clone()).or_insert_with(|| {
+                mesh.control_point_indices.push(cpi);
+                mesh.positions.push(position);
+                if let (Some(normals), Some(normal)) = (&mut mesh.normals, normal) {
+                    normals.push(normal);
+                }
+                if let (Some(tangents), Some(tangent)) = (&mut mesh.tangents, tangent) {
+                    tangents.push(tangent);
+                }
+                for (set, value) in mesh.uv_sets.iter_mut().zip(&uv_values) {
+                    set.push(*value);
+                }
+                for (set, value) in mesh.color_sets.iter_mut().zip(&color_values) {
+                    set.push(*value);
+                }
+                next_index
+            });
+            if index > u32::MAX as usize {
+                bail!("Too many vertices: {}", index + 1);
+            }
+            mesh.indices.push(index as u32);
+        }
+        if axis_conversion.map_or(false, AxisConversion::flips_winding) {
+            for tri in mesh.indices.chunks_exact_mut(3) {
+                tri.swap(1, 2);
+            }
+        }
+
+        Ok(mesh)
+    }
+
+    /// Returns the number of vertices.
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns indices of triangle vertices.
+    ///
+    /// The `i`-th index corresponds to the `i`-th triangle vertex of the
+    /// source triangle vertices, except that the second and the third
+    /// vertices of each triangle are swapped if the axis conversion changes
+    /// handedness.
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Returns control point indices of the vertices.
+    ///
+    /// This is useful to map per-control-point data (such as skin weights and
+    /// blend shape deltas) to the vertices.
+    pub fn control_point_indices(&self) -> &[ControlPointIndex] {
+        &self.control_point_indices
+    }
+
+    /// Returns positions of the vertices.
+    pub fn positions(&self) -> &[[f32; 3]] {
+        &self.positions
+    }
+
+    /// Returns normals of the vertices, if included.
+    pub fn normals(&self) -> Option<&[[f32; 3]]> {
+        self.normals.as_deref()
+    }
+
+    /// Returns tangents of the vertices, if included.
+    ///
+    /// The sign of the binormal is stored as `w`.
+    pub fn tangents(&self) -> Option<&[[f32; 4]]> {
+        self.tangents.as_deref()
+    }
+
+    /// Returns UV sets of the vertices.
+    ///
+    /// UV sets are sorted by the type-local layer element index if all UV
+    /// sets are included, and in the order of addition otherwise.
+    pub fn uv_sets(&self) -> &[Vec<[f32; 2]>] {
+        &self.uv_sets
+    }
+
+    /// Returns color sets of the vertices.
+    ///
+    /// Color sets are sorted by the type-local layer element index if all
+    /// color sets are included, and in the order of addition otherwise.
+    pub fn color_sets(&self) -> &[Vec<[f32; 4]>] {
+        &self.color_sets
+    }
+}
+
+/// Returns the key of the float value for welding.
+///
+/// Negative zero is treated as same as positive zero.
+fn float_key(v: f32) -> u32 {
+    if v == 0.0 {
+        0
+    } else {
+        v.to_bits()
+    }
+}
@@ -8,17 +8,25 @@ use fbxcel::{low::v7400::AttributeValue, tree::v7400::NodeHandle};
 
 pub(crate) use self::common::LayerContentIndex;
 pub use self::{
+    binormal::LayerElementBinormalHandle,
     color::LayerElementColorHandle,
     common::{LayerElementHandle, MappingMode, ReferenceInformation, ReferenceMode},
+    generic::LayerElementGenericHandle,
     material::LayerElementMaterialHandle,
     normal::LayerElementNormalHandle,
+    smoothing::LayerElementSmoothingHandle,
+    tangent::LayerElementTangentHandle,
     uv::LayerElementUvHandle,
 };
 
+pub mod binormal;
 pub mod color;
 mod common;
+pub mod generic;
 pub mod material;
 pub mod normal;
+pub mod smoothing;
+pub mod tangent;
 pub mod uv;
 
 /// Layer node.
@@ -119,8 +127,12 @@ impl<'a> LayerElementEntryHandle<'a> {
     }
 
     /// Returns layer element type.
-    pub fn type_(&self) -> Result<LayerElementType, Error> {
-        self.type_str()?.parse()
+    ///
+    /// Unknown types are returned as [`LayerElementType::Other`].
+    ///
+    /// [`LayerElementType::Other`]: enum.LayerElementType.html#variant.Other
+    pub fn type_(&self) -> Result<LayerElementType<'a>, Error> {
+        self.type_str()?.try_into()
     }
 
     /// Returns the layer element index in the same type.
@@ -183,48 +195,80 @@ impl<'a> std::ops::Deref for LayerElementEntryHandle<'a> {
 
 /// Layer element type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum LayerElementType {
+pub enum LayerElementType<'a> {
+    /// Binormal.
+    Binormal,
     /// Color.
     Color,
     /// Material.
     Material,
     /// Normal.
     Normal,
+    /// Smoothing.
+    Smoothing,
+    /// Tangent.
+    Tangent,
     /// UV.
     Uv,
+    /// Other type, such as `LayerElementVisibility` and vendor-specific
+    /// layer elements.
+    ///
+    /// The value is the type name (node name).
+    Other(&'a str),
 }
 
-impl LayerElementType {
+impl<'a> LayerElementType<'a> {
     /// Returns type name.
-    pub fn type_name(self) -> &'static str {
+    pub fn type_name(self) -> &'a str {
         match self {
+            LayerElementType::Binormal => "LayerElementBinormal",
             LayerElementType::Color => "LayerElementColor",
             LayerElementType::Material => "LayerElementMaterial",
             LayerElementType::Normal => "LayerElementNormal",
+            LayerElementType::Smoothing => "LayerElementSmoothing",
+            LayerElementType::Tangent => "LayerElementTangent",
             LayerElementType::Uv => "LayerElementUV",
+            LayerElementType::Other(name) => name,
+        }
+    }
+
+    /// Returns the known type for the given type name.
+    fn known(s: &str) -> Option<LayerElementType<'static>> {
+        match s {
+            "LayerElementBinormal" => Some(LayerElementType::Binormal),
+            "LayerElementColor" => Some(LayerElementType::Color),
+            "LayerElementMaterial" => Some(LayerElementType::Material),
+            "LayerElementNormal" => Some(LayerElementType::Normal),
+            "LayerElementSmoothing" => Some(LayerElementType::Smoothing),
+            "LayerElementTangent" => Some(LayerElementType::Tangent),
+            "LayerElementUV" => Some(LayerElementType::Uv),
+            _ => None,
         }
     }
 }
 
-impl TryFrom<&str> for LayerElementType {
+impl<'a> TryFrom<&'a str> for LayerElementType<'a> {
     type Error = Error;
 
-    fn try_from(s: &str) -> Result<Self, Self::Error> {
-        match s {
-            "LayerElementColor" => Ok(LayerElementType::Color),
-            "LayerElementMaterial" => Ok(LayerElementType::Material),
-            "LayerElementNormal" => Ok(LayerElementType::Normal),
-            "LayerElementUV" => Ok(LayerElementType::Uv),
-            _ => Err(format_err!("Unknown layer element type: {:?}", s)),
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        if s.is_empty() {
+            bail!("Empty layer element type");
         }
+        Ok(LayerElementType::known(s).unwrap_or(LayerElementType::Other(s)))
     }
 }
 
-impl std::str::FromStr for LayerElementType {
+/// Parses a known layer element type.
+///
+/// As the parsed value cannot borrow the string, this fails for unknown types.
+/// Use `TryFrom<&str>` to get [`LayerElementType::Other`] for them.
+///
+/// [`LayerElementType::Other`]: enum.LayerElementType.html#variant.Other
+impl std::str::FromStr for LayerElementType<'static> {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.try_into()
+        LayerElementType::known(s).ok_or_else(|| format_err!("Unknown layer element type: {:?}", s))
     }
 }
 
@@ -253,21 +297,32 @@ impl LayerElementIndex {
 /// Typed layer element.
 #[derive(Debug, Clone, Copy)]
 pub enum TypedLayerElementHandle<'a> {
+    /// Binormal.
+    Binormal(LayerElementBinormalHandle<'a>),
     /// Color.
     Color(LayerElementColorHandle<'a>),
     /// Material.
     Material(LayerElementMaterialHandle<'a>),
     /// Normal.
     Normal(LayerElementNormalHandle<'a>),
+    /// Smoothing.
+    Smoothing(LayerElementSmoothingHandle<'a>),
+    /// Tangent.
+    Tangent(LayerElementTangentHandle<'a>),
     /// UV.
     Uv(LayerElementUvHandle<'a>),
+    /// Other type.
+    Other(LayerElementGenericHandle<'a>),
 }
 
 impl<'a> TypedLayerElementHandle<'a> {
     /// Creates a new `TypedLayerElementHandle`.
-    fn new(ty: LayerElementType, node: NodeHandle<'a>) -> Self {
+    pub(crate) fn new(ty: LayerElementType<'_>, node: NodeHandle<'a>) -> Self {
         let base = LayerElementHandle::new(node);
         match ty {
+            LayerElementType::Binormal => {
+                TypedLayerElementHandle::Binormal(LayerElementBinormalHandle::new(base))
+            }
             LayerElementType::Color => {
                 TypedLayerElementHandle::Color(LayerElementColorHandle::new(base))
             }
@@ -277,7 +332,16 @@ impl<'a> TypedLayerElementHandle<'a> {
             LayerElementType::Normal => {
                 TypedLayerElementHandle::Normal(LayerElementNormalHandle::new(base))
             }
+            LayerElementType::Smoothing => {
+                TypedLayerElementHandle::Smoothing(LayerElementSmoothingHandle::new(base))
+            }
+            LayerElementType::Tangent => {
+                TypedLayerElementHandle::Tangent(LayerElementTangentHandle::new(base))
+            }
             LayerElementType::Uv => TypedLayerElementHandle::Uv(LayerElementUvHandle::new(base)),
+            LayerElementType::Other(_) => {
+                TypedLayerElementHandle::Other(LayerElementGenericHandle::new(base))
+            }
         }
     }
 }
@@ -287,10 +351,14 @@ impl<'a> std::ops::Deref for TypedLayerElementHandle<'a> {
 
     fn deref(&self) -> &Self::Target {
         match self {
+            TypedLayerElementHandle::Binormal(v) => v,
             TypedLayerElementHandle::Color(v) => v,
             TypedLayerElementHandle::Normal(v) => v,
             TypedLayerElementHandle::Material(v) => v,
+            TypedLayerElementHandle::Smoothing(v) => v,
+            TypedLayerElementHandle::Tangent(v) => v,
             TypedLayerElementHandle::Uv(v) => v,
+            TypedLayerElementHandle::Other(v) => v,
         }
     }
 }
@@ -0,0 +1,153 @@
+//! Binormal.
+
+use anyhow::{bail, format_err, Error};
+use mint::Vector3;
+
+use crate::v7400::data::mesh::{
+    layer::{
+        LayerContentIndex, LayerElementHandle, MappingMode, ReferenceInformation, ReferenceMode,
+    },
+    Edges, PolygonVertexIndex, PolygonVertexRange, PolygonVertices, TriangleVertexIndex,
+    TriangleVertices,
+};
+
+/// Layer element node handle.
+#[derive(Debug, Clone, Copy)]
+pub struct LayerElementBinormalHandle<'a> {
+    /// `LayerElementBinormal` node.
+    node: LayerElementHandle<'a>,
+}
+
+impl<'a> LayerElementBinormalHandle<'a> {
+    /// Creates a new `LayerElementBinormalHandle`.
+    pub fn new(node: LayerElementHandle<'a>) -> Self {
+        Self { node }
+    }
+
+    /// Returns `Binormals` data.
+    pub fn binormals(&self) -> Result<Binormals<'a>, Error> {
+        Binormals::new(self)
+    }
+
+    /// Returns reference to the binormals (xyz) slice.
+    fn binormals_vec3_slice(&self) -> Result<&'a [f64], Error> {
+        self.children_by_name("Binormals")
+            .next()
+            .ok_or_else(|| format_err!("No `Binormals` found for `LayerElementBinormal` node"))?
+            .attributes()
+            .get(0)
+            .ok_or_else(|| format_err!("No attributes found for `Binormals` node"))?
+            .get_arr_f64_or_type()
+            .map_err(|ty| format_err!("Expected `[f64]` as binormals, but got {:?}", ty))
+    }
+
+    /// Returns reference to the binormals norms (w = `sqrt(x*x + y*y + z*z)`)
+    /// slice.
+    ///
+    /// It is not guaranteed to be correct value.
+    /// Use with care, especially if you are using untrusted data.
+    fn binormals_norm_slice(&self) -> Result<Option<&'a [f64]>, Error> {
+        let binormals_w_node = match self.children_by_name("BinormalsW").next() {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        binormals_w_node
+            .attributes()
+            .get(0)
+            .ok_or_else(|| format_err!("No attributes found for `BinormalsW` node"))?
+            .get_arr_f64_or_type()
+            .map(Some)
+            .map_err(|ty| format_err!("Expected `[f64]` as binormals W, but got {:?}", ty))
+    }
+}
+
+impl<'a> std::ops::Deref for LayerElementBinormalHandle<'a> {
+    type Target = LayerElementHandle<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.node
+    }
+}
+
+/// Binormals.
+#[derive(Debug, Clone, Copy)]
+pub struct Binormals<'a> {
+    /// Binormals.
+    binormals: &'a [f64],
+    /// Binormals W.
+    #[allow(dead_code)] // Useful for debugging using `Debug`.
+    binormals_w: Option<&'a [f64]>,
+    /// Mapping mode.
+    mapping_mode: MappingMode,
+}
+
+impl<'a> Binormals<'a> {
+    /// Creates a new `Binormals`.
+    fn new(handle: &LayerElementBinormalHandle<'a>) -> Result<Self, Error> {
+        let binormals = handle.binormals_vec3_slice()?;
+        let binormals_w = handle.binormals_norm_slice()?;
+        let mapping_mode = handle.mapping_mode()?;
+        let reference_mode = handle.reference_mode()?;
+        if reference_mode != ReferenceMode::Direct {
+            bail!(
+                "Unsupported reference mode for binormals: {:?}",
+                reference_mode
+            );
+        }
+        Ok(Self {
+            binormals,
+            binormals_w,
+            mapping_mode,
+        })
+    }
+
+    /// Creates a new `Binormals` from the binormals of all polygon vertices.
+    pub(crate) fn from_polygon_vertex_binormals(binormals: &'a [f64]) -> Self {
+        Self {
+            binormals,
+            binormals_w: None,
+            mapping_mode: MappingMode::ByPolygonVertex,
+        }
+    }
+
+    /// Returns `[f64; 3]` binormal corresponding to the given triangle vertex
+    /// index.
+    pub fn binormal(
+        &self,
+        tris: &TriangleVertices<'a>,
+        tri_vi: TriangleVertexIndex,
+    ) -> Result<Vector3<f64>, Error> {
+        let i = LayerContentIndex::control_point_data_from_triangle_vertices(
+            ReferenceInformation::Direct,
+            self.mapping_mode,
+            tris,
+            self.binormals.len() / 3,
+            tri_vi,
+        )?;
+        Ok(Vector3::from_slice(&self.binormals[(i.get() * 3)..]))
+    }
+
+    /// Returns `[f64; 3]` binormal corresponding to the given polygon vertex.
+    ///
+    /// `polygon` should be returned by `polygon_vertices.polygons()`, and an
+    /// error is returned if `pvi` is not in it.
+    /// `edges` is required for `ByEdge` mapping mode.
+    pub fn binormal_by_polygon_vertex(
+        &self,
+        polygon_vertices: &PolygonVertices<'a>,
+        edges: Option<&Edges<'a>>,
+        polygon: &PolygonVertexRange,
+        pvi: PolygonVertexIndex,
+    ) -> Result<Vector3<f64>, Error> {
+        let i = LayerContentIndex::control_point_data_from_polygon_vertices(
+            ReferenceInformation::Direct,
+            self.mapping_mode,
+            polygon_vertices,
+            edges,
+            self.binormals.len() / 3,
+            polygon,
+            pvi,
+        )?;
+        Ok(Vector3::from_slice(&self.binormals[(i.get() * 3)..]))
+    }
+}
@@ -6,7 +6,8 @@ use crate::v7400::data::mesh::{
     layer::{
         LayerContentIndex, LayerElementHandle, MappingMode, ReferenceInformation, ReferenceMode,
     },
-    TriangleVertexIndex, TriangleVertices,
+    Edges, PolygonVertexIndex, PolygonVertexRange, PolygonVertices, TriangleVertexIndex,
+    TriangleVertices,
 };
 
 /// Layer element node handle.
@@ -116,4 +117,34 @@ impl<'a> Colors<'a> {
             self.colors[i4 + 3],
         ])
     }
+
+    /// Returns `[f64; 4]` color corresponding to the given polygon vertex.
+    ///
+    /// `polygon` should be returned by `polygon_vertices.polygons()`, and an
+    /// error is returned if `pvi` is not in it.
+    /// `edges` is required for `ByEdge` mapping mode.
+    pub fn color_by_polygon_vertex(
+        &self,
+        polygon_vertices: &PolygonVertices<'a>,
+        edges: Option<&Edges<'a>>,
+        polygon: &PolygonVertexRange,
+        pvi: PolygonVertexIndex,
+    ) -> Result<[f64; 4], Error> {
+        let i = LayerContentIndex::control_point_data_from_polygon_vertices(
+            self.reference_info,
+            self.mapping_mode,
+            polygon_vertices,
+            edges,
+            self.colors.len() / 4,
+            polygon,
+            pvi,
+        )?;
+        let i4 = i.get() * 4;
+        Ok([
+            self.colors[i4],
+            self.colors[i4 + 1],
+            self.colors[i4 + 2],
+            self.colors[i4 + 3],
+        ])
+    }
 }
@@ -6,7 +6,10 @@ use anyhow::{bail, format_err, Error};
 
 use crate::{
     fbxcel::tree::v7400::NodeHandle,
-    v7400::data::mesh::{layer::LayerElementIndex, TriangleVertexIndex, TriangleVertices},
+    v7400::data::mesh::{
+        layer::LayerElementIndex, Edges, PolygonVertexIndex, PolygonVertexRange, PolygonVertices,
+        TriangleVertexIndex, TriangleVertices,
+    },
 };
 
 /// Layer element node handle.
@@ -124,6 +127,9 @@ pub enum MappingMode {
     /// By polygon.
     ByPolygon,
     /// By edge.
+    ///
+    /// The edge of a polygon vertex is the edge from the polygon vertex to
+    /// the next polygon vertex in the same polygon.
     ByEdge,
     /// Single value for all.
     AllSame,
@@ -249,9 +255,7 @@ impl LayerContentIndex {
         tri_vi: TriangleVertexIndex,
     ) -> Result<LayerContentIndex, Error> {
         let index = match mapping_mode {
-            MappingMode::None | MappingMode::ByEdge => {
-                bail!("Unsupported mapping mode: {:?}", mapping_mode)
-            }
+            MappingMode::None => bail!("Unsupported mapping mode: {:?}", mapping_mode),
             MappingMode::ByControlPoint => {
                 let cpi = triangle_vertices
                     .control_point_index(tri_vi)
@@ -276,16 +280,80 @@ impl LayerContentIndex {
                     })?;
                 reference_info.get_direct(poly_i.to_usize())?
             }
+            MappingMode::ByEdge => {
+                let pvi = triangle_vertices
+                    .polygon_vertex_index(tri_vi)
+                    .ok_or_else(|| {
+                        format_err!("Failed to get polygon vertex index: tri_vi={:?}", tri_vi)
+                    })?;
+                let edge = triangle_vertices
+                    .edges()?
+                    .edge_index(pvi)
+                    .ok_or_else(|| format_err!("Failed to get edge index: pvi={:?}", pvi))?;
+                reference_info.get_direct(edge.to_usize())?
+            }
             MappingMode::AllSame => reference_info.get_direct(0)?,
         };
-        if index.get() >= layer_element_array_len {
+
+        index.checked(layer_element_array_len)
+    }
+
+    /// Returns the layer content index for the corresponding polygon vertex.
+    ///
+    /// `pvi` should be a polygon vertex in `polygon`, or an error is returned.
+    /// `pvi` is not used for `ByPolygon` and `AllSame` mapping modes.
+    /// `edges` is used only for `ByEdge` mapping mode.
+    pub(crate) fn control_point_data_from_polygon_vertices(
+        reference_info: ReferenceInformation<'_>,
+        mapping_mode: MappingMode,
+        polygon_vertices: &PolygonVertices<'_>,
+        edges: Option<&Edges<'_>>,
+        layer_element_array_len: usize,
+        polygon: &PolygonVertexRange,
+        pvi: PolygonVertexIndex,
+    ) -> Result<LayerContentIndex, Error> {
+        if !polygon.contains(pvi) {
+            bail!(
+                "Polygon vertex is not in the polygon: pvi={:?}, polygon={:?}",
+                pvi,
+                polygon
+            );
+        }
+        let index = match mapping_mode {
+            MappingMode::None => bail!("Unsupported mapping mode: {:?}", mapping_mode),
+            MappingMode::ByControlPoint => {
+                let cpi = polygon_vertices
+                    .polygon_vertex(pvi)
+                    .ok_or_else(|| format_err!("Failed to get polygon vertex: pvi={:?}", pvi))?;
+                reference_info.get_direct(cpi.to_u32() as usize)?
+            }
+            MappingMode::ByPolygonVertex => reference_info.get_direct(pvi.to_usize())?,
+            MappingMode::ByPolygon => {
+                reference_info.get_direct(polygon.polygon_index().to_usize())?
+            }
+            MappingMode::ByEdge => {
+                let edge = edges
+                    .ok_or_else(|| format_err!("Edges are required for `ByEdge` mapping mode"))?
+                    .edge_index(pvi)
+                    .ok_or_else(|| format_err!("Failed to get edge index: pvi={:?}", pvi))?;
+                reference_info.get_direct(edge.to_usize())?
+            }
+            MappingMode::AllSame => reference_info.get_direct(0)?,
+        };
+
+        index.checked(layer_element_array_len)
+    }
+
+    /// Returns the index if it is in range.
+    fn checked(self, layer_element_array_len: usize) -> Result<LayerContentIndex, Error> {
+        if self.get() >= layer_element_array_len {
             bail!(
                 "Calculated index out of range: index={:?}, array_len={:?}",
-                index,
+                self,
                 layer_element_array_len
             );
         }
 
-        Ok(index)
+        Ok(self)
     }
 }
@@ -0,0 +1,390 @@
+//! Generic layer element.
+//!
+//! This is useful to read layer elements without first-class support, such as
+//! `LayerElementVisibility`, `LayerElementHole`, `LayerElementEdgeCrease`,
+//! `LayerElementVertexCrease`, and vendor-specific ones.
+
+use std::ops::Range;
+
+use anyhow::{bail, format_err, Error};
+use fbxcel::{low::v7400::AttributeValue, tree::v7400::NodeHandle};
+
+use crate::v7400::data::mesh::{
+    layer::{
+        LayerContentIndex, LayerElementHandle, MappingMode, ReferenceInformation, ReferenceMode,
+    },
+    Edges, PolygonVertexIndex, PolygonVertexRange, PolygonVertices, TriangleVertexIndex,
+    TriangleVertices,
+};
+
+/// Names of metadata child nodes of layer elements.
+const METADATA_NODE_NAMES: &[&str] = &[
+    "Version",
+    "Name",
+    "MappingInformationType",
+    "ReferenceInformationType",
+];
+
+/// Layer element node handle.
+///
+/// This can be used for layer elements of any type.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use fbxcel_dom::v7400::object::geometry::MeshHandle;
+/// use fbxcel_dom::v7400::data::mesh::{
+///     layer::{LayerElementType, TypedLayerElementHandle},
+///     triangulator,
+/// };
+/// # fn get_mesh<'a>() -> MeshHandle<'a> { unimplemented!() }
+/// let mesh: MeshHandle<'_> = get_mesh();
+/// let tris = mesh
+///     .polygon_vertices()?
+///     .triangulate_each(triangulator::ear_clipping)?;
+/// let ty = LayerElementType::Other("LayerElementVertexCrease");
+/// for elem in mesh.layer_elements(ty) {
+///     let elem = match elem {
+///         TypedLayerElementHandle::Other(v) => v,
+///         _ => unreachable!("Should never happen for `LayerElementType::Other`"),
+///     };
+///     let data = elem.data()?;
+///     for tri_vi in tris.triangle_vertex_indices() {
+///         let crease = data.get(&tris, tri_vi, 1)?;
+///         println!("{:?}: {:?}", tri_vi, crease);
+///     }
+/// }
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct LayerElementGenericHandle<'a> {
+    /// `LayerElement*` node.
+    node: LayerElementHandle<'a>,
+}
+
+impl<'a> LayerElementGenericHandle<'a> {
+    /// Creates a new `LayerElementGenericHandle`.
+    pub fn new(node: LayerElementHandle<'a>) -> Self {
+        Self { node }
+    }
+
+    /// Returns layer element data with automatically detected arrays.
+    ///
+    /// The data array is the first array child node other than metadata
+    /// (such as `Name` and `MappingInformationType`) whose name does not end
+    /// with `Index`.
+    /// The index array is `{data array name}Index` if exists, or the first
+    /// other child node whose name ends with `Index`.
+    pub fn data(&self) -> Result<LayerElementData<'a>, Error> {
+        let values_node = self
+            .children()
+            .filter(|child| !METADATA_NODE_NAMES.contains(&child.name()))
+            .filter(|child| !child.name().ends_with("Index"))
+            .find(|child| {
+                child
+                    .attributes()
+                    .get(0)
+                    .map_or(false, |v| LayerElementValues::new(v).is_some())
+            })
+            .ok_or_else(|| format_err!("No data array found for `{}` node", self.node().name()))?;
+        let index_name = format!("{}Index", values_node.name());
+        let index_node = self.children_by_name(&index_name).next().or_else(|| {
+            self.children()
+                .find(|child| child.name().ends_with("Index"))
+        });
+
+        LayerElementData::new(self, values_node, index_node)
+    }
+
+    /// Returns layer element data with the given data array name and index
+    /// array name.
+    pub fn data_by_name(
+        &self,
+        values_name: &str,
+        index_name: &str,
+    ) -> Result<LayerElementData<'a>, Error> {
+        let values_node = self.children_by_name(values_name).next().ok_or_else(|| {
+            format_err!(
+                "No `{}` found for `{}` node",
+                values_name,
+                self.node().name()
+            )
+        })?;
+        let index_node = self.children_by_name(index_name).next();
+
+        LayerElementData::new(self, values_node, index_node)
+    }
+}
+
+impl<'a> std::ops::Deref for LayerElementGenericHandle<'a> {
+    type Target = LayerElementHandle<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.node
+    }
+}
+
+/// Layer element data.
+#[derive(Debug, Clone, Copy)]
+pub struct LayerElementData<'a> {
+    /// Values.
+    values: LayerElementValues<'a>,
+    /// Reference information.
+    reference_info: ReferenceInformation<'a>,
+    /// Mapping mode.
+    mapping_mode: MappingMode,
+}
+
+impl<'a> LayerElementData<'a> {
+    /// Creates a new `LayerElementData`.
+    fn new(
+        handle: &LayerElementGenericHandle<'a>,
+        values_node: NodeHandle<'a>,
+        index_node: Option<NodeHandle<'a>>,
+    ) -> Result<Self, Error> {
+        let values = values_node
+            .attributes()
+            .get(0)
+            .ok_or_else(|| format_err!("No attributes found for `{}` node", values_node.name()))
+            .and_then(|v| {
+                LayerElementValues::new(v).ok_or_else(|| {
+                    format_err!(
+                        "Expected array as `{}` values, but got {:?}",
+                        values_node.name(),
+                        v.type_()
+                    )
+                })
+            })?;
+        let mapping_mode = handle.mapping_mode()?;
+        let reference_info = match handle.reference_mode()? {
+            ReferenceMode::Direct => ReferenceInformation::Direct,
+            ReferenceMode::IndexToDirect => {
+                let index_node = index_node.ok_or_else(|| {
+                    format_err!(
+                        "No index array found for `{}` node with `IndexToDirect` reference mode",
+                        handle.node().name()
+                    )
+                })?;
+                let indices = index_node
+                    .attributes()
+                    .get(0)
+                    .ok_or_else(|| {
+                        format_err!("No attributes found for `{}` node", index_node.name())
+                    })?
+                    .get_arr_i32_or_type()
+                    .map_err(|ty| {
+                        format_err!(
+                            "Expected `[i32]` as `{}` indices, but got {:?}",
+                            index_node.name(),
+                            ty
+                        )
+                    })?;
+                ReferenceInformation::IndexToDirect(indices)
+            }
+        };
+
+        Ok(Self {
+            values,
+            reference_info,
+            mapping_mode,
+        })
+    }
+
+    /// Returns all values.
+    pub fn values(&self) -> LayerElementValues<'a> {
+        self.values
+    }
+
+    /// Returns the reference information.
+    pub fn reference_information(&self) -> ReferenceInformation<'a> {
+        self.reference_info
+    }
+
+    /// Returns the mapping mode.
+    pub fn mapping_mode(&self) -> MappingMode {
+        self.mapping_mode
+    }
+
+    /// Returns the index of the element corresponding to the given triangle
+    /// vertex index.
+    ///
+    /// `components` is the number of values per element, for example `1` for
+    /// scalars and `3` for 3D vectors.
+    pub fn element_index(
+        &self,
+        tris: &TriangleVertices<'a>,
+        tri_vi: TriangleVertexIndex,
+        components: usize,
+    ) -> Result<usize, Error> {
+        if components == 0 {
+            bail!("Number of components should be positive");
+        }
+        LayerContentIndex::control_point_data_from_triangle_vertices(
+            self.reference_info,
+            self.mapping_mode,
+            tris,
+            self.values.len() / components,
+            tri_vi,
+        )
+        .map(LayerContentIndex::get)
+    }
+
+    /// Returns the index of the element corresponding to the given polygon
+    /// vertex.
+    ///
+    /// `polygon` should be returned by `polygon_vertices.polygons()`, and an
+    /// error is returned if `pvi` is not in it.
+    /// `edges` is required for `ByEdge` mapping mode.
+    ///
+    /// `components` is the number of values per element.
+    pub fn element_index_by_polygon_vertex(
+        &self,
+        polygon_vertices: &PolygonVertices<'a>,
+        edges: Option<&Edges<'a>>,
+        polygon: &PolygonVertexRange,
+        pvi: PolygonVertexIndex,
+        components: usize,
+    ) -> Result<usize, Error> {
+        if components == 0 {
+            bail!("Number of components should be positive");
+        }
+        LayerContentIndex::control_point_data_from_polygon_vertices(
+            self.reference_info,
+            self.mapping_mode,
+            polygon_vertices,
+            edges,
+            self.values.len() / components,
+            polygon,
+            pvi,
+        )
+        .map(LayerContentIndex::get)
+    }
+
+    /// Returns values of the element corresponding to the given triangle
+    /// vertex index.
+    ///
+    /// `components` is the number of values per element, for example `1` for
+    /// scalars and `3` for 3D vectors.
+    pub fn get(
+        &self,
+        tris: &TriangleVertices<'a>,
+        tri_vi: TriangleVertexIndex,
+        components: usize,
+    ) -> Result<LayerElementValues<'a>, Error> {
+        let i = self.element_index(tris, tri_vi, components)?;
+        let start = i * components;
+        self.values
+            .slice(start..(start + components))
+            .ok_or_else(|| {
+                format_err!(
+                    "Index out of range: index={:?}, array_len={:?}",
+                    i,
+                    self.values.len()
+                )
+            })
+    }
+
+    /// Returns values of the element corresponding to the given polygon
+    /// vertex.
+    ///
+    /// `polygon` should be returned by `polygon_vertices.polygons()`, and an
+    /// error is returned if `pvi` is not in it.
+    /// `edges` is required for `ByEdge` mapping mode.
+    ///
+    /// `components` is the number of values per element.
+    pub fn get_by_polygon_vertex(
+        &self,
+        polygon_vertices: &PolygonVertices<'a>,
+        edges: Option<&Edges<'a>>,
+        polygon: &PolygonVertexRange,
+        pvi: PolygonVertexIndex,
+        components: usize,
+    ) -> Result<LayerElementValues<'a>, Error> {
+        let i = self.element_index_by_polygon_vertex(
+            polygon_vertices,
+            edges,
+            polygon,
+            pvi,
+            components,
+        )?;
+        let start = i * components;
+        self.values
+            .slice(start..(start + components))
+            .ok_or_else(|| {
+                format_err!(
+                    "Index out of range: index={:?}, array_len={:?}",
+                    i,
+                    self.values.len()
+                )
+            })
+    }
+}
+
+/// Values array of a layer element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LayerElementValues<'a> {
+    /// `bool` array.
+    Bool(&'a [bool]),
+    /// `i32` array.
+    I32(&'a [i32]),
+    /// `i64` array.
+    I64(&'a [i64]),
+    /// `f32` array.
+    F32(&'a [f32]),
+    /// `f64` array.
+    F64(&'a [f64]),
+}
+
+impl<'a> LayerElementValues<'a> {
+    /// Creates a new `LayerElementValues` if the attribute is an array.
+    fn new(attr: &'a AttributeValue) -> Option<Self> {
+        match attr {
+            AttributeValue::ArrBool(v) => Some(LayerElementValues::Bool(v)),
+            AttributeValue::ArrI32(v) => Some(LayerElementValues::I32(v)),
+            AttributeValue::ArrI64(v) => Some(LayerElementValues::I64(v)),
+            AttributeValue::ArrF32(v) => Some(LayerElementValues::F32(v)),
+            AttributeValue::ArrF64(v) => Some(LayerElementValues::F64(v)),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        match self {
+            LayerElementValues::Bool(v) => v.len(),
+            LayerElementValues::I32(v) => v.len(),
+            LayerElementValues::I64(v) => v.len(),
+            LayerElementValues::F32(v) => v.len(),
+            LayerElementValues::F64(v) => v.len(),
+        }
+    }
+
+    /// Returns whether there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the values in the given range.
+    pub fn slice(&self, range: Range<usize>) -> Option<Self> {
+        Some(match self {
+            LayerElementValues::Bool(v) => LayerElementValues::Bool(v.get(range)?),
+            LayerElementValues::I32(v) => LayerElementValues::I32(v.get(range)?),
+            LayerElementValues::I64(v) => LayerElementValues::I64(v.get(range)?),
+            LayerElementValues::F32(v) => LayerElementValues::F32(v.get(range)?),
+            LayerElementValues::F64(v) => LayerElementValues::F64(v.get(range)?),
+        })
+    }
+
+    /// Returns the value at the given index as `f64`.
+    ///
+    /// `true` and `false` are converted to `1.0` and `0.0` respectively.
+    pub fn get_f64(&self, i: usize) -> Option<f64> {
+        match self {
+            LayerElementValues::Bool(v) => v.get(i).map(|&v| if v { 1.0 } else { 0.0 }),
+            LayerElementValues::I32(v) => v.get(i).map(|&v| f64::from(v)),
+            LayerElementValues::I64(v) => v.get(i).map(|&v| v as f64),
+            LayerElementValues::F32(v) => v.get(i).map(|&v| f64::from(v)),
+            LayerElementValues::F64(v) => v.get(i).copied(),
+        }
+    }
+}
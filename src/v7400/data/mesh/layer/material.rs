@@ -6,6 +6,7 @@ use crate::v7400::data::mesh::{
     layer::{
         LayerContentIndex, LayerElementHandle, MappingMode, ReferenceInformation, ReferenceMode,
     },
+    Edges, PolygonIndex, PolygonVertexIndex, PolygonVertexRange, PolygonVertices,
     TriangleVertexIndex, TriangleVertices,
 };
 
@@ -89,6 +90,57 @@ impl<'a> Materials<'a> {
             self.indices.len(),
             tri_vi,
         )?;
+        self.material_index_at(i)
+    }
+
+    /// Returns material index corresponding to the given polygon vertex.
+    ///
+    /// `polygon` should be returned by `polygon_vertices.polygons()`, and an
+    /// error is returned if `pvi` is not in it.
+    /// `edges` is required for `ByEdge` mapping mode.
+    pub fn material_index_by_polygon_vertex(
+        &self,
+        polygon_vertices: &PolygonVertices<'a>,
+        edges: Option<&Edges<'a>>,
+        polygon: &PolygonVertexRange,
+        pvi: PolygonVertexIndex,
+    ) -> Result<MaterialIndex, Error> {
+        let i = LayerContentIndex::control_point_data_from_polygon_vertices(
+            ReferenceInformation::Direct,
+            self.mapping_mode,
+            polygon_vertices,
+            edges,
+            self.indices.len(),
+            polygon,
+            pvi,
+        )?;
+        self.material_index_at(i)
+    }
+
+    /// Returns material index of the given polygon.
+    ///
+    /// This fails if the mapping mode is neither `ByPolygon` nor `AllSame`.
+    pub fn material_index_by_polygon(&self, poly_i: PolygonIndex) -> Result<MaterialIndex, Error> {
+        let i = match self.mapping_mode {
+            MappingMode::ByPolygon => poly_i.to_usize(),
+            MappingMode::AllSame => 0,
+            mapping_mode => bail!(
+                "Per-polygon material is unavailable for mapping mode {:?}",
+                mapping_mode
+            ),
+        };
+        if i >= self.indices.len() {
+            bail!(
+                "Index out of range: index={:?}, array_len={:?}",
+                i,
+                self.indices.len()
+            );
+        }
+        self.material_index_at(LayerContentIndex::new(i))
+    }
+
+    /// Returns material index at the given index of the material indices.
+    fn material_index_at(&self, i: LayerContentIndex) -> Result<MaterialIndex, Error> {
         let material_index_index = self.indices[i.get()];
         if material_index_index < 0 {
             bail!(
@@ -107,7 +159,7 @@ pub struct MaterialIndex(u32);
 
 impl MaterialIndex {
     /// Creates a new `MaterialIndex`.
-    fn new(i: u32) -> Self {
+    pub(crate) fn new(i: u32) -> Self {
         Self(i)
     }
 
@@ -7,7 +7,8 @@ use crate::v7400::data::mesh::{
     layer::{
         LayerContentIndex, LayerElementHandle, MappingMode, ReferenceInformation, ReferenceMode,
     },
-    TriangleVertexIndex, TriangleVertices,
+    Edges, PolygonVertexIndex, PolygonVertexRange, PolygonVertices, TriangleVertexIndex,
+    TriangleVertices,
 };
 
 /// Layer element node handle.
@@ -100,6 +101,15 @@ impl<'a> Normals<'a> {
         })
     }
 
+    /// Creates a new `Normals` from the normals of all polygon vertices.
+    pub(crate) fn from_polygon_vertex_normals(normals: &'a [f64]) -> Self {
+        Self {
+            normals,
+            normals_w: None,
+            mapping_mode: MappingMode::ByPolygonVertex,
+        }
+    }
+
     /// Returns `[f64; 3]` normal corresponding to the given triangle vertex
     /// index.
     pub fn normal(
@@ -116,4 +126,28 @@ impl<'a> Normals<'a> {
         )?;
         Ok(Vector3::from_slice(&self.normals[(i.get() * 3)..]))
     }
+
+    /// Returns `[f64; 3]` normal corresponding to the given polygon vertex.
+    ///
+    /// `polygon` should be returned by `polygon_vertices.polygons()`, and an
+    /// error is returned if `pvi` is not in it.
+    /// `edges` is required for `ByEdge` mapping mode.
+    pub fn normal_by_polygon_vertex(
+        &self,
+        polygon_vertices: &PolygonVertices<'a>,
+        edges: Option<&Edges<'a>>,
+        polygon: &PolygonVertexRange,
+        pvi: PolygonVertexIndex,
+    ) -> Result<Vector3<f64>, Error> {
+        let i = LayerContentIndex::control_point_data_from_polygon_vertices(
+            ReferenceInformation::Direct,
+            self.mapping_mode,
+            polygon_vertices,
+            edges,
+            self.normals.len() / 3,
+            polygon,
+            pvi,
+        )?;
+        Ok(Vector3::from_slice(&self.normals[(i.get() * 3)..]))
+    }
 }
@@ -0,0 +1,128 @@
+//! Smoothing.
+
+use anyhow::{bail, format_err, Error};
+
+use crate::v7400::data::mesh::{
+    layer::{LayerElementHandle, MappingMode, ReferenceMode},
+    EdgeIndex, PolygonIndex,
+};
+
+/// Layer element node handle.
+#[derive(Debug, Clone, Copy)]
+pub struct LayerElementSmoothingHandle<'a> {
+    /// `LayerElementSmoothing` node.
+    node: LayerElementHandle<'a>,
+}
+
+impl<'a> LayerElementSmoothingHandle<'a> {
+    /// Creates a new `LayerElementSmoothingHandle`.
+    pub fn new(node: LayerElementHandle<'a>) -> Self {
+        Self { node }
+    }
+
+    /// Returns `Smoothing` data.
+    pub fn smoothing(&self) -> Result<Smoothing<'a>, Error> {
+        Smoothing::new(self)
+    }
+
+    /// Returns reference to the smoothing values slice.
+    fn smoothing_slice(&self) -> Result<&'a [i32], Error> {
+        self.children_by_name("Smoothing")
+            .next()
+            .ok_or_else(|| format_err!("No `Smoothing` found for `LayerElementSmoothing` node"))?
+            .attributes()
+            .get(0)
+            .ok_or_else(|| format_err!("No attributes found for `Smoothing` node"))?
+            .get_arr_i32_or_type()
+            .map_err(|ty| format_err!("Expected `[i32]` as smoothing values, but got {:?}", ty))
+    }
+}
+
+impl<'a> std::ops::Deref for LayerElementSmoothingHandle<'a> {
+    type Target = LayerElementHandle<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.node
+    }
+}
+
+/// Smoothing.
+///
+/// If the mapping mode is `ByPolygon` (or `AllSame`), values are smoothing
+/// groups as bit flags: polygons sharing at least one group are smoothed
+/// with each other, and polygons with no groups are flat.
+///
+/// If the mapping mode is `ByEdge`, values are flags of edges: non-zero for
+/// soft (smooth) edges, and zero for hard edges.
+#[derive(Debug, Clone, Copy)]
+pub struct Smoothing<'a> {
+    /// Smoothing values.
+    values: &'a [i32],
+    /// Mapping mode.
+    mapping_mode: MappingMode,
+}
+
+impl<'a> Smoothing<'a> {
+    /// Creates a new `Smoothing`.
+    fn new(handle: &LayerElementSmoothingHandle<'a>) -> Result<Self, Error> {
+        let values = handle.smoothing_slice()?;
+        let mapping_mode = handle.mapping_mode()?;
+        let reference_mode = handle.reference_mode()?;
+        if reference_mode != ReferenceMode::Direct {
+            bail!(
+                "Unsupported reference mode for smoothing: {:?}",
+                reference_mode
+            );
+        }
+        Ok(Self {
+            values,
+            mapping_mode,
+        })
+    }
+
+    /// Returns the mapping mode.
+    pub fn mapping_mode(&self) -> MappingMode {
+        self.mapping_mode
+    }
+
+    /// Returns smoothing groups (as bit flags) of the given polygon.
+    ///
+    /// This fails if the mapping mode is neither `ByPolygon` nor `AllSame`.
+    pub fn smoothing_group(&self, poly_i: PolygonIndex) -> Result<u32, Error> {
+        let i = match self.mapping_mode {
+            MappingMode::ByPolygon => poly_i.to_usize(),
+            MappingMode::AllSame => 0,
+            mapping_mode => bail!(
+                "Smoothing groups are unavailable for mapping mode {:?}",
+                mapping_mode
+            ),
+        };
+        self.value(i).map(|v| v as u32)
+    }
+
+    /// Returns whether the given edge is soft (smooth).
+    ///
+    /// This fails if the mapping mode is neither `ByEdge` nor `AllSame`.
+    pub fn is_soft_edge(&self, edge: EdgeIndex) -> Result<bool, Error> {
+        let i = match self.mapping_mode {
+            MappingMode::ByEdge => edge.to_usize(),
+            MappingMode::AllSame => 0,
+            mapping_mode => bail!(
+                "Edge smoothing is unavailable for mapping mode {:?}",
+                mapping_mode
+            ),
+        };
+        self.value(i).map(|v| v != 0)
+    }
+
+    /// Returns the value at the given index.
+    fn value(&self, i: usize) -> Result<i32, Error> {
+        self.values.get(i).copied().ok_or_else(|| {
+            format_err!(
+                "Index out of range: index={:?}, array_len={:?}",
+                i,
+                self.values.len()
+            )
+        })
+    }
+}
@@ -0,0 +1,153 @@
+//! Tangent.
+
+use anyhow::{bail, format_err, Error};
+use mint::Vector3;
+
+use crate::v7400::data::mesh::{
+    layer::{
+        LayerContentIndex, LayerElementHandle, MappingMode, ReferenceInformation, ReferenceMode,
+    },
+    Edges, PolygonVertexIndex, PolygonVertexRange, PolygonVertices, TriangleVertexIndex,
+    TriangleVertices,
+};
+
+/// Layer element node handle.
+#[derive(Debug, Clone, Copy)]
+pub struct LayerElementTangentHandle<'a> {
+    /// `LayerElementTangent` node.
+    node: LayerElementHandle<'a>,
+}
+
+impl<'a> LayerElementTangentHandle<'a> {
+    /// Creates a new `LayerElementTangentHandle`.
+    pub fn new(node: LayerElementHandle<'a>) -> Self {
+        Self { node }
+    }
+
+    /// Returns `Tangents` data.
+    pub fn tangents(&self) -> Result<Tangents<'a>, Error> {
+        Tangents::new(self)
+    }
+
+    /// Returns reference to the tangents (xyz) slice.
+    fn tangents_vec3_slice(&self) -> Result<&'a [f64], Error> {
+        self.children_by_name("Tangents")
+            .next()
+            .ok_or_else(|| format_err!("No `Tangents` found for `LayerElementTangent` node"))?
+            .attributes()
+            .get(0)
+            .ok_or_else(|| format_err!("No attributes found for `Tangents` node"))?
+            .get_arr_f64_or_type()
+            .map_err(|ty| format_err!("Expected `[f64]` as tangents, but got {:?}", ty))
+    }
+
+    /// Returns reference to the tangents norms (w = `sqrt(x*x + y*y + z*z)`)
+    /// slice.
+    ///
+    /// It is not guaranteed to be correct value.
+    /// Use with care, especially if you are using untrusted data.
+    fn tangents_norm_slice(&self) -> Result<Option<&'a [f64]>, Error> {
+        let tangents_w_node = match self.children_by_name("TangentsW").next() {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        tangents_w_node
+            .attributes()
+            .get(0)
+            .ok_or_else(|| format_err!("No attributes found for `TangentsW` node"))?
+            .get_arr_f64_or_type()
+            .map(Some)
+            .map_err(|ty| format_err!("Expected `[f64]` as tangents W, but got {:?}", ty))
+    }
+}
+
+impl<'a> std::ops::Deref for LayerElementTangentHandle<'a> {
+    type Target = LayerElementHandle<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.node
+    }
+}
+
+/// Tangents.
+#[derive(Debug, Clone, Copy)]
+pub struct Tangents<'a> {
+    /// Tangents.
+    tangents: &'a [f64],
+    /// Tangents W.
+    #[allow(dead_code)] // Useful for debugging using `Debug`.
+    tangents_w: Option<&'a [f64]>,
+    /// Mapping mode.
+    mapping_mode: MappingMode,
+}
+
+impl<'a> Tangents<'a> {
+    /// Creates a new `Tangents`.
+    fn new(handle: &LayerElementTangentHandle<'a>) -> Result<Self, Error> {
+        let tangents = handle.tangents_vec3_slice()?;
+        let tangents_w = handle.tangents_norm_slice()?;
+        let mapping_mode = handle.mapping_mode()?;
+        let reference_mode = handle.reference_mode()?;
+        if reference_mode != ReferenceMode::Direct {
+            bail!(
+                "Unsupported reference mode for tangents: {:?}",
+                reference_mode
+            );
+        }
+        Ok(Self {
+            tangents,
+            tangents_w,
+            mapping_mode,
+        })
+    }
+
+    /// Creates a new `Tangents` from the tangents of all polygon vertices.
+    pub(crate) fn from_polygon_vertex_tangents(tangents: &'a [f64]) -> Self {
+        Self {
+            tangents,
+            tangents_w: None,
+            mapping_mode: MappingMode::ByPolygonVertex,
+        }
+    }
+
+    /// Returns `[f64; 3]` tangent corresponding to the given triangle vertex
+    /// index.
+    pub fn tangent(
+        &self,
+        tris: &TriangleVertices<'a>,
+        tri_vi: TriangleVertexIndex,
+    ) -> Result<Vector3<f64>, Error> {
+        let i = LayerContentIndex::control_point_data_from_triangle_vertices(
+            ReferenceInformation::Direct,
+            self.mapping_mode,
+            tris,
+            self.tangents.len() / 3,
+            tri_vi,
+        )?;
+        Ok(Vector3::from_slice(&self.tangents[(i.get() * 3)..]))
+    }
+
+    /// Returns `[f64; 3]` tangent corresponding to the given polygon vertex.
+    ///
+    /// `polygon` should be returned by `polygon_vertices.polygons()`, and an
+    /// error is returned if `pvi` is not in it.
+    /// `edges` is required for `ByEdge` mapping mode.
+    pub fn tangent_by_polygon_vertex(
+        &self,
+        polygon_vertices: &PolygonVertices<'a>,
+        edges: Option<&Edges<'a>>,
+        polygon: &PolygonVertexRange,
+        pvi: PolygonVertexIndex,
+    ) -> Result<Vector3<f64>, Error> {
+        let i = LayerContentIndex::control_point_data_from_polygon_vertices(
+            ReferenceInformation::Direct,
+            self.mapping_mode,
+            polygon_vertices,
+            edges,
+            self.tangents.len() / 3,
+            polygon,
+            pvi,
+        )?;
+        Ok(Vector3::from_slice(&self.tangents[(i.get() * 3)..]))
+    }
+}
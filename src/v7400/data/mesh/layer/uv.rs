@@ -5,9 +5,11 @@ use mint::Point2;
 
 use crate::v7400::data::mesh::{
     layer::{
-        LayerContentIndex, LayerElementHandle, MappingMode, ReferenceInformation, ReferenceMode,
+        LayerContentIndex, LayerElementHandle, LayerElementIndex, LayerIndex, MappingMode,
+        ReferenceInformation, ReferenceMode,
     },
-    TriangleVertexIndex, TriangleVertices,
+    Edges, PolygonVertexIndex, PolygonVertexRange, PolygonVertices, TriangleVertexIndex,
+    TriangleVertices,
 };
 
 /// Layer element node handle.
@@ -107,4 +109,92 @@ impl<'a> Uv<'a> {
         )?;
         Ok(Point2::from_slice(&self.uv[(i.get() * 2)..]))
     }
+
+    /// Returns `[f64; 2]` uv corresponding to the given polygon vertex.
+    ///
+    /// `polygon` should be returned by `polygon_vertices.polygons()`, and an
+    /// error is returned if `pvi` is not in it.
+    /// `edges` is required for `ByEdge` mapping mode.
+    pub fn uv_by_polygon_vertex(
+        &self,
+        polygon_vertices: &PolygonVertices<'a>,
+        edges: Option<&Edges<'a>>,
+        polygon: &PolygonVertexRange,
+        pvi: PolygonVertexIndex,
+    ) -> Result<Point2<f64>, Error> {
+        let i = LayerContentIndex::control_point_data_from_polygon_vertices(
+            self.reference_info,
+            self.mapping_mode,
+            polygon_vertices,
+            edges,
+            self.uv.len() / 2,
+            polygon,
+            pvi,
+        )?;
+        Ok(Point2::from_slice(&self.uv[(i.get() * 2)..]))
+    }
+}
+
+/// UV set.
+///
+/// A UV set is a UV layer element referred from a layer.
+#[derive(Debug, Clone, Copy)]
+pub struct UvSet<'a> {
+    /// Layer index.
+    layer_index: LayerIndex,
+    /// Type-local layer element index.
+    typed_index: LayerElementIndex,
+    /// UV set name.
+    name: &'a str,
+    /// UV layer element.
+    handle: LayerElementUvHandle<'a>,
+}
+
+impl<'a> UvSet<'a> {
+    /// Creates a new `UvSet`.
+    pub(crate) fn new(
+        layer_index: LayerIndex,
+        handle: LayerElementUvHandle<'a>,
+    ) -> Result<Self, Error> {
+        let typed_index = handle.typed_index()?;
+        // Treat a missing `Name` node as an unnamed UV set.
+        let name = match handle.children_by_name("Name").next() {
+            Some(_) => handle.name()?,
+            None => "",
+        };
+
+        Ok(Self {
+            layer_index,
+            typed_index,
+            name,
+            handle,
+        })
+    }
+
+    /// Returns the index of the layer which refers the UV set.
+    pub fn layer_index(&self) -> LayerIndex {
+        self.layer_index
+    }
+
+    /// Returns the type-local layer element index.
+    pub fn typed_index(&self) -> LayerElementIndex {
+        self.typed_index
+    }
+
+    /// Returns the UV set name.
+    ///
+    /// Returns an empty string if the UV set has no name.
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Returns the UV layer element handle.
+    pub fn handle(&self) -> LayerElementUvHandle<'a> {
+        self.handle
+    }
+
+    /// Returns `UV` data.
+    pub fn uv(&self) -> Result<Uv<'a>, Error> {
+        self.handle.uv()
+    }
 }
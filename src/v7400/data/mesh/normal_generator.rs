@@ -0,0 +1,327 @@
+//! Smooth normal generation.
+
+use std::collections::HashMap;
+
+use anyhow::{format_err, Error};
+use mint::Vector3;
+
+use crate::v7400::{
+    data::mesh::{
+        layer::{normal::Normals, smoothing::Smoothing, MappingMode},
+        PolygonIndex, PolygonVertexIndex, PolygonVertices,
+    },
+    math::{add, dot, normalize, polygon_normal, scale, sub},
+};
+
+/// Weighting of polygon normals for smooth normals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NormalWeighting {
+    /// Weighted by the polygon area.
+    Area,
+    /// Weighted by the angle of the polygon corner.
+    Angle,
+}
+
+impl Default for NormalWeighting {
+    fn default() -> Self {
+        NormalWeighting::Angle
+    }
+}
+
+/// Smooth normal generator.
+///
+/// Polygons sharing a control point are smoothed with each other if they
+/// share a smoothing group, or (if smoothing is not set) if the angle between
+/// the polygon normals does not exceed the crease angle.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use fbxcel_dom::v7400::object::geometry::MeshHandle;
+/// use fbxcel_dom::v7400::data::mesh::triangulator;
+///
+/// # fn get_mesh<'a>() -> MeshHandle<'a> { unimplemented!() }
+/// let mesh: MeshHandle<'_> = get_mesh();
+/// let tris = mesh
+///     .polygon_vertices()?
+///     .triangulate_each(triangulator::ear_clipping)?;
+/// let generated = mesh
+///     .normal_generator()?
+///     .crease_angle(60_f64.to_radians())
+///     .generate()?;
+/// let normals = generated.normals();
+/// for tri_vi in tris.triangle_vertex_indices() {
+///     let normal = normals.normal(&tris, tri_vi)?;
+/// }
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NormalGenerator<'a> {
+    /// Polygon vertices.
+    polygon_vertices: PolygonVertices<'a>,
+    /// Weighting.
+    weighting: NormalWeighting,
+    /// Crease angle in radians.
+    crease_angle: f64,
+    /// Smoothing.
+    smoothing: Option<Smoothing<'a>>,
+}
+
+impl<'a> NormalGenerator<'a> {
+    /// Creates a new `NormalGenerator`.
+    ///
+    /// By default, normals are angle weighted, the crease angle is 180
+    /// degrees (i.e. everything is smoothed), and smoothing is not set.
+    pub fn new(polygon_vertices: &PolygonVertices<'a>) -> Self {
+        Self {
+            polygon_vertices: *polygon_vertices,
+            weighting: NormalWeighting::default(),
+            crease_angle: std::f64::consts::PI,
+            smoothing: None,
+        }
+    }
+
+    /// Sets the weighting of polygon normals.
+    pub fn weighting(&mut self, weighting: NormalWeighting) -> &mut Self {
+        self.weighting = weighting;
+        self
+    }
+
+    /// Sets the crease angle in radians.
+    ///
+    /// This is used only when smoothing is not set.
+    pub fn crease_angle(&mut self, radians: f64) -> &mut Self {
+        self.crease_angle = radians;
+        self
+    }
+
+    /// Sets the smoothing.
+    ///
+    /// Both smoothing groups (`ByPolygon` mapping mode) and edge smoothing
+    /// (`ByEdge` mapping mode) are supported.
+    /// Edge smoothing requires edges of the polygon vertices.
+    /// Smoothing with other mapping modes is ignored, and the crease angle is
+    /// used instead.
+    pub fn smoothing(&mut self, smoothing: Option<Smoothing<'a>>) -> &mut Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Generates normals.
+    pub fn generate(&self) -> Result<GeneratedNormals, Error> {
+        let pvs = &self.polygon_vertices;
+        let pv_len = pvs.raw_polygon_vertices().len();
+
+        let mut pv_polys = vec![0; pv_len];
+        let mut contributions = vec![zero(); pv_len];
+        let mut face_normals = Vec::new();
+        let mut corners_by_cp = HashMap::<_, Vec<_>>::new();
+        let mut points = Vec::new();
+        for (poly_i, range) in pvs.polygon_ranges()?.into_iter().enumerate() {
+            points.clear();
+            for pvi in range.clone() {
+                let pvi = PolygonVertexIndex::new(pvi);
+                let point = pvs
+                    .control_point(pvi)
+                    .ok_or_else(|| format_err!("Failed to get control point: pvi={:?}", pvi))?;
+                points.push(point);
+            }
+            let area_normal = polygon_normal(&points);
+            let face_normal = normalize(area_normal);
+            face_normals.push(face_normal);
+
+            let len = points.len();
+            for (corner, pvi) in range.enumerate() {
+                let cpi = pvs
+                    .polygon_vertex(PolygonVertexIndex::new(pvi))
+                    .expect("Should never fail: the index is in range")
+                    .to_u32();
+                corners_by_cp.entry(cpi).or_default().push(pvi);
+                pv_polys[pvi] = poly_i;
+                contributions[pvi] = match self.weighting {
+                    NormalWeighting::Area => scale(area_normal, 0.5),
+                    NormalWeighting::Angle => {
+                        let current = to_vec(points[corner]);
+                        let prev = to_vec(points[(corner + len - 1) % len]);
+                        let next = to_vec(points[(corner + 1) % len]);
+                        scale(face_normal, corner_angle(current, prev, next))
+                    }
+                };
+            }
+        }
+
+        let rule = match self.smoothing {
+            Some(smoothing) if smoothing.mapping_mode() == MappingMode::ByEdge => {
+                SmoothingRule::Fans(smooth_fans(pvs, &smoothing, &pv_polys)?)
+            }
+            Some(smoothing)
+                if matches!(
+                    smoothing.mapping_mode(),
+                    MappingMode::ByPolygon | MappingMode::AllSame
+                ) =>
+            {
+                SmoothingRule::Groups(
+                    (0..face_normals.len())
+                        .map(|poly_i| smoothing.smoothing_group(PolygonIndex::new(poly_i)))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )
+            }
+            _ => SmoothingRule::CreaseAngle(self.crease_angle),
+        };
+        let is_smooth = |pvi: usize, other: usize| {
+            let (p, q) = (pv_polys[pvi], pv_polys[other]);
+            if p == q {
+                return true;
+            }
+            match &rule {
+                SmoothingRule::Groups(groups) => groups[p] & groups[q] != 0,
+                SmoothingRule::Fans(fans) => fans[pvi] == fans[other],
+                SmoothingRule::CreaseAngle(angle) => {
+                    *angle >= std::f64::consts::PI
+                        || dot(face_normals[p], face_normals[q]) >= angle.cos()
+                }
+            }
+        };
+
+        let mut normals = Vec::with_capacity(pv_len * 3);
+        for (pvi, &poly_i) in pv_polys.iter().enumerate() {
+            let cpi = pvs
+                .polygon_vertex(PolygonVertexIndex::new(pvi))
+                .expect("Should never fail: the index is in range")
+                .to_u32();
+            let sum = corners_by_cp[&cpi]
+                .iter()
+                .filter(|&&other| is_smooth(pvi, other))
+                .fold(zero(), |sum, &other| add(sum, contributions[other]));
+            let normal = if dot(sum, sum) > 0.0 {
+                normalize(sum)
+            } else {
+                face_normals[poly_i]
+            };
+            normals.extend_from_slice(&[normal.x, normal.y, normal.z]);
+        }
+
+        Ok(GeneratedNormals { normals })
+    }
+}
+
+/// Rule to determine whether polygon vertices are smoothed with each other.
+#[derive(Debug, Clone)]
+enum SmoothingRule {
+    /// Smoothing groups of polygons.
+    Groups(Vec<u32>),
+    /// Fans of polygon vertices connected by soft edges.
+    Fans(Vec<usize>),
+    /// Crease angle in radians.
+    CreaseAngle(f64),
+}
+
+/// Returns the fan of each polygon vertex.
+///
+/// Polygon vertices at the same control point and connected by soft edges
+/// belong to the same fan.
+fn smooth_fans(
+    pvs: &PolygonVertices<'_>,
+    smoothing: &Smoothing<'_>,
+    pv_polys: &[usize],
+) -> Result<Vec<usize>, Error> {
+    let edges = pvs.edges()?;
+    let ranges = pvs.polygon_ranges()?;
+    let cpi = |pvi: usize| {
+        pvs.polygon_vertex(PolygonVertexIndex::new(pvi))
+            .expect("Should never fail: the index is in range")
+            .to_u32()
+    };
+
+    // Union-find.
+    let mut parents = (0..pv_polys.len()).collect::<Vec<_>>();
+    for edge in edges.iter() {
+        if !smoothing.is_soft_edge(edge)? {
+            continue;
+        }
+        let [start, end] = match edges.polygon_vertex_indices(edge) {
+            Some(v) => v,
+            None => continue,
+        };
+        for pvi in [start.to_usize(), end.to_usize()] {
+            let cp = cpi(pvi);
+            for &poly_i in edges.polygons(edge) {
+                let corner = ranges[poly_i.to_usize()]
+                    .clone()
+                    .find(|&other| cpi(other) == cp);
+                if let Some(corner) = corner {
+                    let (a, b) = (find(&mut parents, pvi), find(&mut parents, corner));
+                    parents[a] = b;
+                }
+            }
+        }
+    }
+
+    Ok((0..pv_polys.len())
+        .map(|pvi| find(&mut parents, pvi))
+        .collect())
+}
+
+/// Returns the root of the union-find tree.
+fn find(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+/// Generated normals.
+///
+/// Normals are generated for each polygon vertex.
+#[derive(Debug, Clone)]
+pub struct GeneratedNormals {
+    /// Normals of polygon vertices.
+    normals: Vec<f64>,
+}
+
+impl GeneratedNormals {
+    /// Returns `Normals` data.
+    ///
+    /// This can be used in the same way as normals of `LayerElementNormal`.
+    pub fn normals(&self) -> Normals<'_> {
+        Normals::from_polygon_vertex_normals(&self.normals)
+    }
+
+    /// Returns the normal of the given polygon vertex.
+    ///
+    /// The zero vector is returned if the normal cannot be calculated (for
+    /// example, for isolated degenerate polygons).
+    pub fn normal(&self, pvi: PolygonVertexIndex) -> Option<Vector3<f64>> {
+        let i = pvi.to_usize() * 3;
+        self.normals.get(i..(i + 3)).map(Vector3::from_slice)
+    }
+}
+
+/// Returns the zero vector.
+fn zero() -> Vector3<f64> {
+    Vector3 {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    }
+}
+
+/// Converts a point into a vector.
+fn to_vec(p: mint::Point3<f64>) -> Vector3<f64> {
+    Vector3 {
+        x: p.x,
+        y: p.y,
+        z: p.z,
+    }
+}
+
+/// Returns the angle of the corner in radians.
+fn corner_angle(current: Vector3<f64>, prev: Vector3<f64>, next: Vector3<f64>) -> f64 {
+    let a = normalize(sub(prev, current));
+    let b = normalize(sub(next, current));
+    if dot(a, a) == 0.0 || dot(b, b) == 0.0 {
+        return 0.0;
+    }
+    dot(a, b).clamp(-1.0, 1.0).acos()
+}
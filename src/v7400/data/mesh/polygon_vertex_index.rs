@@ -1,9 +1,13 @@
 //! Polygon vertex index.
 
+use std::ops::Range;
+
 use anyhow::{bail, Error};
 use mint::Point3;
 
-use crate::v7400::data::mesh::{ControlPointIndex, ControlPoints, TriangleVertices};
+use crate::v7400::data::mesh::{
+    ControlPointIndex, ControlPoints, Edges, NormalGenerator, TriangleVertices,
+};
 
 /// Polygon vertex index.
 ///
@@ -52,6 +56,8 @@ pub struct PolygonVertices<'a> {
     control_points: ControlPoints<'a>,
     /// Polygon vertices (control point indices).
     polygon_vertices: RawPolygonVertices<'a>,
+    /// Edges (polygon vertex indices).
+    edges: Option<&'a [i32]>,
 }
 
 impl<'a> PolygonVertices<'a> {
@@ -59,10 +65,12 @@ impl<'a> PolygonVertices<'a> {
     pub(crate) fn new(
         control_points: ControlPoints<'a>,
         polygon_vertices: RawPolygonVertices<'a>,
+        edges: Option<&'a [i32]>,
     ) -> Self {
         Self {
             control_points,
             polygon_vertices,
+            edges,
         }
     }
 
@@ -76,11 +84,54 @@ impl<'a> PolygonVertices<'a> {
         self.polygon_vertices.data
     }
 
+    /// Returns a slice of the raw edges (polygon vertex indices), if
+    /// available.
+    pub(crate) fn raw_edges(&self) -> Option<&'a [i32]> {
+        self.edges
+    }
+
+    /// Returns edges.
+    ///
+    /// This builds the lookup tables of the edges, so build once and reuse it
+    /// for many lookups.
+    ///
+    /// This fails if the mesh has no `Edges`.
+    pub fn edges(&self) -> Result<Edges<'a>, Error> {
+        Edges::new(self)
+    }
+
     /// Returns a polygon vertex at the given index.
     pub fn polygon_vertex(&self, pvi: PolygonVertexIndex) -> Option<PolygonVertex> {
         self.polygon_vertices.get(pvi)
     }
 
+    /// Returns an iterator of polygons.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use fbxcel_dom::v7400::object::geometry::MeshHandle;
+    /// # fn get_mesh<'a>() -> MeshHandle<'a> { unimplemented!() }
+    /// let mesh: MeshHandle<'_> = get_mesh();
+    /// let polygon_vertices = mesh.polygon_vertices()?;
+    /// for polygon in polygon_vertices.polygons()? {
+    ///     let cpis = polygon
+    ///         .iter()
+    ///         .map(|pvi| polygon_vertices.polygon_vertex(pvi).expect("Should never fail"))
+    ///         .map(|pv| pv.to_u32())
+    ///         .collect::<Vec<_>>();
+    ///     println!("{:?}: {:?}", polygon.polygon_index(), cpis);
+    /// }
+    /// # Ok::<_, anyhow::Error>(())
+    /// ```
+    pub fn polygons(&self) -> Result<impl Iterator<Item = PolygonVertexRange>, Error> {
+        Ok(self
+            .polygon_ranges()?
+            .into_iter()
+            .enumerate()
+            .map(|(poly_i, range)| PolygonVertexRange::new(PolygonIndex::new(poly_i), range)))
+    }
+
     /// Returns a control point at the given index.
     pub fn control_point(&self, i: impl Into<IntoCpiWithPolyVerts>) -> Option<Point3<f64>> {
         i.into()
@@ -88,7 +139,38 @@ impl<'a> PolygonVertices<'a> {
             .and_then(|cpi| self.control_points.get(cpi))
     }
 
+    /// Returns a smooth normal generator.
+    pub fn normal_generator(&self) -> NormalGenerator<'a> {
+        NormalGenerator::new(self)
+    }
+
+    /// Returns ranges of polygon vertex indices of each polygon.
+    pub(crate) fn polygon_ranges(&self) -> Result<Vec<Range<usize>>, Error> {
+        let data = self.polygon_vertices.data;
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        for (i, &pv) in data.iter().enumerate() {
+            if PolygonVertex::new(pv).is_end() {
+                ranges.push(start..(i + 1));
+                start = i + 1;
+            }
+        }
+        if start != data.len() {
+            bail!(
+                "Incomplete polygon found: pv_index_start={:?}, len={}",
+                start,
+                data.len()
+            );
+        }
+
+        Ok(ranges)
+    }
+
     /// Triangulates the polygons and returns indices map.
+    ///
+    /// Built-in triangulators are available in [`triangulator`] module.
+    ///
+    /// [`triangulator`]: triangulator/index.html
     pub fn triangulate_each<F>(&self, mut triangulator: F) -> Result<TriangleVertices<'a>, Error>
     where
         F: FnMut(
@@ -188,12 +270,12 @@ impl From<&PolygonVertex> for ControlPointIndex {
 }
 
 /// Polygon index.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PolygonIndex(usize);
 
 impl PolygonIndex {
     /// Creates a new `PolygonIndex`.
-    fn new(v: usize) -> Self {
+    pub(crate) fn new(v: usize) -> Self {
         Self(v)
     }
 
@@ -209,6 +291,60 @@ impl PolygonIndex {
     }
 }
 
+/// Range of polygon vertex indices of a polygon.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PolygonVertexRange {
+    /// Polygon index.
+    polygon_index: PolygonIndex,
+    /// Range of polygon vertex indices.
+    range: Range<usize>,
+}
+
+impl PolygonVertexRange {
+    /// Creates a new `PolygonVertexRange`.
+    fn new(polygon_index: PolygonIndex, range: Range<usize>) -> Self {
+        Self {
+            polygon_index,
+            range,
+        }
+    }
+
+    /// Returns the polygon index.
+    pub fn polygon_index(&self) -> PolygonIndex {
+        self.polygon_index
+    }
+
+    /// Returns the number of polygon vertices.
+    pub fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    /// Returns whether the polygon has no polygon vertices.
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    /// Returns the first polygon vertex index.
+    pub fn start(&self) -> PolygonVertexIndex {
+        PolygonVertexIndex::new(self.range.start)
+    }
+
+    /// Returns the polygon vertex index next to the last one.
+    pub fn end(&self) -> PolygonVertexIndex {
+        PolygonVertexIndex::new(self.range.end)
+    }
+
+    /// Returns whether the polygon contains the given polygon vertex index.
+    pub fn contains(&self, pvi: PolygonVertexIndex) -> bool {
+        self.range.contains(&pvi.to_usize())
+    }
+
+    /// Returns an iterator of polygon vertex indices.
+    pub fn iter(&self) -> impl Iterator<Item = PolygonVertexIndex> {
+        self.range.clone().map(PolygonVertexIndex::new)
+    }
+}
+
 /// A type to contain a value convertible into control point index.
 ///
 /// This is used for [`PolygonVertices::control_point`], but not intended to be
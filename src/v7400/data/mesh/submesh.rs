@@ -0,0 +1,189 @@
+//! Per-material submesh.
+
+use std::collections::BTreeMap;
+
+use anyhow::Error;
+
+use crate::v7400::{
+    data::mesh::{
+        layer::material::{MaterialIndex, Materials},
+        PolygonIndex, PolygonVertices, TriangleIndex, TriangleVertexIndex, TriangleVertices,
+    },
+    object::material::MaterialHandle,
+};
+
+/// Triangles of a mesh with the same material.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use fbxcel_dom::v7400::object::model::MeshHandle;
+/// use fbxcel_dom::v7400::data::mesh::triangulator;
+///
+/// # fn get_mesh<'a>() -> MeshHandle<'a> { unimplemented!() }
+/// let mesh: MeshHandle<'_> = get_mesh();
+/// let tris = mesh
+///     .geometry()?
+///     .polygon_vertices()?
+///     .triangulate_each(triangulator::ear_clipping)?;
+/// for submesh in mesh.submeshes(&tris)? {
+///     let name = submesh.material().and_then(|material| material.name());
+///     println!(
+///         "material {:?} ({:?}): {} triangles",
+///         submesh.material_index(),
+///         name,
+///         submesh.triangles().len()
+///     );
+/// }
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Submesh<'a> {
+    /// Material index.
+    material_index: MaterialIndex,
+    /// Material.
+    material: Option<MaterialHandle<'a>>,
+    /// Triangles.
+    triangles: Vec<TriangleIndex>,
+}
+
+impl<'a> Submesh<'a> {
+    /// Splits the triangles into submeshes.
+    ///
+    /// `materials` should be sorted in the connection order.
+    /// If `material_indices` is `None`, all triangles use the material 0.
+    pub(crate) fn split(
+        tris: &TriangleVertices<'a>,
+        material_indices: Option<&Materials<'a>>,
+        materials: &[MaterialHandle<'a>],
+    ) -> Result<Vec<Self>, Error> {
+        let mut triangles = BTreeMap::<_, Vec<_>>::new();
+        for tri_i in 0..(tris.len() / 3) {
+            let material_index = match material_indices {
+                Some(material_indices) => {
+                    material_indices.material_index(tris, TriangleVertexIndex::new(tri_i * 3))?
+                }
+                None => MaterialIndex::new(0),
+            };
+            triangles
+                .entry(material_index)
+                .or_default()
+                .push(TriangleIndex::new(tri_i));
+        }
+
+        Ok(triangles
+            .into_iter()
+            .map(|(material_index, triangles)| Self {
+                material_index,
+                material: materials.get(material_index.to_u32() as usize).copied(),
+                triangles,
+            })
+            .collect())
+    }
+
+    /// Returns the material index.
+    pub fn material_index(&self) -> MaterialIndex {
+        self.material_index
+    }
+
+    /// Returns the material.
+    ///
+    /// Returns `None` if the material index is out of range of the materials
+    /// connected to the model.
+    pub fn material(&self) -> Option<MaterialHandle<'a>> {
+        self.material
+    }
+
+    /// Returns triangles of the submesh, in ascending order.
+    pub fn triangles(&self) -> &[TriangleIndex] {
+        &self.triangles
+    }
+
+    /// Returns an iterator of triangle vertex indices of the submesh.
+    pub fn triangle_vertex_indices(&self) -> impl Iterator<Item = TriangleVertexIndex> + '_ {
+        self.triangles.iter().flat_map(|tri_i| {
+            let base = tri_i.to_usize() * 3;
+            (base..(base + 3)).map(TriangleVertexIndex::new)
+        })
+    }
+}
+
+/// Polygons of a mesh with the same material.
+///
+/// Unlike [`Submesh`], this is independent of triangulation.
+///
+/// [`Submesh`]: struct.Submesh.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # use fbxcel_dom::v7400::object::model::MeshHandle;
+/// # fn get_mesh<'a>() -> MeshHandle<'a> { unimplemented!() }
+/// let mesh: MeshHandle<'_> = get_mesh();
+/// for submesh in mesh.polygon_submeshes()? {
+///     println!(
+///         "material {:?}: {} polygons",
+///         submesh.material_index(),
+///         submesh.polygons().len()
+///     );
+/// }
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct PolygonSubmesh<'a> {
+    /// Material index.
+    material_index: MaterialIndex,
+    /// Material.
+    material: Option<MaterialHandle<'a>>,
+    /// Polygons.
+    polygons: Vec<PolygonIndex>,
+}
+
+impl<'a> PolygonSubmesh<'a> {
+    /// Splits the polygons into submeshes.
+    ///
+    /// `materials` should be sorted in the connection order.
+    /// If `material_indices` is `None`, all polygons use the material 0.
+    pub(crate) fn split(
+        polygon_vertices: &PolygonVertices<'a>,
+        material_indices: Option<&Materials<'a>>,
+        materials: &[MaterialHandle<'a>],
+    ) -> Result<Vec<Self>, Error> {
+        let mut polygons = BTreeMap::<_, Vec<_>>::new();
+        for polygon in polygon_vertices.polygons()? {
+            let poly_i = polygon.polygon_index();
+            let material_index = match material_indices {
+                Some(material_indices) => material_indices.material_index_by_polygon(poly_i)?,
+                None => MaterialIndex::new(0),
+            };
+            polygons.entry(material_index).or_default().push(poly_i);
+        }
+
+        Ok(polygons
+            .into_iter()
+            .map(|(material_index, polygons)| Self {
+                material_index,
+                material: materials.get(material_index.to_u32() as usize).copied(),
+                polygons,
+            })
+            .collect())
+    }
+
+    /// Returns the material index.
+    pub fn material_index(&self) -> MaterialIndex {
+        self.material_index
+    }
+
+    /// Returns the material.
+    ///
+    /// Returns `None` if the material index is out of range of the materials
+    /// connected to the model.
+    pub fn material(&self) -> Option<MaterialHandle<'a>> {
+        self.material
+    }
+
+    /// Returns polygons of the submesh, in ascending order.
+    pub fn polygons(&self) -> &[PolygonIndex] {
+        &self.polygons
+    }
+}
@@ -0,0 +1,544 @@
+//! MikkTSpace-compatible tangent generation.
+
+use std::collections::HashMap;
+
+use anyhow::{format_err, Error};
+use mint::{Vector3, Vector4};
+
+use crate::v7400::{
+    data::mesh::{
+        layer::{binormal::Binormals, normal::Normals, tangent::Tangents, uv::Uv},
+        PolygonVertexIndex, TriangleVertices,
+    },
+    math::{add, cross, dot, scale, sub},
+};
+
+/// Generated tangents and binormals.
+///
+/// Tangents are generated following the algorithm of [MikkTSpace] (with the
+/// default angular threshold), and are expected to be close to tangent spaces
+/// used by normal map bakers.
+///
+/// Note that tangents are generated from the given triangles.
+/// MikkTSpace receives quads as they are and chooses the diagonal of each quad
+/// by itself, so the result can differ from a baker which is given the
+/// original quads instead of the triangles (for example, if the triangulator
+/// splits a quad along the other diagonal).
+///
+/// Tangents and binormals are stored for each polygon vertex.
+///
+/// [MikkTSpace]: http://www.mikktspace.com/
+///
+/// # Examples
+///
+/// ```no_run
+/// # use fbxcel_dom::v7400::object::geometry::MeshHandle;
+/// use fbxcel_dom::v7400::data::mesh::{
+///     layer::{LayerElementType, TypedLayerElementHandle},
+///     triangulator, GeneratedTangents,
+/// };
+///
+/// # fn get_mesh<'a>() -> MeshHandle<'a> { unimplemented!() }
+/// let mesh: MeshHandle<'_> = get_mesh();
+/// let tris = mesh
+///     .polygon_vertices()?
+///     .triangulate_each(triangulator::ear_clipping)?;
+/// let generated_normals = mesh.normal_generator()?.generate()?;
+/// let uv = match mesh.layer_elements(LayerElementType::Uv).next() {
+///     Some(TypedLayerElementHandle::Uv(v)) => v.uv()?,
+///     _ => panic!("UV not found"),
+/// };
+/// let tangents =
+///     GeneratedTangents::from_triangle_vertices(&tris, &generated_normals.normals(), &uv)?;
+/// for tri_vi in tris.triangle_vertex_indices() {
+///     let tangent = tangents.tangents().tangent(&tris, tri_vi)?;
+/// }
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct GeneratedTangents {
+    /// Tangents of polygon vertices.
+    tangents: Vec<f64>,
+    /// Binormals of polygon vertices.
+    binormals: Vec<f64>,
+    /// Signs (handedness) of polygon vertices.
+    signs: Vec<f64>,
+}
+
+impl GeneratedTangents {
+    /// Generates tangents from the positions, normals, and UVs of the
+    /// triangle vertices.
+    pub fn from_triangle_vertices<'a>(
+        tris: &TriangleVertices<'a>,
+        normals: &Normals<'a>,
+        uv: &Uv<'a>,
+    ) -> Result<Self, Error> {
+        let vertices = tris
+            .triangle_vertex_indices()
+            .map(|tri_vi| {
+                let position = tris.control_point(tri_vi).ok_or_else(|| {
+                    format_err!("Failed to get control point: tri_vi={:?}", tri_vi)
+                })?;
+                let normal = normals.normal(tris, tri_vi)?;
+                let uv = uv.uv(tris, tri_vi)?;
+                Ok(Vertex {
+                    position: Vector3 {
+                        x: position.x,
+                        y: position.y,
+                        z: position.z,
+                    },
+                    normal,
+                    uv: [uv.x, uv.y],
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let tspaces = generate_tspaces(&vertices);
+
+        let pv_len = tris.polygon_vertices().raw_polygon_vertices().len();
+        let mut tangents = vec![0.0; pv_len * 3];
+        let mut binormals = vec![0.0; pv_len * 3];
+        let mut signs = vec![0.0; pv_len];
+        let mut written = vec![false; pv_len];
+        for (tri_vi, (vertex, tspace)) in tris
+            .triangle_vertex_indices()
+            .zip(vertices.iter().zip(&tspaces))
+        {
+            let pvi = tris
+                .polygon_vertex_index(tri_vi)
+                .ok_or_else(|| format_err!("Failed to get polygon vertex index: {:?}", tri_vi))?
+                .to_usize();
+            if written[pvi] {
+                continue;
+            }
+            written[pvi] = true;
+            let sign = if tspace.orient_preserving { 1.0 } else { -1.0 };
+            let binormal = scale(cross(vertex.normal, tspace.os), sign);
+            tangents[(pvi * 3)..(pvi * 3 + 3)].copy_from_slice(&[
+                tspace.os.x,
+                tspace.os.y,
+                tspace.os.z,
+            ]);
+            binormals[(pvi * 3)..(pvi * 3 + 3)]
+                .copy_from_slice(&[binormal.x, binormal.y, binormal.z]);
+            signs[pvi] = sign;
+        }
+
+        Ok(Self {
+            tangents,
+            binormals,
+            signs,
+        })
+    }
+
+    /// Returns `Tangents` data.
+    ///
+    /// This can be used in the same way as tangents of `LayerElementTangent`.
+    pub fn tangents(&self) -> Tangents<'_> {
+        Tangents::from_polygon_vertex_tangents(&self.tangents)
+    }
+
+    /// Returns `Binormals` data.
+    ///
+    /// This can be used in the same way as binormals of
+    /// `LayerElementBinormal`.
+    pub fn binormals(&self) -> Binormals<'_> {
+        Binormals::from_polygon_vertex_binormals(&self.binormals)
+    }
+
+    /// Returns the tangent of the given polygon vertex, with the sign of
+    /// the binormal as `w`.
+    ///
+    /// The binormal is `w * cross(normal, tangent)`.
+    /// The zero vector is returned for polygon vertices not used by any
+    /// triangles.
+    pub fn tangent(&self, pvi: PolygonVertexIndex) -> Option<Vector4<f64>> {
+        let i = pvi.to_usize();
+        let sign = *self.signs.get(i)?;
+        Some(Vector4 {
+            x: self.tangents[i * 3],
+            y: self.tangents[i * 3 + 1],
+            z: self.tangents[i * 3 + 2],
+            w: sign,
+        })
+    }
+}
+
+/// Attributes of a triangle vertex.
+#[derive(Debug, Clone, Copy)]
+struct Vertex {
+    /// Position.
+    position: Vector3<f64>,
+    /// Normal.
+    normal: Vector3<f64>,
+    /// UV.
+    uv: [f64; 2],
+}
+
+/// Tangent space.
+#[derive(Debug, Clone, Copy)]
+struct TSpace {
+    /// Tangent.
+    os: Vector3<f64>,
+    /// Whether the UV mapping is orientation preserving.
+    orient_preserving: bool,
+}
+
+impl Default for TSpace {
+    fn default() -> Self {
+        Self {
+            os: Vector3 {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            orient_preserving: false,
+        }
+    }
+}
+
+/// Per-triangle data.
+#[derive(Debug, Clone)]
+struct TriInfo {
+    /// Neighbor triangles on each edge.
+    ///
+    /// The `i`-th edge is from the `i`-th vertex to the next vertex.
+    neighbors: [Option<usize>; 3],
+    /// Groups of each vertex.
+    groups: [Option<usize>; 3],
+    /// Tangent (normalized if possible).
+    os: Vector3<f64>,
+    /// Bitangent (normalized if possible).
+    ot: Vector3<f64>,
+    /// Whether the UV mapping is orientation preserving.
+    orient_preserving: bool,
+    /// Whether the triangle has degenerate UVs and can be grouped with any
+    /// orientation.
+    group_with_any: bool,
+    /// Whether the triangle is degenerate.
+    degenerate: bool,
+}
+
+/// Group of triangles sharing a vertex, connected by edges, and with the
+/// same orientation.
+#[derive(Debug, Clone)]
+struct Group {
+    /// Representative (welded) vertex.
+    vertex: u32,
+    /// Whether the UV mapping is orientation preserving.
+    orient_preserving: bool,
+    /// Triangles.
+    faces: Vec<usize>,
+}
+
+/// Generates tangent spaces for the triangle vertices.
+fn generate_tspaces(vertices: &[Vertex]) -> Vec<TSpace> {
+    let tri_len = vertices.len() / 3;
+    let welded = weld(vertices);
+    let mut infos = init_tri_infos(vertices, &welded);
+    build_neighbors(&welded, &mut infos);
+    let groups = build_groups(&welded, &mut infos);
+
+    let mut tspaces = vec![TSpace::default(); tri_len * 3];
+    let mut cache = HashMap::new();
+    let mut members = Vec::new();
+    for (group_i, group) in groups.iter().enumerate() {
+        for &face in &group.faces {
+            let corner = corner_of(&welded, face, group.vertex);
+            let n = vertices[face * 3 + corner].normal;
+            let (os, ot) = project_tspace(n, &infos[face]);
+            members.clear();
+            members.extend(group.faces.iter().copied().filter(|&other| {
+                if other == face || infos[face].group_with_any || infos[other].group_with_any {
+                    return true;
+                }
+                let (other_os, other_ot) = project_tspace(n, &infos[other]);
+                dot(os, other_os) > -1.0 && dot(ot, other_ot) > -1.0
+            }));
+            members.sort_unstable();
+            let tspace = *cache
+                .entry((group_i, members.clone()))
+                .or_insert_with(|| TSpace {
+                    orient_preserving: group.orient_preserving,
+                    ..eval_tspace(vertices, &welded, &infos, &members, group.vertex)
+                });
+            tspaces[face * 3 + corner] = tspace;
+        }
+    }
+
+    // Degenerate triangles use tangent spaces of the same vertex in other
+    // triangles.
+    let mut good_vertices = HashMap::new();
+    for (tri_vi, &vertex) in welded.iter().enumerate() {
+        if !infos[tri_vi / 3].degenerate {
+            good_vertices.entry(vertex).or_insert(tri_vi);
+        }
+    }
+    for (tri_vi, vertex) in welded.iter().enumerate() {
+        if infos[tri_vi / 3].degenerate {
+            if let Some(&src) = good_vertices.get(vertex) {
+                tspaces[tri_vi] = tspaces[src];
+            }
+        }
+    }
+
+    tspaces
+}
+
+/// Returns the indices of welded vertices.
+///
+/// Vertices with the same position, normal, and UV are welded.
+fn weld(vertices: &[Vertex]) -> Vec<u32> {
+    let mut indices = HashMap::new();
+    vertices
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let key = [
+                float_key(v.position.x),
+                float_key(v.position.y),
+                float_key(v.position.z),
+                float_key(v.normal.x),
+                float_key(v.normal.y),
+                float_key(v.normal.z),
+                float_key(v.uv[0]),
+                float_key(v.uv[1]),
+            ];
+            *indices.entry(key).or_insert(i as u32)
+        })
+        .collect()
+}
+
+/// Initializes per-triangle data.
+fn init_tri_infos(vertices: &[Vertex], welded: &[u32]) -> Vec<TriInfo> {
+    (0..(vertices.len() / 3))
+        .map(|tri_i| {
+            let mut info = TriInfo {
+                neighbors: [None; 3],
+                groups: [None; 3],
+                os: zero(),
+                ot: zero(),
+                orient_preserving: false,
+                group_with_any: true,
+                degenerate: false,
+            };
+            let w = &welded[(tri_i * 3)..(tri_i * 3 + 3)];
+            if w[0] == w[1] || w[0] == w[2] || w[1] == w[2] {
+                info.degenerate = true;
+                return info;
+            }
+
+            let [v1, v2, v3] = [
+                vertices[tri_i * 3],
+                vertices[tri_i * 3 + 1],
+                vertices[tri_i * 3 + 2],
+            ];
+            let t21x = v2.uv[0] - v1.uv[0];
+            let t21y = v2.uv[1] - v1.uv[1];
+            let t31x = v3.uv[0] - v1.uv[0];
+            let t31y = v3.uv[1] - v1.uv[1];
+            let d1 = sub(v2.position, v1.position);
+            let d2 = sub(v3.position, v1.position);
+
+            let signed_area_x2 = t21x * t31y - t21y * t31x;
+            let mut os = sub(scale(d1, t31y), scale(d2, t21y));
+            let mut ot = sub(scale(d2, t21x), scale(d1, t31x));
+            info.orient_preserving = signed_area_x2 > 0.0;
+            if not_zero(signed_area_x2) {
+                let abs_area = signed_area_x2.abs();
+                let len_os = dot(os, os).sqrt();
+                let len_ot = dot(ot, ot).sqrt();
+                let s = if info.orient_preserving { 1.0 } else { -1.0 };
+                if not_zero(len_os) {
+                    os = scale(os, s / len_os);
+                }
+                if not_zero(len_ot) {
+                    ot = scale(ot, s / len_ot);
+                }
+                let mag_s = len_os / abs_area;
+                let mag_t = len_ot / abs_area;
+                if not_zero(mag_s) && not_zero(mag_t) {
+                    info.group_with_any = false;
+                }
+            }
+            info.os = os;
+            info.ot = ot;
+
+            info
+        })
+        .collect()
+}
+
+/// Finds neighbor triangles sharing edges.
+fn build_neighbors(welded: &[u32], infos: &mut [TriInfo]) {
+    let mut edges = HashMap::<_, Vec<_>>::new();
+    for (tri_i, info) in infos.iter().enumerate() {
+        if info.degenerate {
+            continue;
+        }
+        for i in 0..3 {
+            let edge = (welded[tri_i * 3 + i], welded[tri_i * 3 + (i + 1) % 3]);
+            edges.entry(edge).or_default().push((tri_i, i));
+        }
+    }
+    for tri_i in 0..infos.len() {
+        if infos[tri_i].degenerate {
+            continue;
+        }
+        for i in 0..3 {
+            if infos[tri_i].neighbors[i].is_some() {
+                continue;
+            }
+            let reversed = (welded[tri_i * 3 + (i + 1) % 3], welded[tri_i * 3 + i]);
+            let found = edges.get(&reversed).and_then(|candidates| {
+                candidates
+                    .iter()
+                    .copied()
+                    .find(|&(other, j)| other != tri_i && infos[other].neighbors[j].is_none())
+            });
+            if let Some((other, j)) = found {
+                infos[tri_i].neighbors[i] = Some(other);
+                infos[other].neighbors[j] = Some(tri_i);
+            }
+        }
+    }
+}
+
+/// Builds groups of triangles around each vertex.
+fn build_groups(welded: &[u32], infos: &mut [TriInfo]) -> Vec<Group> {
+    let mut groups = Vec::new();
+    let mut stack = Vec::new();
+    for tri_i in 0..infos.len() {
+        if infos[tri_i].degenerate {
+            continue;
+        }
+        for i in 0..3 {
+            if infos[tri_i].groups[i].is_some() {
+                continue;
+            }
+            let group_i = groups.len();
+            let mut group = Group {
+                vertex: welded[tri_i * 3 + i],
+                orient_preserving: infos[tri_i].orient_preserving,
+                faces: vec![tri_i],
+            };
+            infos[tri_i].groups[i] = Some(group_i);
+            stack.clear();
+            push_neighbors(&mut stack, &infos[tri_i], i);
+            while let Some(face) = stack.pop() {
+                let corner = corner_of(welded, face, group.vertex);
+                let info = &mut infos[face];
+                if info.groups[corner].is_some() {
+                    continue;
+                }
+                if info.group_with_any && info.groups.iter().all(Option::is_none) {
+                    // The first group determines the orientation.
+                    info.orient_preserving = group.orient_preserving;
+                }
+                if info.orient_preserving != group.orient_preserving {
+                    continue;
+                }
+                group.faces.push(face);
+                info.groups[corner] = Some(group_i);
+                push_neighbors(&mut stack, info, corner);
+            }
+            groups.push(group);
+        }
+    }
+
+    groups
+}
+
+/// Pushes neighbor triangles sharing the vertex at the given corner, in the
+/// reverse order of visit.
+fn push_neighbors(stack: &mut Vec<usize>, info: &TriInfo, corner: usize) {
+    let left = info.neighbors[corner];
+    let right = info.neighbors[(corner + 2) % 3];
+    stack.extend(right);
+    stack.extend(left);
+}
+
+/// Returns the corner of the triangle at the given (welded) vertex.
+fn corner_of(welded: &[u32], tri_i: usize, vertex: u32) -> usize {
+    (0..3)
+        .find(|&i| welded[tri_i * 3 + i] == vertex)
+        .expect("Should never fail: grouped triangles share the vertex")
+}
+
+/// Returns the tangent and bitangent of the triangle projected onto the plane
+/// perpendicular to the normal.
+fn project_tspace(n: Vector3<f64>, info: &TriInfo) -> (Vector3<f64>, Vector3<f64>) {
+    let os = normalize_safe(sub(info.os, scale(n, dot(n, info.os))));
+    let ot = normalize_safe(sub(info.ot, scale(n, dot(n, info.ot))));
+    (os, ot)
+}
+
+/// Evaluates the tangent space at the vertex from the given triangles.
+fn eval_tspace(
+    vertices: &[Vertex],
+    welded: &[u32],
+    infos: &[TriInfo],
+    faces: &[usize],
+    vertex: u32,
+) -> TSpace {
+    let mut os = zero();
+    for &face in faces {
+        let info = &infos[face];
+        if info.group_with_any {
+            continue;
+        }
+        let corner = corner_of(welded, face, vertex);
+        let n = vertices[face * 3 + corner].normal;
+        let (face_os, _) = project_tspace(n, info);
+
+        let p0 = vertices[face * 3 + (corner + 2) % 3].position;
+        let p1 = vertices[face * 3 + corner].position;
+        let p2 = vertices[face * 3 + (corner + 1) % 3].position;
+        let v1 = sub(p0, p1);
+        let v1 = normalize_safe(sub(v1, scale(n, dot(n, v1))));
+        let v2 = sub(p2, p1);
+        let v2 = normalize_safe(sub(v2, scale(n, dot(n, v2))));
+        let angle = dot(v1, v2).clamp(-1.0, 1.0).acos();
+
+        os = add(os, scale(face_os, angle));
+    }
+
+    TSpace {
+        os: normalize_safe(os),
+        orient_preserving: false,
+    }
+}
+
+/// Returns the zero vector.
+fn zero() -> Vector3<f64> {
+    Vector3 {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    }
+}
+
+/// Returns whether the value is not (almost) zero.
+fn not_zero(v: f64) -> bool {
+    v.abs() > f64::from(f32::MIN_POSITIVE)
+}
+
+/// Normalizes the vector if its length is not (almost) zero.
+fn normalize_safe(v: Vector3<f64>) -> Vector3<f64> {
+    let len = dot(v, v).sqrt();
+    if not_zero(len) {
+        scale(v, 1.0 / len)
+    } else {
+        v
+    }
+}
+
+/// Returns the key of the float value for welding.
+///
+/// Negative zero is treated as same as positive zero.
+fn float_key(v: f64) -> u64 {
+    if v == 0.0 {
+        0
+    } else {
+        v.to_bits()
+    }
+}
@@ -1,9 +1,13 @@
 //! Triangle vertex index.
 
+use std::sync::{Arc, Mutex, PoisonError};
+
+use anyhow::Error;
 use mint::Point3;
 
 use crate::v7400::data::mesh::{
-    ControlPointIndex, PolygonIndex, PolygonVertex, PolygonVertexIndex, PolygonVertices,
+    edge::EdgeTables, ControlPointIndex, Edges, NormalGenerator, PolygonIndex, PolygonVertex,
+    PolygonVertexIndex, PolygonVertices,
 };
 
 /// Triange vertex index.
@@ -36,7 +40,7 @@ impl TriangleVertexIndex {
 /// Triangle vertices (this is arary of control point indices).
 ///
 /// "Triangle vertex" means "index of control point".
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct TriangleVertices<'a> {
     /// Source polygon vertices which can contain non-triangles.
     polygon_vertices: PolygonVertices<'a>,
@@ -44,6 +48,8 @@ pub struct TriangleVertices<'a> {
     tri_pv_indices: Vec<PolygonVertexIndex>,
     /// A map from triangle index to polygon index.
     tri_poly_indices: Vec<PolygonIndex>,
+    /// Lookup tables of edges, built on the first use.
+    edge_tables: Mutex<Option<Arc<EdgeTables>>>,
 }
 
 impl<'a> TriangleVertices<'a> {
@@ -57,6 +63,7 @@ impl<'a> TriangleVertices<'a> {
             polygon_vertices,
             tri_pv_indices,
             tri_poly_indices,
+            edge_tables: Mutex::new(None),
         }
     }
 
@@ -113,19 +120,62 @@ impl<'a> TriangleVertices<'a> {
         self.tri_poly_indices.get(tri_i.to_usize()).cloned()
     }
 
+    /// Returns edges of the source polygon vertices.
+    ///
+    /// Edges are built on the first call, and cached for later calls.
+    ///
+    /// This fails if the mesh has no `Edges` or has invalid `Edges`.
+    pub fn edges(&self) -> Result<Edges<'a>, Error> {
+        let mut tables = self
+            .edge_tables
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(tables) = &*tables {
+            return Ok(Edges::with_tables(&self.polygon_vertices, tables.clone()));
+        }
+        let edges = self.polygon_vertices.edges()?;
+        *tables = Some(edges.tables().clone());
+
+        Ok(edges)
+    }
+
+    /// Returns a smooth normal generator for the source polygon vertices.
+    ///
+    /// Normals are generated for polygons, not for triangles, so they can be
+    /// used with any triangulation of the polygons.
+    pub fn normal_generator(&self) -> NormalGenerator<'a> {
+        self.polygon_vertices.normal_generator()
+    }
+
     /// Returns an iterator of triangle vertex indices.
     pub fn triangle_vertex_indices(&self) -> impl Iterator<Item = TriangleVertexIndex> {
         (0..self.len()).map(TriangleVertexIndex::new)
     }
 }
 
+impl Clone for TriangleVertices<'_> {
+    fn clone(&self) -> Self {
+        let edge_tables = self
+            .edge_tables
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        Self {
+            polygon_vertices: self.polygon_vertices,
+            tri_pv_indices: self.tri_pv_indices.clone(),
+            tri_poly_indices: self.tri_poly_indices.clone(),
+            edge_tables: Mutex::new(edge_tables),
+        }
+    }
+}
+
 /// Triangle index.
 #[derive(Debug, Clone, Copy)]
 pub struct TriangleIndex(usize);
 
 impl TriangleIndex {
     /// Creates a new `TriangleIndex`.
-    fn new(v: usize) -> Self {
+    pub(crate) fn new(v: usize) -> Self {
         Self(v)
     }
 
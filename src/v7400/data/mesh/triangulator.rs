@@ -0,0 +1,207 @@
+//! Built-in triangulators.
+//!
+//! Functions in this module can be passed directly to
+//! [`PolygonVertices::triangulate_each()`].
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use fbxcel_dom::v7400::object::geometry::MeshHandle;
+//! use fbxcel_dom::v7400::data::mesh::triangulator;
+//!
+//! # fn get_mesh<'a>() -> MeshHandle<'a> { unimplemented!() }
+//! let mesh: MeshHandle<'_> = get_mesh();
+//! let tris = mesh
+//!     .polygon_vertices()?
+//!     .triangulate_each(triangulator::ear_clipping)?;
+//! # Ok::<_, anyhow::Error>(())
+//! ```
+//!
+//! [`PolygonVertices::triangulate_each()`]:
+//! ../struct.PolygonVertices.html#method.triangulate_each
+
+use anyhow::{format_err, Error};
+use mint::{Point3, Vector3};
+
+use crate::v7400::{
+    data::mesh::{PolygonVertexIndex, PolygonVertices},
+    math::{cross, dot, normalize, polygon_normal, sub},
+};
+
+/// Triangulates the polygon as a triangle fan.
+///
+/// This is fast, but produces correct results only for convex polygons.
+/// Polygons with less than 3 vertices produce no triangles.
+pub fn fan(
+    _pvs: &PolygonVertices<'_>,
+    poly_pvis: &[PolygonVertexIndex],
+    results: &mut Vec<[PolygonVertexIndex; 3]>,
+) -> Result<(), Error> {
+    if poly_pvis.len() < 3 {
+        return Ok(());
+    }
+    let first = poly_pvis[0];
+    results.extend(poly_pvis[1..].windows(2).map(|w| [first, w[0], w[1]]));
+    Ok(())
+}
+
+/// Triangulates the polygon by ear clipping on the best-fit plane.
+///
+/// The polygon is projected onto the plane calculated by Newell's method,
+/// so non-planar polygons are also supported.
+/// Concave polygons are correctly triangulated, and degenerate (for example
+/// self-intersecting or zero-area) polygons are triangulated without
+/// failure, though the result may contain degenerate triangles.
+/// Polygons with less than 3 vertices produce no triangles.
+///
+/// The winding order of the resulting triangles is same as the polygon.
+pub fn ear_clipping(
+    pvs: &PolygonVertices<'_>,
+    poly_pvis: &[PolygonVertexIndex],
+    results: &mut Vec<[PolygonVertexIndex; 3]>,
+) -> Result<(), Error> {
+    if poly_pvis.len() <= 3 {
+        return fan(pvs, poly_pvis, results);
+    }
+
+    let points = poly_pvis
+        .iter()
+        .map(|&pvi| {
+            pvs.control_point(pvi)
+                .ok_or_else(|| format_err!("Failed to get control point: pvi={:?}", pvi))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let points = match project_to_best_fit_plane(&points) {
+        Some(v) => v,
+        // All vertices are (almost) at the same point or on the same line.
+        None => return fan(pvs, poly_pvis, results),
+    };
+
+    // Tolerance for signed areas, relative to the polygon size.
+    let epsilon = {
+        let (min, max) = points.iter().fold(
+            ([f64::INFINITY; 2], [f64::NEG_INFINITY; 2]),
+            |(min, max), p| {
+                (
+                    [min[0].min(p[0]), min[1].min(p[1])],
+                    [max[0].max(p[0]), max[1].max(p[1])],
+                )
+            },
+        );
+        let size = (max[0] - min[0]).max(max[1] - min[1]);
+        size * size * 1e-12
+    };
+
+    let mut remaining = (0..points.len()).collect::<Vec<_>>();
+    while remaining.len() > 3 {
+        let len = remaining.len();
+        let corner = |i: usize| {
+            (
+                remaining[(i + len - 1) % len],
+                remaining[i],
+                remaining[(i + 1) % len],
+            )
+        };
+        let ear = (0..len).find(|&i| {
+            let (prev, current, next) = corner(i);
+            let (a, b, c) = (points[prev], points[current], points[next]);
+            if signed_area(a, b, c) <= epsilon {
+                // Reflex or degenerate corner.
+                return false;
+            }
+            !remaining.iter().any(|&other| {
+                other != prev
+                    && other != current
+                    && other != next
+                    && points[other] != a
+                    && points[other] != b
+                    && points[other] != c
+                    && is_in_triangle(points[other], a, b, c)
+            })
+        });
+        // If no ears are found (due to degeneracy or self-intersection),
+        // clip the most convex corner to ensure progress.
+        let ear = ear.unwrap_or_else(|| {
+            (0..len)
+                .max_by(|&i, &j| {
+                    let area = |i| {
+                        let (prev, current, next) = corner(i);
+                        signed_area(points[prev], points[current], points[next])
+                    };
+                    area(i)
+                        .partial_cmp(&area(j))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .expect("Should never fail: there are more than 3 vertices")
+        });
+        let (prev, current, next) = corner(ear);
+        results.push([poly_pvis[prev], poly_pvis[current], poly_pvis[next]]);
+        remaining.remove(ear);
+    }
+    results.push([
+        poly_pvis[remaining[0]],
+        poly_pvis[remaining[1]],
+        poly_pvis[remaining[2]],
+    ]);
+
+    Ok(())
+}
+
+/// Projects the points onto the best-fit plane.
+///
+/// The resulting 2D coordinates are counterclockwise if the polygon is
+/// counterclockwise around the plane normal.
+/// Returns `None` if the plane normal cannot be calculated.
+fn project_to_best_fit_plane(points: &[Point3<f64>]) -> Option<Vec<[f64; 2]>> {
+    let to_vec = |p: Point3<f64>| Vector3 {
+        x: p.x,
+        y: p.y,
+        z: p.z,
+    };
+    let normal = polygon_normal(points);
+    let length = dot(normal, normal).sqrt();
+    if !length.is_normal() {
+        return None;
+    }
+    let normal = normalize(normal);
+
+    // Orthonormal basis `(u, v, normal)`.
+    let helper = if normal.x.abs() < 0.9 {
+        Vector3 {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        }
+    } else {
+        Vector3 {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        }
+    };
+    let u = normalize(cross(helper, normal));
+    let v = cross(normal, u);
+
+    let origin = to_vec(points[0]);
+    Some(
+        points
+            .iter()
+            .map(|&p| {
+                let d = sub(to_vec(p), origin);
+                [dot(d, u), dot(d, v)]
+            })
+            .collect(),
+    )
+}
+
+/// Returns twice the signed area of the triangle (positive if
+/// counterclockwise).
+fn signed_area(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Returns whether the point is inside (or on the boundary of) the
+/// counterclockwise triangle.
+fn is_in_triangle(p: [f64; 2], a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> bool {
+    signed_area(a, b, p) >= 0.0 && signed_area(b, c, p) >= 0.0 && signed_area(c, a, p) >= 0.0
+}
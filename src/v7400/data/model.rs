@@ -0,0 +1,130 @@
+//! Model data.
+
+use std::convert::TryFrom;
+
+use anyhow::{bail, Error};
+
+use crate::v7400::object::property::{loaders::PrimitiveLoader, LoadProperty, PropertyHandle};
+
+/// Rotation order of euler angles.
+///
+/// The first axis in the name is applied first.
+/// For example, `Xyz` rotates around X axis, then Y axis, and then Z axis.
+///
+/// This corresponds to `EFbxRotationOrder` of FBX SDK.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RotationOrder {
+    /// X, Y, and then Z.
+    Xyz,
+    /// X, Z, and then Y.
+    Xzy,
+    /// Y, Z, and then X.
+    Yzx,
+    /// Y, X, and then Z.
+    Yxz,
+    /// Z, X, and then Y.
+    Zxy,
+    /// Z, Y, and then X.
+    Zyx,
+    /// Spheric XYZ.
+    ///
+    /// This is treated as `Xyz` when rotation matrices are calculated.
+    SphericXyz,
+}
+
+impl TryFrom<i32> for RotationOrder {
+    type Error = Error;
+
+    fn try_from(v: i32) -> Result<Self, Self::Error> {
+        match v {
+            0 => Ok(RotationOrder::Xyz),
+            1 => Ok(RotationOrder::Xzy),
+            2 => Ok(RotationOrder::Yzx),
+            3 => Ok(RotationOrder::Yxz),
+            4 => Ok(RotationOrder::Zxy),
+            5 => Ok(RotationOrder::Zyx),
+            6 => Ok(RotationOrder::SphericXyz),
+            v => bail!("Unexpected `RotationOrder` value: {:?}", v),
+        }
+    }
+}
+
+/// `RotationOrder` property loader.
+#[derive(Default, Debug, Clone, Copy)]
+pub(crate) struct RotationOrderLoader;
+
+impl<'a> LoadProperty<'a> for RotationOrderLoader {
+    type Value = RotationOrder;
+    type Error = Error;
+
+    fn expecting(&self) -> String {
+        "`i32` value as rotation order".into()
+    }
+
+    fn load(self, node: &PropertyHandle<'a>) -> Result<Self::Value, Self::Error> {
+        if node.data_type()? != "enum" {
+            bail!(
+                "Unexpected data type: expected \"enum\", but got {:?}",
+                node.data_type()
+            );
+        }
+        node.load_value(PrimitiveLoader::<i32>::new())
+            .and_then(TryFrom::try_from)
+    }
+}
+
+/// Transform inheritance type.
+///
+/// This specifies how the parent transform affects the child.
+/// In the names, `R` and `S` are parent rotation and scaling, and `r` and `s`
+/// are child (local) rotation and scaling.
+///
+/// This corresponds to `FbxTransform::EInheritType` of FBX SDK.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InheritType {
+    /// Parent rotation, child rotation, parent scaling, and child scaling.
+    RrSs,
+    /// Parent rotation, parent scaling, child rotation, and child scaling.
+    RSrs,
+    /// Parent rotation, child rotation, and child scaling.
+    ///
+    /// The local scaling of the parent is not inherited.
+    Rrs,
+}
+
+impl TryFrom<i32> for InheritType {
+    type Error = Error;
+
+    fn try_from(v: i32) -> Result<Self, Self::Error> {
+        match v {
+            0 => Ok(InheritType::RrSs),
+            1 => Ok(InheritType::RSrs),
+            2 => Ok(InheritType::Rrs),
+            v => bail!("Unexpected `InheritType` value: {:?}", v),
+        }
+    }
+}
+
+/// `InheritType` property loader.
+#[derive(Default, Debug, Clone, Copy)]
+pub(crate) struct InheritTypeLoader;
+
+impl<'a> LoadProperty<'a> for InheritTypeLoader {
+    type Value = InheritType;
+    type Error = Error;
+
+    fn expecting(&self) -> String {
+        "`i32` value as inherit type".into()
+    }
+
+    fn load(self, node: &PropertyHandle<'a>) -> Result<Self::Value, Self::Error> {
+        if node.data_type()? != "enum" {
+            bail!(
+                "Unexpected data type: expected \"enum\", but got {:?}",
+                node.data_type()
+            );
+        }
+        node.load_value(PrimitiveLoader::<i32>::new())
+            .and_then(TryFrom::try_from)
+    }
+}
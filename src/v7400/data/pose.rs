@@ -0,0 +1,39 @@
+//! Pose data.
+
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::{bail, Error};
+
+/// Pose type.
+///
+/// This corresponds to `FbxPose::IsBindPose()` and `FbxPose::IsRestPose()`
+/// of FBX SDK.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PoseType {
+    /// Bind pose.
+    ///
+    /// Matrices are global transforms of the nodes at binding time.
+    BindPose,
+    /// Rest pose.
+    RestPose,
+}
+
+impl TryFrom<&str> for PoseType {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "BindPose" => Ok(PoseType::BindPose),
+            "RestPose" => Ok(PoseType::RestPose),
+            s => bail!("Unexpected `PoseType` value: {:?}", s),
+        }
+    }
+}
+
+impl std::str::FromStr for PoseType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.try_into()
+    }
+}
@@ -0,0 +1,444 @@
+//! Skeleton.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{bail, format_err, Error};
+use mint::ColumnMatrix4;
+
+use crate::v7400::{
+    data::{axis::AxisConversion, unit::UnitConversion},
+    math::Mat4,
+    object::{
+        deformer::SkinHandle,
+        model::{ModelHandle, TransformEvaluator, TypedModelHandle},
+        pose::PoseHandle,
+        ObjectId, TypedObjectHandle,
+    },
+    Document,
+};
+
+/// Joint of a skeleton.
+#[derive(Debug, Clone, Copy)]
+pub struct Joint<'a> {
+    /// Model of the joint.
+    model: ModelHandle<'a>,
+    /// Parent joint index.
+    parent: Option<usize>,
+    /// Local transform at rest.
+    local_transform: ColumnMatrix4<f64>,
+    /// Global transform at binding time.
+    bind_matrix: ColumnMatrix4<f64>,
+    /// Inverse of the bind matrix.
+    inverse_bind_matrix: ColumnMatrix4<f64>,
+}
+
+impl<'a> Joint<'a> {
+    /// Returns the model of the joint.
+    pub fn model(&self) -> ModelHandle<'a> {
+        self.model
+    }
+
+    /// Returns the object ID of the joint model.
+    pub fn object_id(&self) -> ObjectId {
+        self.model.object_id()
+    }
+
+    /// Returns the name of the joint model.
+    pub fn name(&self) -> Option<&'a str> {
+        self.model.name()
+    }
+
+    /// Returns the parent joint index.
+    pub fn parent(&self) -> Option<usize> {
+        self.parent
+    }
+
+    /// Returns the local transform at rest.
+    ///
+    /// This is the world transform at rest relative to the parent joint,
+    /// i.e. `parent_world⁻¹ * world`, so non-joint models between joints are
+    /// folded into it.
+    /// For root joints, this is the world transform at rest.
+    pub fn local_transform(&self) -> ColumnMatrix4<f64> {
+        self.local_transform
+    }
+
+    /// Returns the global transform at binding time.
+    pub fn bind_matrix(&self) -> ColumnMatrix4<f64> {
+        self.bind_matrix
+    }
+
+    /// Returns the inverse bind matrix.
+    ///
+    /// This transforms global positions at binding time into the local space
+    /// of the joint.
+    /// Note that control points of a mesh are in the local space of the mesh,
+    /// so they should be transformed by the cluster `Transform` (see
+    /// [`ClusterHandle::transform()`]) before this matrix is applied.
+    ///
+    /// [`ClusterHandle::transform()`]:
+    /// ../../object/deformer/struct.ClusterHandle.html#method.transform
+    pub fn inverse_bind_matrix(&self) -> ColumnMatrix4<f64> {
+        self.inverse_bind_matrix
+    }
+}
+
+/// Skeleton, i.e. joint hierarchy.
+///
+/// Joints are sorted so that parents always precede their children, and
+/// joint indices are stable for the same input document.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use fbxcel_dom::v7400::object::deformer::SkinHandle;
+/// use fbxcel_dom::v7400::data::skeleton::Skeleton;
+///
+/// # fn skin() -> SkinHandle<'static> { unimplemented!() }
+/// let skin: SkinHandle<'_> = skin();
+/// let skeleton = Skeleton::from_skin(&skin)?;
+/// for (i, joint) in skeleton.joints().iter().enumerate() {
+///     println!("#{}: {:?} (parent={:?})", i, joint.name(), joint.parent());
+/// }
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Skeleton<'a> {
+    /// Joints.
+    joints: Vec<Joint<'a>>,
+    /// Joint indices.
+    indices: HashMap<ObjectId, usize>,
+}
+
+impl<'a> Skeleton<'a> {
+    /// Creates a skeleton from the skin.
+    ///
+    /// This is a shorthand for `SkeletonBuilder::new(doc).add_skin(skin)?.build()`.
+    pub fn from_skin(skin: &SkinHandle<'a>) -> Result<Self, Error> {
+        SkeletonBuilder::new(skin.document())
+            .add_skin(skin)?
+            .build()
+    }
+
+    /// Creates a skeleton from the root model (usually a `LimbNode`).
+    ///
+    /// This is a shorthand for `SkeletonBuilder::new(doc).add_root(root)?.build()`.
+    pub fn from_root(root: &ModelHandle<'a>) -> Result<Self, Error> {
+        SkeletonBuilder::new(root.document())
+            .add_root(root)?
+            .build()
+    }
+
+    /// Returns the number of joints.
+    pub fn len(&self) -> usize {
+        self.joints.len()
+    }
+
+    /// Returns whether the skeleton has no joints.
+    pub fn is_empty(&self) -> bool {
+        self.joints.is_empty()
+    }
+
+    /// Returns the joints.
+    pub fn joints(&self) -> &[Joint<'a>] {
+        &self.joints
+    }
+
+    /// Returns the joint at the given index.
+    pub fn joint(&self, index: usize) -> Option<&Joint<'a>> {
+        self.joints.get(index)
+    }
+
+    /// Returns the index of the joint for the given model.
+    pub fn joint_index(&self, object_id: ObjectId) -> Option<usize> {
+        self.indices.get(&object_id).copied()
+    }
+
+    /// Returns an iterator of root joint indices.
+    pub fn roots(&self) -> impl Iterator<Item = usize> + '_ {
+        self.joints
+            .iter()
+            .enumerate()
+            .filter(|(_, joint)| joint.parent.is_none())
+            .map(|(i, _)| i)
+    }
+
+    /// Returns parent indices of all joints.
+    pub fn parent_indices(&self) -> Vec<Option<usize>> {
+        self.joints.iter().map(|joint| joint.parent).collect()
+    }
+
+    /// Returns local transforms at rest of all joints.
+    pub fn local_transforms(&self) -> Vec<ColumnMatrix4<f64>> {
+        self.joints
+            .iter()
+            .map(|joint| joint.local_transform)
+            .collect()
+    }
+
+    /// Returns inverse bind matrices of all joints.
+    pub fn inverse_bind_matrices(&self) -> Vec<ColumnMatrix4<f64>> {
+        self.joints
+            .iter()
+            .map(|joint| joint.inverse_bind_matrix)
+            .collect()
+    }
+}
+
+/// Skeleton builder.
+///
+/// Skins and root models can be added to create a single skeleton shared by
+/// multiple meshes.
+#[derive(Debug, Clone)]
+pub struct SkeletonBuilder<'a> {
+    /// Document.
+    doc: &'a Document,
+    /// Joint models, in the order of addition.
+    models: Vec<ModelHandle<'a>>,
+    /// Object IDs of the joint models.
+    model_ids: HashSet<ObjectId>,
+    /// Bind matrices taken from clusters.
+    bind_matrices: HashMap<ObjectId, ColumnMatrix4<f64>>,
+    /// Axis conversion.
+    axis_conversion: Option<AxisConversion>,
+    /// Unit conversion.
+    unit_conversion: Option<UnitConversion>,
+}
+
+impl<'a> SkeletonBuilder<'a> {
+    /// Creates a new `SkeletonBuilder`.
+    pub fn new(doc: &'a Document) -> Self {
+        Self {
+            doc,
+            models: Vec::new(),
+            model_ids: HashSet::new(),
+            bind_matrices: HashMap::new(),
+            axis_conversion: None,
+            unit_conversion: None,
+        }
+    }
+
+    /// Sets the axis conversion applied to local transforms and bind
+    /// matrices of the joints.
+    pub fn axis_conversion(&mut self, conversion: Option<AxisConversion>) -> &mut Self {
+        self.axis_conversion = conversion;
+        self
+    }
+
+    /// Sets the unit conversion applied to local transforms and bind
+    /// matrices of the joints.
+    pub fn unit_conversion(&mut self, conversion: Option<UnitConversion>) -> &mut Self {
+        self.unit_conversion = conversion;
+        self
+    }
+
+    /// Adds joints linked from the clusters of the skin.
+    ///
+    /// Ancestor `LimbNode` models of the linked models are also added.
+    /// Bind matrices are taken from the clusters (see
+    /// [`ClusterHandle::bind_matrix()`]).
+    ///
+    /// [`ClusterHandle::bind_matrix()`]:
+    /// ../../object/deformer/struct.ClusterHandle.html#method.bind_matrix
+    pub fn add_skin(&mut self, skin: &SkinHandle<'a>) -> Result<&mut Self, Error> {
+        for cluster in skin.clusters() {
+            let model = match cluster.link_model() {
+                Some(v) => v,
+                None => continue,
+            };
+            if let Some(bind) = cluster.bind_matrix()? {
+                self.bind_matrices.entry(model.object_id()).or_insert(bind);
+            }
+            self.add_model(model);
+
+            let mut visited = HashSet::new();
+            let mut current = model;
+            while let Some(parent) = current.parent_model() {
+                if !visited.insert(parent.object_id()) {
+                    bail!(
+                        "Cyclic model hierarchy detected: object_id={:?}",
+                        parent.object_id()
+                    );
+                }
+                if !matches!(parent, TypedModelHandle::LimbNode(_)) {
+                    break;
+                }
+                self.add_model(*parent);
+                current = *parent;
+            }
+        }
+        Ok(self)
+    }
+
+    /// Adds the root model and its descendant `LimbNode` and `Null` models.
+    pub fn add_root(&mut self, root: &ModelHandle<'a>) -> Result<&mut Self, Error> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![*root];
+        while let Some(model) = stack.pop() {
+            if !visited.insert(model.object_id()) {
+                bail!(
+                    "Cyclic model hierarchy detected: object_id={:?}",
+                    model.object_id()
+                );
+            }
+            self.add_model(model);
+            let children = model
+                .child_models()
+                .filter(|child| {
+                    matches!(
+                        child,
+                        TypedModelHandle::LimbNode(_) | TypedModelHandle::Null(_)
+                    )
+                })
+                .map(|child| *child)
+                .collect::<Vec<_>>();
+            stack.extend(children.into_iter().rev());
+        }
+        Ok(self)
+    }
+
+    /// Adds the model as a joint.
+    fn add_model(&mut self, model: ModelHandle<'a>) {
+        if self.model_ids.insert(model.object_id()) {
+            self.models.push(model);
+        }
+    }
+
+    /// Creates the skeleton.
+    ///
+    /// Bind matrices of joints not taken from clusters are taken from bind
+    /// poses, and world transforms at rest are used if the joints are not in
+    /// any bind pose.
+    pub fn build(&self) -> Result<Skeleton<'a>, Error> {
+        let order = self.sorted_joints()?;
+        let bind_poses = self
+            .doc
+            .objects()
+            .filter_map(|obj| match obj.get_typed() {
+                TypedObjectHandle::Pose(o) => Some(o),
+                _ => None,
+            })
+            .filter(PoseHandle::is_bind_pose)
+            .collect::<Vec<_>>();
+        let mut evaluator = TransformEvaluator::new(self.doc);
+        evaluator
+            .set_axis_conversion(self.axis_conversion)
+            .set_unit_conversion(self.unit_conversion);
+
+        let mut joints: Vec<Joint<'a>> = Vec::with_capacity(order.len());
+        let mut indices = HashMap::with_capacity(order.len());
+        // World transforms at rest of the joints.
+        let mut worlds: Vec<Mat4> = Vec::with_capacity(order.len());
+        for (model, parent) in order {
+            let object_id = model.object_id();
+            let world = evaluator.world_transform_mat(&model)?;
+            let local_transform = match parent {
+                Some(parent) => {
+                    worlds[parent].inverse().ok_or_else(|| {
+                        format_err!(
+                            "Singular world transform of the parent joint: object_id={:?}",
+                            joints[parent].object_id()
+                        )
+                    })? * world
+                }
+                None => world,
+            };
+            worlds.push(world);
+            let bind_matrix = match self.bind_matrices.get(&object_id) {
+                Some(v) => evaluator.convert_matrix(Mat4::from(*v)),
+                None => match pose_matrix(&bind_poses, object_id)? {
+                    Some(v) => evaluator.convert_matrix(Mat4::from(v)),
+                    None => world,
+                },
+            };
+            let inverse_bind_matrix = bind_matrix
+                .inverse()
+                .ok_or_else(|| {
+                    format_err!("Singular bind matrix for joint: object_id={:?}", object_id)
+                })?
+                .into();
+            indices.insert(object_id, joints.len());
+            joints.push(Joint {
+                model,
+                parent,
+                local_transform: local_transform.into(),
+                bind_matrix: bind_matrix.into(),
+                inverse_bind_matrix,
+            });
+        }
+
+        Ok(Skeleton { joints, indices })
+    }
+
+    /// Returns joint models and parent indices, sorted so that parents
+    /// precede their children.
+    ///
+    /// Roots are sorted in the order of addition, and children are sorted in
+    /// the order of connections.
+    fn sorted_joints(&self) -> Result<Vec<(ModelHandle<'a>, Option<usize>)>, Error> {
+        let roots = self
+            .models
+            .iter()
+            .filter(|model| !self.has_joint_ancestor(model));
+
+        let mut sorted = Vec::with_capacity(self.models.len());
+        let mut visited = HashSet::new();
+        for root in roots {
+            // Depth-first traversal, including non-joint models between
+            // joints. Stack items are models and their nearest joint ancestor.
+            let mut stack = vec![(*root, None)];
+            while let Some((model, parent)) = stack.pop() {
+                if !visited.insert(model.object_id()) {
+                    continue;
+                }
+                let parent = if self.model_ids.contains(&model.object_id()) {
+                    sorted.push((model, parent));
+                    Some(sorted.len() - 1)
+                } else {
+                    parent
+                };
+                let children = model.child_models().map(|child| *child).collect::<Vec<_>>();
+                stack.extend(children.into_iter().rev().map(|child| (child, parent)));
+            }
+        }
+
+        if sorted.len() != self.models.len() {
+            bail!(
+                "Failed to sort joints: expected {} joints, but got {}",
+                self.models.len(),
+                sorted.len()
+            );
+        }
+        Ok(sorted)
+    }
+
+    /// Returns whether any ancestor of the model is a joint.
+    fn has_joint_ancestor(&self, model: &ModelHandle<'a>) -> bool {
+        let mut visited = HashSet::new();
+        let mut current = *model;
+        while let Some(parent) = current.parent_model() {
+            if self.model_ids.contains(&parent.object_id()) {
+                return true;
+            }
+            if !visited.insert(parent.object_id()) {
+                // Cyclic hierarchy.
+                return false;
+            }
+            current = *parent;
+        }
+        false
+    }
+}
+
+/// Returns the matrix of the object in the first pose which has it.
+fn pose_matrix(
+    poses: &[PoseHandle<'_>],
+    object_id: ObjectId,
+) -> Result<Option<ColumnMatrix4<f64>>, Error> {
+    for pose in poses {
+        if let Some(m) = pose.matrix(object_id)? {
+            return Ok(Some(m));
+        }
+    }
+    Ok(None)
+}
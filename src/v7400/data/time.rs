@@ -0,0 +1,460 @@
+//! Time data.
+
+use std::{convert::TryFrom, fmt};
+
+use anyhow::{bail, Error};
+
+use crate::v7400::object::property::{loaders::PrimitiveLoader, LoadProperty, PropertyHandle};
+
+/// FBX time (`KTime`), in ticks.
+///
+/// A second is 46186158000 ticks.
+///
+/// This corresponds to `FbxTime` of FBX SDK.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FbxTime(i64);
+
+impl FbxTime {
+    /// Number of ticks in a second.
+    pub const TICKS_PER_SECOND: i64 = 46_186_158_000;
+
+    /// Zero.
+    pub const ZERO: Self = Self(0);
+
+    /// Creates a new `FbxTime` from the ticks.
+    pub fn new(ticks: i64) -> Self {
+        Self(ticks)
+    }
+
+    /// Creates a new `FbxTime` from the seconds.
+    ///
+    /// The result is rounded to the nearest tick.
+    pub fn from_seconds(seconds: f64) -> Self {
+        Self((seconds * Self::TICKS_PER_SECOND as f64).round() as i64)
+    }
+
+    /// Creates a new `FbxTime` from the frames at the given frame rate.
+    ///
+    /// The result is rounded to the nearest tick.
+    pub fn from_frames(frames: f64, frame_rate: f64) -> Self {
+        Self::from_seconds(frames / frame_rate)
+    }
+
+    /// Returns the ticks.
+    pub fn ticks(self) -> i64 {
+        self.0
+    }
+
+    /// Returns the seconds.
+    pub fn seconds(self) -> f64 {
+        self.0 as f64 / Self::TICKS_PER_SECOND as f64
+    }
+
+    /// Returns the frames (including fraction) at the given frame rate.
+    pub fn frames(self, frame_rate: f64) -> f64 {
+        self.seconds() * frame_rate
+    }
+
+    /// Returns the frame number at the given frame rate.
+    ///
+    /// The frame number is rounded down, but times very close to the start
+    /// of a frame are treated as the frame, to absorb rounding errors of
+    /// non-integer frame rates.
+    pub fn frame_count(self, frame_rate: f64) -> i64 {
+        let frames = self.frames(frame_rate);
+        let rounded = frames.round();
+        if (frames - rounded).abs() < 1e-6 {
+            rounded as i64
+        } else {
+            frames.floor() as i64
+        }
+    }
+
+    /// Returns the SMPTE timecode for the given time mode.
+    ///
+    /// `custom_frame_rate` is used only when the time mode is
+    /// [`TimeMode::Custom`].
+    ///
+    /// Frames are counted by the actual frame rate, and the frames field
+    /// wraps at the nominal (rounded) frame rate, e.g. 30 for 29.97 fps.
+    ///
+    /// [`TimeMode::Custom`]: enum.TimeMode.html#variant.Custom
+    pub fn timecode(self, time_mode: TimeMode, custom_frame_rate: f64) -> Timecode {
+        let frame_rate = time_mode.frame_rate(custom_frame_rate);
+        let nominal_rate = (frame_rate.round() as i64).max(1);
+        let mut frame = self.frame_count(frame_rate);
+        let is_negative = frame < 0;
+        frame = frame.abs();
+
+        if time_mode.is_drop_frame() {
+            // Frame numbers 0 and 1 (for 30 fps) are skipped at the start of
+            // every minute, except for every tenth minute.
+            let drop = nominal_rate / 15;
+            let frames_per_minute = nominal_rate * 60 - drop;
+            let frames_per_10_minutes = frames_per_minute * 10 + drop;
+            let tens = frame / frames_per_10_minutes;
+            let rem = frame % frames_per_10_minutes;
+            frame += drop * 9 * tens;
+            if rem > drop {
+                frame += drop * ((rem - drop) / frames_per_minute);
+            }
+        }
+
+        let frames = frame % nominal_rate;
+        let total_seconds = frame / nominal_rate;
+        Timecode {
+            is_negative,
+            hours: total_seconds / 3600,
+            minutes: (total_seconds / 60 % 60) as u8,
+            seconds: (total_seconds % 60) as u8,
+            frames: frames as u32,
+            is_drop_frame: time_mode.is_drop_frame(),
+        }
+    }
+}
+
+impl From<i64> for FbxTime {
+    fn from(ticks: i64) -> Self {
+        Self(ticks)
+    }
+}
+
+impl From<FbxTime> for i64 {
+    fn from(time: FbxTime) -> Self {
+        time.0
+    }
+}
+
+impl std::ops::Add for FbxTime {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl std::ops::Sub for FbxTime {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+/// SMPTE timecode.
+///
+/// This is formatted as `HH:MM:SS:FF`, or `HH:MM:SS;FF` for drop frame
+/// timecode.
+///
+/// # Examples
+///
+/// ```
+/// # use fbxcel_dom::v7400::data::time::{FbxTime, TimeMode};
+/// let time = FbxTime::from_seconds(3723.5);
+/// assert_eq!(time.timecode(TimeMode::Frames30, 0.0).to_string(), "01:02:03:15");
+/// assert_eq!(time.timecode(TimeMode::Pal, 0.0).to_string(), "01:02:03:12");
+///
+/// // Drop frame timecode: frames 0 and 1 are skipped at 00:01:00.
+/// let time = FbxTime::from_frames(1800.0, 30000.0 / 1001.0);
+/// assert_eq!(time.timecode(TimeMode::NtscDropFrame, 0.0).to_string(), "00:01:00;02");
+/// // ...but not at 00:10:00.
+/// let time = FbxTime::from_frames(17982.0, 30000.0 / 1001.0);
+/// assert_eq!(time.timecode(TimeMode::NtscDropFrame, 0.0).to_string(), "00:10:00;00");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Timecode {
+    /// Whether the time is negative.
+    is_negative: bool,
+    /// Hours.
+    hours: i64,
+    /// Minutes.
+    minutes: u8,
+    /// Seconds.
+    seconds: u8,
+    /// Frames.
+    frames: u32,
+    /// Whether the timecode is drop frame.
+    is_drop_frame: bool,
+}
+
+impl Timecode {
+    /// Returns whether the time is negative.
+    ///
+    /// If this is true, other fields represents the absolute value.
+    pub fn is_negative(&self) -> bool {
+        self.is_negative
+    }
+
+    /// Returns the hours.
+    pub fn hours(&self) -> i64 {
+        self.hours
+    }
+
+    /// Returns the minutes.
+    pub fn minutes(&self) -> u8 {
+        self.minutes
+    }
+
+    /// Returns the seconds.
+    pub fn seconds(&self) -> u8 {
+        self.seconds
+    }
+
+    /// Returns the frames.
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    /// Returns whether the timecode is drop frame.
+    pub fn is_drop_frame(&self) -> bool {
+        self.is_drop_frame
+    }
+}
+
+impl fmt::Display for Timecode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_negative {
+            f.write_str("-")?;
+        }
+        let frame_sep = if self.is_drop_frame { ';' } else { ':' };
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hours, self.minutes, self.seconds, frame_sep, self.frames
+        )
+    }
+}
+
+/// Time mode (frame rate).
+///
+/// This corresponds to `FbxTime::EMode` of FBX SDK.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TimeMode {
+    /// Default mode (30 fps).
+    Default,
+    /// 120 fps.
+    Frames120,
+    /// 100 fps.
+    Frames100,
+    /// 60 fps.
+    Frames60,
+    /// 50 fps.
+    Frames50,
+    /// 48 fps.
+    Frames48,
+    /// 30 fps.
+    Frames30,
+    /// 30 fps, with drop frame timecode.
+    Frames30Drop,
+    /// NTSC drop frame (29.97 fps, with drop frame timecode).
+    NtscDropFrame,
+    /// NTSC full frame (29.97 fps).
+    NtscFullFrame,
+    /// PAL (25 fps).
+    Pal,
+    /// 24 fps.
+    Frames24,
+    /// 1000 fps.
+    Frames1000,
+    /// Film full frame (23.976 fps).
+    FilmFullFrame,
+    /// Custom frame rate.
+    ///
+    /// The frame rate is specified by `CustomFrameRate` global setting.
+    Custom,
+    /// 96 fps.
+    Frames96,
+    /// 72 fps.
+    Frames72,
+    /// 59.94 fps.
+    Frames59dot94,
+    /// 119.88 fps.
+    Frames119dot88,
+}
+
+impl TimeMode {
+    /// Returns the frame rate (frames per second).
+    ///
+    /// `custom_frame_rate` is used only when the time mode is `Custom`.
+    pub fn frame_rate(self, custom_frame_rate: f64) -> f64 {
+        match self {
+            TimeMode::Default | TimeMode::Frames30 | TimeMode::Frames30Drop => 30.0,
+            TimeMode::Frames120 => 120.0,
+            TimeMode::Frames100 => 100.0,
+            TimeMode::Frames60 => 60.0,
+            TimeMode::Frames50 => 50.0,
+            TimeMode::Frames48 => 48.0,
+            TimeMode::NtscDropFrame | TimeMode::NtscFullFrame => 30000.0 / 1001.0,
+            TimeMode::Pal => 25.0,
+            TimeMode::Frames24 => 24.0,
+            TimeMode::Frames1000 => 1000.0,
+            TimeMode::FilmFullFrame => 24000.0 / 1001.0,
+            TimeMode::Custom => custom_frame_rate,
+            TimeMode::Frames96 => 96.0,
+            TimeMode::Frames72 => 72.0,
+            TimeMode::Frames59dot94 => 60000.0 / 1001.0,
+            TimeMode::Frames119dot88 => 120000.0 / 1001.0,
+        }
+    }
+
+    /// Returns whether the time mode uses drop frame timecode.
+    pub fn is_drop_frame(self) -> bool {
+        matches!(self, TimeMode::Frames30Drop | TimeMode::NtscDropFrame)
+    }
+}
+
+impl TryFrom<i32> for TimeMode {
+    type Error = Error;
+
+    fn try_from(v: i32) -> Result<Self, Self::Error> {
+        match v {
+            0 => Ok(TimeMode::Default),
+            1 => Ok(TimeMode::Frames120),
+            2 => Ok(TimeMode::Frames100),
+            3 => Ok(TimeMode::Frames60),
+            4 => Ok(TimeMode::Frames50),
+            5 => Ok(TimeMode::Frames48),
+            6 => Ok(TimeMode::Frames30),
+            7 => Ok(TimeMode::Frames30Drop),
+            8 => Ok(TimeMode::NtscDropFrame),
+            9 => Ok(TimeMode::NtscFullFrame),
+            10 => Ok(TimeMode::Pal),
+            11 => Ok(TimeMode::Frames24),
+            12 => Ok(TimeMode::Frames1000),
+            13 => Ok(TimeMode::FilmFullFrame),
+            14 => Ok(TimeMode::Custom),
+            15 => Ok(TimeMode::Frames96),
+            16 => Ok(TimeMode::Frames72),
+            17 => Ok(TimeMode::Frames59dot94),
+            18 => Ok(TimeMode::Frames119dot88),
+            v => bail!("Unexpected `TimeMode` value: {:?}", v),
+        }
+    }
+}
+
+/// `TimeMode` property loader.
+#[derive(Default, Debug, Clone, Copy)]
+pub(crate) struct TimeModeLoader;
+
+impl<'a> LoadProperty<'a> for TimeModeLoader {
+    type Value = TimeMode;
+    type Error = Error;
+
+    fn expecting(&self) -> String {
+        "`i32` value as time mode".into()
+    }
+
+    fn load(self, node: &PropertyHandle<'a>) -> Result<Self::Value, Self::Error> {
+        if node.data_type()? != "enum" {
+            bail!(
+                "Unexpected data type: expected \"enum\", but got {:?}",
+                node.data_type()
+            );
+        }
+        node.load_value(PrimitiveLoader::<i32>::new())
+            .and_then(TryFrom::try_from)
+    }
+}
+
+/// Time protocol (time display format).
+///
+/// This corresponds to `FbxGlobalSettings::ETimeProtocol` of FBX SDK.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TimeProtocol {
+    /// SMPTE timecode.
+    Smpte,
+    /// Frame count.
+    FrameCount,
+    /// Default protocol.
+    Default,
+}
+
+impl TryFrom<i32> for TimeProtocol {
+    type Error = Error;
+
+    fn try_from(v: i32) -> Result<Self, Self::Error> {
+        match v {
+            0 => Ok(TimeProtocol::Smpte),
+            1 => Ok(TimeProtocol::FrameCount),
+            2 => Ok(TimeProtocol::Default),
+            v => bail!("Unexpected `TimeProtocol` value: {:?}", v),
+        }
+    }
+}
+
+/// `TimeProtocol` property loader.
+#[derive(Default, Debug, Clone, Copy)]
+pub(crate) struct TimeProtocolLoader;
+
+impl<'a> LoadProperty<'a> for TimeProtocolLoader {
+    type Value = TimeProtocol;
+    type Error = Error;
+
+    fn expecting(&self) -> String {
+        "`i32` value as time protocol".into()
+    }
+
+    fn load(self, node: &PropertyHandle<'a>) -> Result<Self::Value, Self::Error> {
+        if node.data_type()? != "enum" {
+            bail!(
+                "Unexpected data type: expected \"enum\", but got {:?}",
+                node.data_type()
+            );
+        }
+        node.load_value(PrimitiveLoader::<i32>::new())
+            .and_then(TryFrom::try_from)
+    }
+}
+
+/// Snap on frame mode.
+///
+/// This corresponds to `FbxGlobalSettings::ESnapOnFrameMode` of FBX SDK.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SnapOnFrameMode {
+    /// No snap.
+    NoSnap,
+    /// Snap on frame.
+    SnapOnFrame,
+    /// Play on frame.
+    PlayOnFrame,
+    /// Snap and play on frame.
+    SnapAndPlayOnFrame,
+}
+
+impl TryFrom<i32> for SnapOnFrameMode {
+    type Error = Error;
+
+    fn try_from(v: i32) -> Result<Self, Self::Error> {
+        match v {
+            0 => Ok(SnapOnFrameMode::NoSnap),
+            1 => Ok(SnapOnFrameMode::SnapOnFrame),
+            2 => Ok(SnapOnFrameMode::PlayOnFrame),
+            3 => Ok(SnapOnFrameMode::SnapAndPlayOnFrame),
+            v => bail!("Unexpected `SnapOnFrameMode` value: {:?}", v),
+        }
+    }
+}
+
+/// `SnapOnFrameMode` property loader.
+#[derive(Default, Debug, Clone, Copy)]
+pub(crate) struct SnapOnFrameModeLoader;
+
+impl<'a> LoadProperty<'a> for SnapOnFrameModeLoader {
+    type Value = SnapOnFrameMode;
+    type Error = Error;
+
+    fn expecting(&self) -> String {
+        "`i32` value as snap on frame mode".into()
+    }
+
+    fn load(self, node: &PropertyHandle<'a>) -> Result<Self::Value, Self::Error> {
+        if node.data_type()? != "enum" {
+            bail!(
+                "Unexpected data type: expected \"enum\", but got {:?}",
+                node.data_type()
+            );
+        }
+        node.load_value(PrimitiveLoader::<i32>::new())
+            .and_then(TryFrom::try_from)
+    }
+}
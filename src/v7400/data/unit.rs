@@ -0,0 +1,196 @@
+//! Unit data.
+
+use mint::{ColumnMatrix4, Point3, Vector3};
+
+/// Length unit.
+///
+/// This corresponds to `FbxSystemUnit` of FBX SDK.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Unit {
+    /// Millimeter.
+    Millimeter,
+    /// Centimeter.
+    Centimeter,
+    /// Decimeter.
+    Decimeter,
+    /// Meter.
+    Meter,
+    /// Kilometer.
+    Kilometer,
+    /// Inch.
+    Inch,
+    /// Foot.
+    Foot,
+    /// Yard.
+    Yard,
+    /// Mile.
+    Mile,
+    /// Custom unit, with the size in centimeters.
+    Custom(f64),
+}
+
+impl Unit {
+    /// Preset units.
+    const PRESETS: [Unit; 9] = [
+        Unit::Millimeter,
+        Unit::Centimeter,
+        Unit::Decimeter,
+        Unit::Meter,
+        Unit::Kilometer,
+        Unit::Inch,
+        Unit::Foot,
+        Unit::Yard,
+        Unit::Mile,
+    ];
+
+    /// Creates a unit from the scale factor (size of the unit in centimeters).
+    ///
+    /// A preset is returned if the scale factor is (almost) equal to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fbxcel_dom::v7400::data::unit::Unit;
+    /// assert_eq!(Unit::from_scale_factor(100.0), Unit::Meter);
+    /// assert_eq!(Unit::from_scale_factor(2.54), Unit::Inch);
+    /// assert_eq!(Unit::from_scale_factor(3.0), Unit::Custom(3.0));
+    /// ```
+    pub fn from_scale_factor(centimeters: f64) -> Self {
+        Self::PRESETS
+            .iter()
+            .copied()
+            .find(|preset| {
+                let preset = preset.scale_factor();
+                (centimeters - preset).abs() <= preset * 1e-9
+            })
+            .unwrap_or(Unit::Custom(centimeters))
+    }
+
+    /// Returns the scale factor (size of the unit in centimeters).
+    pub fn scale_factor(self) -> f64 {
+        match self {
+            Unit::Millimeter => 0.1,
+            Unit::Centimeter => 1.0,
+            Unit::Decimeter => 10.0,
+            Unit::Meter => 100.0,
+            Unit::Kilometer => 100_000.0,
+            Unit::Inch => 2.54,
+            Unit::Foot => 30.48,
+            Unit::Yard => 91.44,
+            Unit::Mile => 160_934.4,
+            Unit::Custom(v) => v,
+        }
+    }
+
+    /// Returns the conversion from this unit to the given one.
+    pub fn conversion_to(self, target: Unit) -> UnitConversion {
+        UnitConversion {
+            factor: self.scale_factor() / target.scale_factor(),
+        }
+    }
+}
+
+impl Default for Unit {
+    /// Returns the default unit of FBX (centimeter).
+    fn default() -> Self {
+        Unit::Centimeter
+    }
+}
+
+/// Conversion from a unit to another.
+///
+/// Lengths are multiplied by the [`factor()`], and values without length
+/// dimension (such as rotations, scalings, and normals) are left as is.
+/// Use this as below:
+///
+/// * [`convert_point()`] for control points.
+/// * [`convert_vector()`] for translations, pivots, offsets, and shape
+///   deltas.
+/// * [`convert_length()`] for scalar distances such as camera near and far
+///   planes, light decay and attenuation distances, and animation curve
+///   values of translations.
+/// * [`convert_matrix()`] for transform matrices, such as model transforms
+///   and cluster (bind) matrices.
+///
+/// [`factor()`]: #method.factor
+/// [`convert_point()`]: #method.convert_point
+/// [`convert_vector()`]: #method.convert_vector
+/// [`convert_length()`]: #method.convert_length
+/// [`convert_matrix()`]: #method.convert_matrix
+///
+/// # Examples
+///
+/// ```
+/// # use fbxcel_dom::v7400::data::unit::Unit;
+/// use mint::Point3;
+///
+/// let conversion = Unit::Inch.conversion_to(Unit::Centimeter);
+/// assert_eq!(conversion.convert_length(10.0), 25.4);
+///
+/// let conversion = Unit::Centimeter.conversion_to(Unit::Meter);
+/// let p = conversion.convert_point(Point3 { x: 100.0, y: 50.0, z: 0.0 });
+/// assert_eq!(p, Point3 { x: 1.0, y: 0.5, z: 0.0 });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitConversion {
+    /// Scale factor.
+    factor: f64,
+}
+
+impl UnitConversion {
+    /// Returns the identity conversion.
+    pub fn identity() -> Self {
+        Self { factor: 1.0 }
+    }
+
+    /// Returns the scale factor.
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    /// Returns whether the conversion does nothing.
+    pub fn is_identity(&self) -> bool {
+        self.factor == 1.0
+    }
+
+    /// Converts a length.
+    pub fn convert_length(&self, v: f64) -> f64 {
+        v * self.factor
+    }
+
+    /// Converts a point.
+    pub fn convert_point(&self, p: Point3<f64>) -> Point3<f64> {
+        Point3 {
+            x: p.x * self.factor,
+            y: p.y * self.factor,
+            z: p.z * self.factor,
+        }
+    }
+
+    /// Converts a vector (with length dimension).
+    pub fn convert_vector(&self, v: Vector3<f64>) -> Vector3<f64> {
+        Vector3 {
+            x: v.x * self.factor,
+            y: v.y * self.factor,
+            z: v.z * self.factor,
+        }
+    }
+
+    /// Converts a transform matrix.
+    ///
+    /// This is `S * M * S^-1` where `S` is the scaling by the factor, i.e. the
+    /// translation part is scaled and the others are left as is.
+    pub fn convert_matrix(&self, m: ColumnMatrix4<f64>) -> ColumnMatrix4<f64> {
+        let mut m = m;
+        m.w.x *= self.factor;
+        m.w.y *= self.factor;
+        m.w.z *= self.factor;
+        m
+    }
+}
+
+impl Default for UnitConversion {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
@@ -1,7 +1,23 @@
 //! The Global Settings for the FBX file. See struct `GlobalSettings`.
 
+use std::convert::TryFrom;
+
+use anyhow::{format_err, Error};
+use rgb::RGB;
+
+use crate::v7400::data::axis::{
+    Axis, AxisConversion, AxisLoader, AxisSign, AxisSignLoader, AxisSystem, SignedAxis,
+};
+use crate::v7400::data::time::{
+    FbxTime, SnapOnFrameMode, SnapOnFrameModeLoader, TimeMode, TimeModeLoader, TimeProtocol,
+    TimeProtocolLoader,
+};
+use crate::v7400::data::unit::{Unit, UnitConversion};
 use crate::v7400::document::Document;
-use crate::v7400::object::property::PropertiesHandle;
+use crate::v7400::object::property::{
+    loaders::{BorrowedStringLoader, FbxTimeLoader, PrimitiveLoader, RgbLoader},
+    PropertiesHandle,
+};
 
 /// The Global Settings for the FBX file.
 ///
@@ -27,4 +43,323 @@ impl<'a> GlobalSettings<'a> {
     pub fn raw_properties(&self) -> PropertiesHandle<'a> {
         self.properties
     }
+
+    impl_prop_proxy_getters! {
+        /// Returns time mode.
+        time_mode -> TimeMode {
+            name = "TimeMode",
+            loader = TimeModeLoader,
+            description = "time mode",
+            default: {
+                /// Returns time mode.
+                ///
+                /// Returns default if the value is not set.
+                time_mode_or_default = TimeMode::Default
+            }
+        }
+
+        /// Returns custom frame rate.
+        ///
+        /// This is used when the time mode is `TimeMode::Custom`.
+        custom_frame_rate -> f64 {
+            name = "CustomFrameRate",
+            loader = PrimitiveLoader::<f64>::new(),
+            description = "custom frame rate",
+            default: {
+                /// Returns custom frame rate.
+                ///
+                /// Returns `-1` (unspecified) if the value is not set.
+                /// Use [`frame_rate()`] to get a valid frame rate.
+                ///
+                /// [`frame_rate()`]: #method.frame_rate
+                custom_frame_rate_or_default = -1.0
+            }
+        }
+
+        /// Returns up axis.
+        up_axis -> Axis {
+            name = "UpAxis",
+            loader = AxisLoader,
+            description = "up axis",
+            default: {
+                /// Returns up axis.
+                ///
+                /// Returns default if the value is not set.
+                up_axis_or_default = Axis::Y
+            }
+        }
+
+        /// Returns up axis sign.
+        up_axis_sign -> AxisSign {
+            name = "UpAxisSign",
+            loader = AxisSignLoader,
+            description = "up axis sign",
+            default: {
+                /// Returns up axis sign.
+                ///
+                /// Returns default if the value is not set.
+                up_axis_sign_or_default = AxisSign::Positive
+            }
+        }
+
+        /// Returns front axis.
+        front_axis -> Axis {
+            name = "FrontAxis",
+            loader = AxisLoader,
+            description = "front axis",
+            default: {
+                /// Returns front axis.
+                ///
+                /// Returns default if the value is not set.
+                front_axis_or_default = Axis::Z
+            }
+        }
+
+        /// Returns front axis sign.
+        front_axis_sign -> AxisSign {
+            name = "FrontAxisSign",
+            loader = AxisSignLoader,
+            description = "front axis sign",
+            default: {
+                /// Returns front axis sign.
+                ///
+                /// Returns default if the value is not set.
+                front_axis_sign_or_default = AxisSign::Positive
+            }
+        }
+
+        /// Returns coord axis.
+        coord_axis -> Axis {
+            name = "CoordAxis",
+            loader = AxisLoader,
+            description = "coord axis",
+            default: {
+                /// Returns coord axis.
+                ///
+                /// Returns default if the value is not set.
+                coord_axis_or_default = Axis::X
+            }
+        }
+
+        /// Returns coord axis sign.
+        coord_axis_sign -> AxisSign {
+            name = "CoordAxisSign",
+            loader = AxisSignLoader,
+            description = "coord axis sign",
+            default: {
+                /// Returns coord axis sign.
+                ///
+                /// Returns default if the value is not set.
+                coord_axis_sign_or_default = AxisSign::Positive
+            }
+        }
+
+        /// Returns original up axis sign.
+        original_up_axis_sign -> AxisSign {
+            name = "OriginalUpAxisSign",
+            loader = AxisSignLoader,
+            description = "original up axis sign",
+            default: {
+                /// Returns original up axis sign.
+                ///
+                /// Returns default if the value is not set.
+                original_up_axis_sign_or_default = AxisSign::Positive
+            }
+        }
+
+        /// Returns unit scale factor.
+        ///
+        /// This is the size of the unit length in centimeters.
+        unit_scale_factor -> f64 {
+            name = "UnitScaleFactor",
+            loader = PrimitiveLoader::<f64>::new(),
+            description = "unit scale factor",
+            default: {
+                /// Returns unit scale factor.
+                ///
+                /// Returns default if the value is not set.
+                unit_scale_factor_or_default = 1.0
+            }
+        }
+
+        /// Returns original unit scale factor.
+        ///
+        /// This is the size of the unit length in centimeters, in the
+        /// application which originally created the file.
+        original_unit_scale_factor -> f64 {
+            name = "OriginalUnitScaleFactor",
+            loader = PrimitiveLoader::<f64>::new(),
+            description = "original unit scale factor",
+            default: {
+                /// Returns original unit scale factor.
+                ///
+                /// Returns default if the value is not set.
+                original_unit_scale_factor_or_default = 1.0
+            }
+        }
+
+        /// Returns ambient color.
+        ambient_color -> RGB<f64> {
+            name = "AmbientColor",
+            loader = RgbLoader::<RGB<f64>>::new(),
+            description = "ambient color",
+            default: {
+                /// Returns ambient color.
+                ///
+                /// Returns default if the value is not set.
+                ambient_color_or_default = RGB::from([0.0; 3])
+            }
+        }
+
+        /// Returns default camera name.
+        default_camera -> &'a str {
+            name = "DefaultCamera",
+            loader = BorrowedStringLoader::new(),
+            description = "default camera name",
+            default: {
+                /// Returns default camera name.
+                ///
+                /// Returns default if the value is not set.
+                default_camera_or_default = "Producer Perspective"
+            }
+        }
+
+        /// Returns time protocol.
+        time_protocol -> TimeProtocol {
+            name = "TimeProtocol",
+            loader = TimeProtocolLoader,
+            description = "time protocol",
+            default: {
+                /// Returns time protocol.
+                ///
+                /// Returns default if the value is not set.
+                time_protocol_or_default = TimeProtocol::Default
+            }
+        }
+
+        /// Returns snap on frame mode.
+        snap_on_frame_mode -> SnapOnFrameMode {
+            name = "SnapOnFrameMode",
+            loader = SnapOnFrameModeLoader,
+            description = "snap on frame mode",
+            default: {
+                /// Returns snap on frame mode.
+                ///
+                /// Returns default if the value is not set.
+                snap_on_frame_mode_or_default = SnapOnFrameMode::NoSnap
+            }
+        }
+
+        /// Returns time span start.
+        time_span_start -> FbxTime {
+            name = "TimeSpanStart",
+            loader = FbxTimeLoader::new(),
+            description = "time span start",
+            default: {
+                /// Returns time span start.
+                ///
+                /// Returns default if the value is not set.
+                time_span_start_or_default = FbxTime::ZERO
+            }
+        }
+
+        /// Returns time span stop.
+        time_span_stop -> FbxTime {
+            name = "TimeSpanStop",
+            loader = FbxTimeLoader::new(),
+            description = "time span stop",
+            default: {
+                /// Returns time span stop.
+                ///
+                /// Returns default if the value is not set.
+                time_span_stop_or_default = FbxTime::ZERO
+            }
+        }
+    }
+
+    /// Returns frame rate (frames per second).
+    ///
+    /// This is calculated from `TimeMode` and `CustomFrameRate`.
+    /// Returns an error if the time mode is `TimeMode::Custom` and
+    /// `CustomFrameRate` is not set or not positive.
+    pub fn frame_rate(&self) -> Result<f64, Error> {
+        let time_mode = self.time_mode_or_default()?;
+        if time_mode != TimeMode::Custom {
+            return Ok(time_mode.frame_rate(0.0));
+        }
+        match self.custom_frame_rate()? {
+            Some(rate) if rate > 0.0 => Ok(rate),
+            rate => Err(format_err!(
+                "Custom frame rate should be positive for custom time mode, but got {:?}",
+                rate
+            )),
+        }
+    }
+
+    /// Returns original up axis.
+    ///
+    /// Returns `Ok(None)` if the value is not set or is `-1` (unknown).
+    pub fn original_up_axis(&self) -> Result<Option<Axis>, Error> {
+        let value = match self.properties.get_property("OriginalUpAxis") {
+            Some(p) => p.load_value(PrimitiveLoader::<i32>::new()),
+            None => return Ok(None),
+        };
+        match value {
+            Ok(-1) => Ok(None),
+            Ok(v) => Axis::try_from(v).map(Some),
+            Err(e) => Err(e),
+        }
+        .map_err(|e| format_err!("Failed to load original up axis: {}", e))
+    }
+
+    /// Returns axis system.
+    ///
+    /// This is calculated from `UpAxis`, `FrontAxis`, `CoordAxis`, and their
+    /// signs.
+    pub fn axis_system(&self) -> Result<AxisSystem, Error> {
+        let up = SignedAxis::new(self.up_axis_or_default()?, self.up_axis_sign_or_default()?);
+        let front = SignedAxis::new(
+            self.front_axis_or_default()?,
+            self.front_axis_sign_or_default()?,
+        );
+        let coord = SignedAxis::new(
+            self.coord_axis_or_default()?,
+            self.coord_axis_sign_or_default()?,
+        );
+        AxisSystem::new(up, front, coord).ok_or_else(|| {
+            format_err!(
+                "Invalid axis system: up={:?}, front={:?}, coord={:?}",
+                up,
+                front,
+                coord
+            )
+        })
+    }
+
+    /// Returns conversion from the axis system of the document to the
+    /// given one.
+    pub fn axis_conversion_to(&self, target: &AxisSystem) -> Result<AxisConversion, Error> {
+        Ok(self.axis_system()?.conversion_to(target))
+    }
+
+    /// Returns unit.
+    ///
+    /// This is calculated from `UnitScaleFactor`.
+    pub fn unit(&self) -> Result<Unit, Error> {
+        self.unit_scale_factor_or_default()
+            .map(Unit::from_scale_factor)
+    }
+
+    /// Returns original unit.
+    ///
+    /// This is calculated from `OriginalUnitScaleFactor`.
+    pub fn original_unit(&self) -> Result<Unit, Error> {
+        self.original_unit_scale_factor_or_default()
+            .map(Unit::from_scale_factor)
+    }
+
+    /// Returns conversion from the unit of the document to the given one.
+    pub fn unit_conversion_to(&self, target: Unit) -> Result<UnitConversion, Error> {
+        Ok(self.unit()?.conversion_to(target))
+    }
 }
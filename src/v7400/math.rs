@@ -0,0 +1,322 @@
+//! Small linear algebra helpers.
+//!
+//! `mint` provides only interoperable data types, so the few operations
+//! required to evaluate FBX data are implemented here.
+
+use mint::{ColumnMatrix4, Point3, Vector3};
+
+use crate::v7400::data::model::RotationOrder;
+
+/// 4x4 matrix of `f64`, stored in column-major order.
+///
+/// `self.0[col][row]` is the element at the given column and row.
+/// Vectors are treated as column vectors, i.e. `A * B` applies `B` first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Mat4(pub(crate) [[f64; 4]; 4]);
+
+impl Mat4 {
+    /// Identity matrix.
+    pub(crate) const IDENTITY: Self = Self([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+
+    /// Creates a translation matrix.
+    pub(crate) fn translation(v: Vector3<f64>) -> Self {
+        let mut m = Self::IDENTITY;
+        m.0[3] = [v.x, v.y, v.z, 1.0];
+        m
+    }
+
+    /// Creates a scaling matrix.
+    pub(crate) fn scaling(v: Vector3<f64>) -> Self {
+        let mut m = Self::IDENTITY;
+        m.0[0][0] = v.x;
+        m.0[1][1] = v.y;
+        m.0[2][2] = v.z;
+        m
+    }
+
+    /// Creates a rotation matrix around the X axis.
+    pub(crate) fn rotation_x(degrees: f64) -> Self {
+        let (s, c) = degrees.to_radians().sin_cos();
+        let mut m = Self::IDENTITY;
+        m.0[1] = [0.0, c, s, 0.0];
+        m.0[2] = [0.0, -s, c, 0.0];
+        m
+    }
+
+    /// Creates a rotation matrix around the Y axis.
+    pub(crate) fn rotation_y(degrees: f64) -> Self {
+        let (s, c) = degrees.to_radians().sin_cos();
+        let mut m = Self::IDENTITY;
+        m.0[0] = [c, 0.0, -s, 0.0];
+        m.0[2] = [s, 0.0, c, 0.0];
+        m
+    }
+
+    /// Creates a rotation matrix around the Z axis.
+    pub(crate) fn rotation_z(degrees: f64) -> Self {
+        let (s, c) = degrees.to_radians().sin_cos();
+        let mut m = Self::IDENTITY;
+        m.0[0] = [c, s, 0.0, 0.0];
+        m.0[1] = [-s, c, 0.0, 0.0];
+        m
+    }
+
+    /// Creates a rotation matrix from euler angles in degrees.
+    pub(crate) fn euler(degrees: Vector3<f64>, order: RotationOrder) -> Self {
+        let x = Self::rotation_x(degrees.x);
+        let y = Self::rotation_y(degrees.y);
+        let z = Self::rotation_z(degrees.z);
+        // The first axis in the order is applied first, i.e. it is the
+        // rightmost in the product.
+        match order {
+            RotationOrder::Xyz | RotationOrder::SphericXyz => z * y * x,
+            RotationOrder::Xzy => y * z * x,
+            RotationOrder::Yzx => x * z * y,
+            RotationOrder::Yxz => z * x * y,
+            RotationOrder::Zxy => y * x * z,
+            RotationOrder::Zyx => x * y * z,
+        }
+    }
+
+    /// Returns the transposed matrix.
+    pub(crate) fn transpose(&self) -> Self {
+        let mut m = [[0.0; 4]; 4];
+        for (col, m_col) in m.iter_mut().enumerate() {
+            for (row, v) in m_col.iter_mut().enumerate() {
+                *v = self.0[row][col];
+            }
+        }
+        Self(m)
+    }
+
+    /// Returns the inverse matrix, or `None` if the matrix is singular.
+    pub(crate) fn inverse(&self) -> Option<Self> {
+        // Cofactor expansion, using the row-major view of the transposed
+        // storage.
+        let m = self.transpose().0;
+        let a2323 = m[2][2] * m[3][3] - m[2][3] * m[3][2];
+        let a1323 = m[2][1] * m[3][3] - m[2][3] * m[3][1];
+        let a1223 = m[2][1] * m[3][2] - m[2][2] * m[3][1];
+        let a0323 = m[2][0] * m[3][3] - m[2][3] * m[3][0];
+        let a0223 = m[2][0] * m[3][2] - m[2][2] * m[3][0];
+        let a0123 = m[2][0] * m[3][1] - m[2][1] * m[3][0];
+        let a2313 = m[1][2] * m[3][3] - m[1][3] * m[3][2];
+        let a1313 = m[1][1] * m[3][3] - m[1][3] * m[3][1];
+        let a1213 = m[1][1] * m[3][2] - m[1][2] * m[3][1];
+        let a2312 = m[1][2] * m[2][3] - m[1][3] * m[2][2];
+        let a1312 = m[1][1] * m[2][3] - m[1][3] * m[2][1];
+        let a1212 = m[1][1] * m[2][2] - m[1][2] * m[2][1];
+        let a0313 = m[1][0] * m[3][3] - m[1][3] * m[3][0];
+        let a0213 = m[1][0] * m[3][2] - m[1][2] * m[3][0];
+        let a0312 = m[1][0] * m[2][3] - m[1][3] * m[2][0];
+        let a0212 = m[1][0] * m[2][2] - m[1][2] * m[2][0];
+        let a0113 = m[1][0] * m[3][1] - m[1][1] * m[3][0];
+        let a0112 = m[1][0] * m[2][1] - m[1][1] * m[2][0];
+
+        let det = m[0][0] * (m[1][1] * a2323 - m[1][2] * a1323 + m[1][3] * a1223)
+            - m[0][1] * (m[1][0] * a2323 - m[1][2] * a0323 + m[1][3] * a0223)
+            + m[0][2] * (m[1][0] * a1323 - m[1][1] * a0323 + m[1][3] * a0123)
+            - m[0][3] * (m[1][0] * a1223 - m[1][1] * a0223 + m[1][2] * a0123);
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+
+        let inv = [
+            [
+                inv_det * (m[1][1] * a2323 - m[1][2] * a1323 + m[1][3] * a1223),
+                inv_det * -(m[0][1] * a2323 - m[0][2] * a1323 + m[0][3] * a1223),
+                inv_det * (m[0][1] * a2313 - m[0][2] * a1313 + m[0][3] * a1213),
+                inv_det * -(m[0][1] * a2312 - m[0][2] * a1312 + m[0][3] * a1212),
+            ],
+            [
+                inv_det * -(m[1][0] * a2323 - m[1][2] * a0323 + m[1][3] * a0223),
+                inv_det * (m[0][0] * a2323 - m[0][2] * a0323 + m[0][3] * a0223),
+                inv_det * -(m[0][0] * a2313 - m[0][2] * a0313 + m[0][3] * a0213),
+                inv_det * (m[0][0] * a2312 - m[0][2] * a0312 + m[0][3] * a0212),
+            ],
+            [
+                inv_det * (m[1][0] * a1323 - m[1][1] * a0323 + m[1][3] * a0123),
+                inv_det * -(m[0][0] * a1323 - m[0][1] * a0323 + m[0][3] * a0123),
+                inv_det * (m[0][0] * a1313 - m[0][1] * a0313 + m[0][3] * a0113),
+                inv_det * -(m[0][0] * a1312 - m[0][1] * a0312 + m[0][3] * a0112),
+            ],
+            [
+                inv_det * -(m[1][0] * a1223 - m[1][1] * a0223 + m[1][2] * a0123),
+                inv_det * (m[0][0] * a1223 - m[0][1] * a0223 + m[0][2] * a0123),
+                inv_det * -(m[0][0] * a1213 - m[0][1] * a0213 + m[0][2] * a0113),
+                inv_det * (m[0][0] * a1212 - m[0][1] * a0212 + m[0][2] * a0112),
+            ],
+        ];
+        // `inv` is row-major here; convert back to column-major.
+        Some(Self(inv).transpose())
+    }
+
+    /// Returns the translation part.
+    pub(crate) fn translation_part(&self) -> Vector3<f64> {
+        Vector3 {
+            x: self.0[3][0],
+            y: self.0[3][1],
+            z: self.0[3][2],
+        }
+    }
+
+    /// Returns the upper-left 3x3 part, without translation.
+    pub(crate) fn linear_part(&self) -> Self {
+        let mut m = *self;
+        m.0[3] = [0.0, 0.0, 0.0, 1.0];
+        m
+    }
+
+    /// Returns the rotation part as an orthonormal matrix.
+    ///
+    /// Scaling and shear are removed by Gram-Schmidt orthonormalization of the
+    /// basis vectors.
+    /// If the matrix has negative determinant, the third axis is flipped so
+    /// that the result is always a proper rotation.
+    pub(crate) fn rotation_part(&self) -> Self {
+        let x = normalize(column3(self, 0));
+        let y = column3(self, 1);
+        let y = normalize(sub(y, scale(x, dot(x, y))));
+        let mut z = cross(x, y);
+        if dot(z, column3(self, 2)) < 0.0 {
+            z = scale(z, -1.0);
+        }
+        let mut m = Self::IDENTITY;
+        m.0[0] = [x.x, x.y, x.z, 0.0];
+        m.0[1] = [y.x, y.y, y.z, 0.0];
+        m.0[2] = [z.x, z.y, z.z, 0.0];
+        m
+    }
+
+    /// Transforms the given point.
+    pub(crate) fn transform_point(&self, p: Point3<f64>) -> Point3<f64> {
+        let m = &self.0;
+        Point3 {
+            x: m[0][0] * p.x + m[1][0] * p.y + m[2][0] * p.z + m[3][0],
+            y: m[0][1] * p.x + m[1][1] * p.y + m[2][1] * p.z + m[3][1],
+            z: m[0][2] * p.x + m[1][2] * p.y + m[2][2] * p.z + m[3][2],
+        }
+    }
+
+    /// Transforms the given direction vector (ignoring translation).
+    pub(crate) fn transform_vector(&self, v: Vector3<f64>) -> Vector3<f64> {
+        let m = &self.0;
+        Vector3 {
+            x: m[0][0] * v.x + m[1][0] * v.y + m[2][0] * v.z,
+            y: m[0][1] * v.x + m[1][1] * v.y + m[2][1] * v.z,
+            z: m[0][2] * v.x + m[1][2] * v.y + m[2][2] * v.z,
+        }
+    }
+}
+
+impl std::ops::Mul for Mat4 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let mut m = [[0.0; 4]; 4];
+        for (col, m_col) in m.iter_mut().enumerate() {
+            for (row, v) in m_col.iter_mut().enumerate() {
+                *v = (0..4).map(|k| self.0[k][row] * rhs.0[col][k]).sum();
+            }
+        }
+        Self(m)
+    }
+}
+
+impl From<ColumnMatrix4<f64>> for Mat4 {
+    fn from(m: ColumnMatrix4<f64>) -> Self {
+        Self(m.into())
+    }
+}
+
+impl From<Mat4> for ColumnMatrix4<f64> {
+    fn from(m: Mat4) -> Self {
+        m.0.into()
+    }
+}
+
+/// Returns the first three elements of the given column.
+fn column3(m: &Mat4, col: usize) -> Vector3<f64> {
+    Vector3 {
+        x: m.0[col][0],
+        y: m.0[col][1],
+        z: m.0[col][2],
+    }
+}
+
+/// Returns the dot product.
+pub(crate) fn dot(a: Vector3<f64>, b: Vector3<f64>) -> f64 {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
+/// Returns the cross product.
+pub(crate) fn cross(a: Vector3<f64>, b: Vector3<f64>) -> Vector3<f64> {
+    Vector3 {
+        x: a.y * b.z - a.z * b.y,
+        y: a.z * b.x - a.x * b.z,
+        z: a.x * b.y - a.y * b.x,
+    }
+}
+
+/// Returns `a + b`.
+pub(crate) fn add(a: Vector3<f64>, b: Vector3<f64>) -> Vector3<f64> {
+    Vector3 {
+        x: a.x + b.x,
+        y: a.y + b.y,
+        z: a.z + b.z,
+    }
+}
+
+/// Returns `a - b`.
+pub(crate) fn sub(a: Vector3<f64>, b: Vector3<f64>) -> Vector3<f64> {
+    Vector3 {
+        x: a.x - b.x,
+        y: a.y - b.y,
+        z: a.z - b.z,
+    }
+}
+
+/// Returns `v * s`.
+pub(crate) fn scale(v: Vector3<f64>, s: f64) -> Vector3<f64> {
+    Vector3 {
+        x: v.x * s,
+        y: v.y * s,
+        z: v.z * s,
+    }
+}
+
+/// Returns the normalized vector.
+///
+/// Returns the zero vector as is.
+pub(crate) fn normalize(v: Vector3<f64>) -> Vector3<f64> {
+    let len = dot(v, v).sqrt();
+    if len == 0.0 {
+        v
+    } else {
+        scale(v, 1.0 / len)
+    }
+}
+
+/// Returns the normal of the polygon by Newell's method.
+///
+/// The length of the returned vector is twice the area of the polygon.
+pub(crate) fn polygon_normal(points: &[Point3<f64>]) -> Vector3<f64> {
+    let mut normal = Vector3 {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+    for (i, &current) in points.iter().enumerate() {
+        let next = points[(i + 1) % points.len()];
+        normal.x += (current.y - next.y) * (current.z + next.z);
+        normal.y += (current.z - next.z) * (current.x + next.x);
+        normal.z += (current.x - next.x) * (current.y + next.y);
+    }
+    normal
+}
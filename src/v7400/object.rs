@@ -74,7 +74,10 @@
 
 use std::fmt;
 
-use fbxcel::tree::v7400::{NodeHandle, NodeId};
+use fbxcel::{
+    low::v7400::AttributeValue,
+    tree::v7400::{NodeHandle, NodeId},
+};
 
 use crate::v7400::{connection::Connection, Document};
 
@@ -88,6 +91,10 @@ pub(crate) use self::{
 #[macro_use]
 mod macros;
 
+pub mod animationcurve;
+pub mod animationcurvenode;
+pub mod animationlayer;
+pub mod animationstack;
 mod cache;
 pub mod deformer;
 pub mod geometry;
@@ -95,6 +102,7 @@ pub mod material;
 mod meta;
 pub mod model;
 pub mod nodeattribute;
+pub mod pose;
 pub mod property;
 pub mod scene;
 pub mod texture;
@@ -238,6 +246,15 @@ impl<'a> ObjectHandle<'a> {
         self.node_id.to_handle(self.doc.tree())
     }
 
+    /// Returns the first attribute of the child node with the given name.
+    pub(crate) fn child_attribute(&self, name: &str) -> Option<&'a AttributeValue> {
+        self.node()
+            .children_by_name(name)
+            .next()?
+            .attributes()
+            .get(0)
+    }
+
     /// Returns the object type.
     pub fn get_typed(&self) -> TypedObjectHandle<'a> {
         TypedObjectHandle::new(*self)
@@ -0,0 +1,106 @@
+//! `AnimationCurve` object.
+
+use anyhow::{format_err, Error};
+
+use crate::v7400::{
+    data::{animation::AnimationCurveData, time::FbxTime},
+    object::{animationcurvenode, ObjectHandle, TypedObjectHandle},
+};
+
+define_object_subtype! {
+    /// `AnimationCurve` node handle.
+    AnimationCurveHandle: ObjectHandle
+}
+
+impl<'a> AnimationCurveHandle<'a> {
+    /// Returns an iterator of parent animation curve nodes and channel names.
+    ///
+    /// Channel names are connection labels, such as `d|X`.
+    pub fn curve_nodes(
+        &self,
+    ) -> impl Iterator<Item = (&'a str, animationcurvenode::AnimationCurveNodeHandle<'a>)> {
+        self.destination_objects()
+            .filter_map(|obj| obj.label().map(|label| (label, obj)))
+            .filter_map(|(label, obj)| obj.object_handle().map(|obj| (label, obj)))
+            .filter_map(|(label, obj)| match obj.get_typed() {
+                TypedObjectHandle::AnimationCurveNode(o) => Some((label, o)),
+                _ => None,
+            })
+    }
+
+    /// Returns the default value, if available.
+    pub fn default_value(&self) -> Option<f64> {
+        self.node()
+            .children_by_name("Default")
+            .next()?
+            .attributes()
+            .get(0)?
+            .get_f64()
+    }
+
+    /// Returns key times (`KeyTime`).
+    pub fn key_time(&self) -> Result<&'a [i64], Error> {
+        self.child_attribute("KeyTime")
+            .ok_or_else(|| format_err!("`KeyTime` not found for animation curve"))?
+            .get_arr_i64_or_type()
+            .map_err(|ty| format_err!("Expected `[i64]` as `KeyTime`, but got {:?}", ty))
+    }
+
+    /// Returns key values (`KeyValueFloat`).
+    pub fn key_value_float(&self) -> Result<&'a [f32], Error> {
+        self.child_attribute("KeyValueFloat")
+            .ok_or_else(|| format_err!("`KeyValueFloat` not found for animation curve"))?
+            .get_arr_f32_or_type()
+            .map_err(|ty| format_err!("Expected `[f32]` as `KeyValueFloat`, but got {:?}", ty))
+    }
+
+    /// Returns key attribute flags (`KeyAttrFlags`).
+    pub fn key_attr_flags(&self) -> Result<&'a [i32], Error> {
+        self.child_attribute("KeyAttrFlags")
+            .ok_or_else(|| format_err!("`KeyAttrFlags` not found for animation curve"))?
+            .get_arr_i32_or_type()
+            .map_err(|ty| format_err!("Expected `[i32]` as `KeyAttrFlags`, but got {:?}", ty))
+    }
+
+    /// Returns key attribute data (`KeyAttrDataFloat`).
+    ///
+    /// Each key attribute has four elements.
+    pub fn key_attr_data_float(&self) -> Result<&'a [f32], Error> {
+        self.child_attribute("KeyAttrDataFloat")
+            .ok_or_else(|| format_err!("`KeyAttrDataFloat` not found for animation curve"))?
+            .get_arr_f32_or_type()
+            .map_err(|ty| format_err!("Expected `[f32]` as `KeyAttrDataFloat`, but got {:?}", ty))
+    }
+
+    /// Returns key attribute reference counts (`KeyAttrRefCount`).
+    ///
+    /// Each element is the number of consecutive keys sharing the
+    /// corresponding key attribute.
+    pub fn key_attr_ref_count(&self) -> Result<&'a [i32], Error> {
+        self.child_attribute("KeyAttrRefCount")
+            .ok_or_else(|| format_err!("`KeyAttrRefCount` not found for animation curve"))?
+            .get_arr_i32_or_type()
+            .map_err(|ty| format_err!("Expected `[i32]` as `KeyAttrRefCount`, but got {:?}", ty))
+    }
+
+    /// Returns the curve data, which consists of keys and their attributes.
+    pub fn curve_data(&self) -> Result<AnimationCurveData<'a>, Error> {
+        AnimationCurveData::new(
+            self.key_time()?,
+            self.key_value_float()?,
+            self.key_attr_flags()?,
+            self.key_attr_data_float()?,
+            self.key_attr_ref_count()?,
+        )
+    }
+
+    /// Evaluates the curve at the given time.
+    ///
+    /// If the curve has no keys, the default value is returned.
+    pub fn evaluate(&self, time: FbxTime) -> Result<f64, Error> {
+        self.curve_data()?
+            .evaluate(time)
+            .or_else(|| self.default_value())
+            .ok_or_else(|| format_err!("Animation curve has neither keys nor default value"))
+    }
+}
@@ -0,0 +1,141 @@
+//! `AnimationCurveNode` object.
+
+use anyhow::{format_err, Error};
+use mint::Vector3;
+
+use crate::v7400::{
+    data::time::FbxTime,
+    object::{
+        animationcurve, animationlayer,
+        property::{loaders::PrimitiveLoader, PropertyHandle},
+        ObjectHandle, TypedObjectHandle,
+    },
+};
+
+define_object_subtype! {
+    /// `AnimationCurveNode` node handle.
+    ///
+    /// An animation curve node groups curves for components (channels) of a
+    /// property, such as `d|X`, `d|Y`, and `d|Z` of `Lcl Translation`.
+    AnimationCurveNodeHandle: ObjectHandle
+}
+
+impl<'a> AnimationCurveNodeHandle<'a> {
+    /// Returns an iterator of parent animation layers.
+    pub fn layers(&self) -> impl Iterator<Item = animationlayer::AnimationLayerHandle<'a>> {
+        self.destination_objects()
+            .filter(|obj| obj.label().is_none())
+            .filter_map(|obj| obj.object_handle())
+            .filter_map(|obj| match obj.get_typed() {
+                TypedObjectHandle::AnimationLayer(o) => Some(o),
+                _ => None,
+            })
+    }
+
+    /// Returns an iterator of child animation curves and their channel names.
+    ///
+    /// Channel names are connection labels, such as `d|X`.
+    pub fn curves(
+        &self,
+    ) -> impl Iterator<Item = (&'a str, animationcurve::AnimationCurveHandle<'a>)> {
+        self.source_objects()
+            .filter_map(|obj| obj.label().map(|label| (label, obj)))
+            .filter_map(|(label, obj)| obj.object_handle().map(|obj| (label, obj)))
+            .filter_map(|(label, obj)| match obj.get_typed() {
+                TypedObjectHandle::AnimationCurve(o) => Some((label, o)),
+                _ => None,
+            })
+    }
+
+    /// Returns the child animation curve for the given channel, if available.
+    ///
+    /// `channel` is a connection label, such as `d|X`.
+    pub fn curve(&self, channel: &str) -> Option<animationcurve::AnimationCurveHandle<'a>> {
+        self.curves()
+            .find(|(label, _)| *label == channel)
+            .map(|(_, curve)| curve)
+    }
+
+    /// Returns the default (non-animated) value of the given channel.
+    ///
+    /// `channel` is a property name, such as `d|X`.
+    pub fn channel_default_value(&self, channel: &str) -> Result<Option<f64>, Error> {
+        self.direct_properties()
+            .and_then(|props| props.get_property(channel))
+            .map(|p| p.load_value(PrimitiveLoader::<f64>::new()))
+            .transpose()
+            .map_err(|e| format_err!("Failed to load default value of {:?}: {}", channel, e))
+    }
+
+    /// Evaluates the channel at the given time.
+    ///
+    /// If no curve is connected to the channel, the default value of the
+    /// channel is returned.
+    pub fn evaluate_channel(&self, channel: &str, time: FbxTime) -> Result<Option<f64>, Error> {
+        match self.curve(channel) {
+            Some(curve) => curve.evaluate(time).map(Some),
+            None => self.channel_default_value(channel),
+        }
+    }
+
+    /// Evaluates `d|X`, `d|Y`, and `d|Z` channels at the given time.
+    pub fn evaluate_vector3(&self, time: FbxTime) -> Result<Vector3<f64>, Error> {
+        let channel = |name: &str| {
+            self.evaluate_channel(name, time)?.ok_or_else(|| {
+                format_err!(
+                    "Neither curve nor default value found for channel {:?}: object={:?}",
+                    name,
+                    self
+                )
+            })
+        };
+        Ok(Vector3 {
+            x: channel("d|X")?,
+            y: channel("d|Y")?,
+            z: channel("d|Z")?,
+        })
+    }
+
+    /// Returns the animated property, if available.
+    pub fn target(&self) -> Option<AnimatedProperty<'a>> {
+        self.destination_objects()
+            .filter_map(|obj| obj.label().map(|label| (label, obj)))
+            .filter_map(|(label, obj)| obj.object_handle().map(|obj| (label, obj)))
+            .map(|(property_name, object)| AnimatedProperty {
+                object,
+                property_name,
+            })
+            .next()
+    }
+}
+
+/// A property animated by an animation curve node.
+#[derive(Debug, Clone, Copy)]
+pub struct AnimatedProperty<'a> {
+    /// Animated object.
+    object: ObjectHandle<'a>,
+    /// Property name.
+    property_name: &'a str,
+}
+
+impl<'a> AnimatedProperty<'a> {
+    /// Returns the animated object.
+    pub fn object(&self) -> ObjectHandle<'a> {
+        self.object
+    }
+
+    /// Returns the animated property name, such as `Lcl Translation`.
+    pub fn property_name(&self) -> &'a str {
+        self.property_name
+    }
+
+    /// Returns the property node of the animated object, if available.
+    ///
+    /// Note that this returns only the property directly specified for the
+    /// object, and does not check default properties.
+    pub fn property(&self) -> Option<PropertyHandle<'a>> {
+        self.object
+            .direct_properties()
+            .and_then(|props| props.get_property(self.property_name))
+    }
+}
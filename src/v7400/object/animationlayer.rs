@@ -0,0 +1,34 @@
+//! `AnimationLayer` object.
+
+use crate::v7400::object::{animationcurvenode, animationstack, ObjectHandle, TypedObjectHandle};
+
+define_object_subtype! {
+    /// `AnimationLayer` node handle.
+    AnimationLayerHandle: ObjectHandle
+}
+
+impl<'a> AnimationLayerHandle<'a> {
+    /// Returns an iterator of parent animation stacks.
+    pub fn stacks(&self) -> impl Iterator<Item = animationstack::AnimationStackHandle<'a>> {
+        self.destination_objects()
+            .filter(|obj| obj.label().is_none())
+            .filter_map(|obj| obj.object_handle())
+            .filter_map(|obj| match obj.get_typed() {
+                TypedObjectHandle::AnimationStack(o) => Some(o),
+                _ => None,
+            })
+    }
+
+    /// Returns an iterator of child animation curve nodes.
+    pub fn curve_nodes(
+        &self,
+    ) -> impl Iterator<Item = animationcurvenode::AnimationCurveNodeHandle<'a>> {
+        self.source_objects()
+            .filter(|obj| obj.label().is_none())
+            .filter_map(|obj| obj.object_handle())
+            .filter_map(|obj| match obj.get_typed() {
+                TypedObjectHandle::AnimationCurveNode(o) => Some(o),
+                _ => None,
+            })
+    }
+}
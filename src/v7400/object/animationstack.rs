@@ -0,0 +1,106 @@
+//! `AnimationStack` object.
+
+use crate::v7400::{
+    data::time::FbxTime,
+    object::{
+        animationlayer,
+        property::{loaders::FbxTimeLoader, ObjectProperties},
+        ObjectHandle, TypedObjectHandle,
+    },
+};
+
+define_object_subtype! {
+    /// `AnimationStack` node handle.
+    AnimationStackHandle: ObjectHandle
+}
+
+impl<'a> AnimationStackHandle<'a> {
+    /// Returns an iterator of child animation layers.
+    pub fn layers(&self) -> impl Iterator<Item = animationlayer::AnimationLayerHandle<'a>> {
+        self.source_objects()
+            .filter(|obj| obj.label().is_none())
+            .filter_map(|obj| obj.object_handle())
+            .filter_map(|obj| match obj.get_typed() {
+                TypedObjectHandle::AnimationLayer(o) => Some(o),
+                _ => None,
+            })
+    }
+
+    /// Returns properties.
+    pub fn properties(&self) -> AnimationStackProperties<'a> {
+        AnimationStackProperties {
+            properties: self.properties_by_native_typename("FbxAnimStack"),
+        }
+    }
+}
+
+/// Proxy type to animation stack properties.
+#[derive(Debug, Clone, Copy)]
+pub struct AnimationStackProperties<'a> {
+    /// Properties.
+    properties: ObjectProperties<'a>,
+}
+
+impl<'a> AnimationStackProperties<'a> {
+    impl_prop_proxy_getters! {
+        /// Returns the local start time.
+        local_start -> FbxTime {
+            name = "LocalStart",
+            loader = FbxTimeLoader::new(),
+            description = "local start time",
+            default: {
+                /// Returns the local start time.
+                ///
+                /// Returns default if the value is not set.
+                local_start_or_default = FbxTime::ZERO
+            }
+        }
+
+        /// Returns the local stop time.
+        local_stop -> FbxTime {
+            name = "LocalStop",
+            loader = FbxTimeLoader::new(),
+            description = "local stop time",
+            default: {
+                /// Returns the local stop time.
+                ///
+                /// Returns default if the value is not set.
+                local_stop_or_default = FbxTime::ZERO
+            }
+        }
+
+        /// Returns the reference start time.
+        reference_start -> FbxTime {
+            name = "ReferenceStart",
+            loader = FbxTimeLoader::new(),
+            description = "reference start time",
+            default: {
+                /// Returns the reference start time.
+                ///
+                /// Returns default if the value is not set.
+                reference_start_or_default = FbxTime::ZERO
+            }
+        }
+
+        /// Returns the reference stop time.
+        reference_stop -> FbxTime {
+            name = "ReferenceStop",
+            loader = FbxTimeLoader::new(),
+            description = "reference stop time",
+            default: {
+                /// Returns the reference stop time.
+                ///
+                /// Returns default if the value is not set.
+                reference_stop_or_default = FbxTime::ZERO
+            }
+        }
+    }
+}
+
+impl<'a> std::ops::Deref for AnimationStackProperties<'a> {
+    type Target = ObjectProperties<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.properties
+    }
+}
@@ -3,8 +3,10 @@
 use crate::v7400::object::ObjectHandle;
 
 pub use self::{
-    blendshape::BlendShapeHandle, blendshapechannel::BlendShapeChannelHandle,
-    cluster::ClusterHandle, skin::SkinHandle,
+    blendshape::BlendShapeHandle,
+    blendshapechannel::{BlendShapeChannelHandle, BlendShapeChannelProperties},
+    cluster::ClusterHandle,
+    skin::SkinHandle,
 };
 
 mod blendshape;
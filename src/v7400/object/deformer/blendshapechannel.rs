@@ -4,7 +4,9 @@ use anyhow::{format_err, Error};
 
 use crate::v7400::object::{
     deformer::{self, SubDeformerHandle},
-    geometry, TypedObjectHandle,
+    geometry,
+    property::{loaders::PrimitiveLoader, ObjectProperties},
+    TypedObjectHandle,
 };
 
 define_object_subtype! {
@@ -44,4 +46,93 @@ impl<'a> BlendShapeChannelHandle<'a> {
                 _ => None,
             })
     }
+
+    /// Returns properties.
+    pub fn properties(&self) -> BlendShapeChannelProperties<'a> {
+        BlendShapeChannelProperties {
+            properties: self.properties_by_native_typename("FbxBlendShapeChannel"),
+        }
+    }
+
+    /// Returns the deform percent (`DeformPercent`), in the range of 0 to
+    /// 100.
+    ///
+    /// The `DeformPercent` property is preferred, and the `DeformPercent`
+    /// child node is used if the property is not available.
+    /// Returns 0 if neither is available.
+    pub fn deform_percent(&self) -> Result<f64, Error> {
+        if let Some(v) = self.properties().deform_percent()? {
+            return Ok(v);
+        }
+        match self.child_attribute("DeformPercent") {
+            Some(attr) => attr
+                .get_f64_or_type()
+                .map_err(|ty| format_err!("Expected `f64` as `DeformPercent`, but got {:?}", ty)),
+            None => Ok(0.0),
+        }
+    }
+
+    /// Returns the full weights of the target shapes (`FullWeights`).
+    ///
+    /// A full weight is the deform percent at which the corresponding target
+    /// shape (in the order of [`shapes()`]) is fully applied.
+    /// Returns an empty slice if the full weights are not specified.
+    ///
+    /// [`shapes()`]: #method.shapes
+    pub fn full_weights(&self) -> Result<&'a [f64], Error> {
+        match self.child_attribute("FullWeights") {
+            Some(attr) => attr
+                .get_arr_f64_or_type()
+                .map_err(|ty| format_err!("Expected `[f64]` as `FullWeights`, but got {:?}", ty)),
+            None => Ok(&[]),
+        }
+    }
+
+    /// Returns the full weights of the target shapes, for each shape.
+    ///
+    /// If `FullWeights` is not available or its length does not match the
+    /// number of shapes, evenly spaced full weights (`100 * (i + 1) / count`)
+    /// are returned.
+    pub fn target_full_weights(&self) -> Result<Vec<f64>, Error> {
+        let count = self.shapes().count();
+        let full_weights = self.full_weights()?;
+        if full_weights.len() == count {
+            return Ok(full_weights.to_vec());
+        }
+        Ok((0..count)
+            .map(|i| 100.0 * (i + 1) as f64 / count as f64)
+            .collect())
+    }
+}
+
+/// Proxy type to blendshape channel properties.
+#[derive(Debug, Clone, Copy)]
+pub struct BlendShapeChannelProperties<'a> {
+    /// Properties.
+    properties: ObjectProperties<'a>,
+}
+
+impl<'a> BlendShapeChannelProperties<'a> {
+    impl_prop_proxy_getters! {
+        /// Returns deform percent.
+        deform_percent -> f64 {
+            name = "DeformPercent",
+            loader = PrimitiveLoader::<f64>::new(),
+            description = "deform percent",
+            default: {
+                /// Returns deform percent.
+                ///
+                /// Returns default if the value is not set.
+                deform_percent_or_default = 0.0
+            }
+        }
+    }
+}
+
+impl<'a> std::ops::Deref for BlendShapeChannelProperties<'a> {
+    type Target = ObjectProperties<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.properties
+    }
 }
@@ -1,10 +1,19 @@
 //! `SubDeformer` object (cluster).
 
-use anyhow::{format_err, Error};
+use std::convert::TryFrom;
 
-use crate::v7400::object::{
-    deformer::{self, SubDeformerHandle},
-    TypedObjectHandle,
+use anyhow::{bail, format_err, Error};
+use mint::ColumnMatrix4;
+
+use crate::v7400::{
+    data::{
+        deformer::LinkMode,
+        mesh::{control_point_indices, ControlPointIndex},
+    },
+    object::{
+        deformer::{self, SubDeformerHandle},
+        model, pose, TypedObjectHandle,
+    },
 };
 
 define_object_subtype! {
@@ -30,4 +39,167 @@ impl<'a> ClusterHandle<'a> {
                 )
             })
     }
+
+    /// Returns the linked model (bone), if available.
+    ///
+    /// The linked model is usually `LimbNode` or `Null` model.
+    pub fn link_model(&self) -> Option<model::ModelHandle<'a>> {
+        self.source_objects()
+            .filter(|obj| obj.label().is_none())
+            .filter_map(|obj| obj.object_handle())
+            .find_map(|obj| match obj.get_typed() {
+                TypedObjectHandle::Model(o) => Some(*o),
+                _ => None,
+            })
+    }
+
+    /// Returns control point indices (`Indexes`).
+    ///
+    /// Returns an empty slice if the cluster has no influences.
+    pub fn indexes(&self) -> Result<&'a [i32], Error> {
+        match self.child_attribute("Indexes") {
+            Some(attr) => attr
+                .get_arr_i32_or_type()
+                .map_err(|ty| format_err!("Expected `[i32]` as `Indexes`, but got {:?}", ty)),
+            None => Ok(&[]),
+        }
+    }
+
+    /// Returns weights (`Weights`).
+    ///
+    /// Returns an empty slice if the cluster has no influences.
+    pub fn weights(&self) -> Result<&'a [f64], Error> {
+        match self.child_attribute("Weights") {
+            Some(attr) => attr
+                .get_arr_f64_or_type()
+                .map_err(|ty| format_err!("Expected `[f64]` as `Weights`, but got {:?}", ty)),
+            None => Ok(&[]),
+        }
+    }
+
+    /// Returns an iterator of influenced control points and weights.
+    pub fn influences(&self) -> Result<impl Iterator<Item = (ControlPointIndex, f64)> + 'a, Error> {
+        let indexes = self.indexes()?;
+        let weights = self.weights()?;
+        if indexes.len() != weights.len() {
+            bail!(
+                "Cluster indexes and weights count mismatch: indexes={}, weights={}",
+                indexes.len(),
+                weights.len()
+            );
+        }
+        Ok(control_point_indices(indexes, "cluster")?.zip(weights.iter().copied()))
+    }
+
+    /// Returns the transform matrix of the mesh at binding time (`Transform`).
+    pub fn transform(&self) -> Result<Option<ColumnMatrix4<f64>>, Error> {
+        self.matrix("Transform")
+    }
+
+    /// Returns the transform matrix of the linked model at binding time
+    /// (`TransformLink`).
+    pub fn transform_link(&self) -> Result<Option<ColumnMatrix4<f64>>, Error> {
+        self.matrix("TransformLink")
+    }
+
+    /// Returns the transform matrix of the associate model at binding time
+    /// (`TransformAssociateModel`).
+    ///
+    /// This is usually available only for additive clusters.
+    pub fn transform_associate_model(&self) -> Result<Option<ColumnMatrix4<f64>>, Error> {
+        self.matrix("TransformAssociateModel")
+    }
+
+    /// Returns the global transform matrix of the linked model at binding
+    /// time.
+    ///
+    /// This returns `TransformLink` if it is available and consistent (i.e.
+    /// finite and invertible). Otherwise, the matrix of the linked model in
+    /// a bind pose is returned. Files exported by some (especially old)
+    /// exporters lack a valid `TransformLink`, and this fallback is useful
+    /// for them.
+    pub fn bind_matrix(&self) -> Result<Option<ColumnMatrix4<f64>>, Error> {
+        if let Some(m) = self.transform_link()? {
+            if is_consistent_matrix(&m) {
+                return Ok(Some(m));
+            }
+        }
+        let link_id = match self.link_model() {
+            Some(model) => model.object_id(),
+            None => return Ok(None),
+        };
+        for obj in self.document().objects() {
+            let pose = match obj.get_typed() {
+                TypedObjectHandle::Pose(o) => o,
+                _ => continue,
+            };
+            if !pose.is_bind_pose() {
+                continue;
+            }
+            if let Some(m) = pose.matrix(link_id)? {
+                return Ok(Some(m));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the bind pose which contains the linked model, if available.
+    pub fn bind_pose(&self) -> Option<pose::PoseHandle<'a>> {
+        let link_id = self.link_model()?.object_id();
+        self.document()
+            .objects()
+            .filter_map(|obj| match obj.get_typed() {
+                TypedObjectHandle::Pose(o) => Some(o),
+                _ => None,
+            })
+            .find(|pose| pose.is_bind_pose() && pose.pose_node(link_id).is_some())
+    }
+
+    /// Returns the link mode (`Mode`).
+    ///
+    /// Returns `LinkMode::Normalize` if the mode is not specified.
+    pub fn link_mode(&self) -> Result<LinkMode, Error> {
+        match self.child_attribute("Mode") {
+            Some(attr) => attr
+                .get_string_or_type()
+                .map_err(|ty| format_err!("Expected string as `Mode`, but got {:?}", ty))?
+                .parse(),
+            None => Ok(LinkMode::default()),
+        }
+    }
+
+    /// Returns the matrix stored in the child node with the given name.
+    fn matrix(&self, name: &str) -> Result<Option<ColumnMatrix4<f64>>, Error> {
+        let attr = match self.child_attribute(name) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        let values = attr
+            .get_arr_f64_or_type()
+            .map_err(|ty| format_err!("Expected `[f64]` as `{}`, but got {:?}", name, ty))?;
+        <[f64; 16]>::try_from(values)
+            .map(|m| Some(m.into()))
+            .map_err(|_| {
+                format_err!(
+                    "Expected 16 elements as `{}`, but got {}",
+                    name,
+                    values.len()
+                )
+            })
+    }
+}
+
+/// Returns whether the matrix is usable as a bind matrix.
+///
+/// A matrix is considered consistent if all elements are finite and the
+/// linear part is invertible.
+fn is_consistent_matrix(m: &ColumnMatrix4<f64>) -> bool {
+    let arr: [f64; 16] = (*m).into();
+    if !arr.iter().all(|v| v.is_finite()) {
+        return false;
+    }
+    let (x, y, z) = (m.x, m.y, m.z);
+    let det = x.x * (y.y * z.z - y.z * z.y) - y.x * (x.y * z.z - x.z * z.y)
+        + z.x * (x.y * y.z - x.z * y.y);
+    det.abs() > 1e-12
 }
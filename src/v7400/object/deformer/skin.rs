@@ -1,10 +1,16 @@
 //! `Deformer` object (skin).
 
-use anyhow::{format_err, Error};
+use anyhow::{bail, format_err, Error};
 
-use crate::v7400::object::{
-    deformer::{self, DeformerHandle},
-    geometry, TypedObjectHandle,
+use crate::v7400::{
+    data::{
+        deformer::{SkinInfluences, SkinningType},
+        mesh::{control_point_indices, ControlPointIndex},
+    },
+    object::{
+        deformer::{self, DeformerHandle},
+        geometry, TypedObjectHandle,
+    },
 };
 
 define_object_subtype! {
@@ -43,4 +49,66 @@ impl<'a> SkinHandle<'a> {
                 _ => None,
             })
     }
+
+    /// Returns per-control-point influences.
+    pub fn influences(&self) -> Result<SkinInfluences, Error> {
+        SkinInfluences::from_skin(self)
+    }
+
+    /// Returns the skinning type (`SkinningType`).
+    ///
+    /// Returns `SkinningType::Linear` if the type is not specified.
+    pub fn skinning_type(&self) -> Result<SkinningType, Error> {
+        match self.child_attribute("SkinningType") {
+            Some(attr) => attr
+                .get_string_or_type()
+                .map_err(|ty| format_err!("Expected string as `SkinningType`, but got {:?}", ty))?
+                .parse(),
+            None => Ok(SkinningType::default()),
+        }
+    }
+
+    /// Returns control point indices for blend weights (`BlendIndexes`).
+    ///
+    /// Returns an empty slice if the skin has no blend weights.
+    pub fn blend_indexes(&self) -> Result<&'a [i32], Error> {
+        match self.child_attribute("BlendIndexes") {
+            Some(attr) => attr
+                .get_arr_i32_or_type()
+                .map_err(|ty| format_err!("Expected `[i32]` as `BlendIndexes`, but got {:?}", ty)),
+            None => Ok(&[]),
+        }
+    }
+
+    /// Returns blend weights (`BlendWeights`).
+    ///
+    /// Returns an empty slice if the skin has no blend weights.
+    pub fn blend_weights(&self) -> Result<&'a [f64], Error> {
+        match self.child_attribute("BlendWeights") {
+            Some(attr) => attr
+                .get_arr_f64_or_type()
+                .map_err(|ty| format_err!("Expected `[f64]` as `BlendWeights`, but got {:?}", ty)),
+            None => Ok(&[]),
+        }
+    }
+
+    /// Returns an iterator of control points and blend weights.
+    ///
+    /// A blend weight is the weight of dual quaternion skinning for
+    /// `SkinningType::Blend`.
+    /// Control points not listed have blend weight 0.
+    pub fn control_point_blend_weights(
+        &self,
+    ) -> Result<impl Iterator<Item = (ControlPointIndex, f64)> + 'a, Error> {
+        let indexes = self.blend_indexes()?;
+        let weights = self.blend_weights()?;
+        if indexes.len() != weights.len() {
+            bail!(
+                "Skin blend indexes and weights count mismatch: indexes={}, weights={}",
+                indexes.len(),
+                weights.len()
+            );
+        }
+        Ok(control_point_indices(indexes, "skin blend indexes")?.zip(weights.iter().copied()))
+    }
 }
@@ -3,7 +3,11 @@
 use anyhow::{format_err, Error};
 
 use crate::v7400::{
-    data::mesh::{layer::LayerHandle, ControlPoints, PolygonVertices, RawPolygonVertices},
+    data::mesh::{
+        layer::{uv::UvSet, LayerElementType, LayerHandle, TypedLayerElementHandle},
+        ControlPoints, Edges, IndexedMeshBuilder, NormalGenerator, PolygonVertices,
+        RawPolygonVertices,
+    },
     object::{deformer, geometry::GeometryHandle, model, TypedObjectHandle},
 };
 
@@ -88,15 +92,140 @@ impl<'a> MeshHandle<'a> {
             })
     }
 
+    /// Returns raw edges (polygon vertex indices), if available.
+    pub(crate) fn raw_edges(&self) -> Result<Option<&'a [i32]>, Error> {
+        let edges_node = match self.node().children_by_name("Edges").next() {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        edges_node
+            .attributes()
+            .get(0)
+            .ok_or_else(|| format_err!("`Edges` node has no attributes"))?
+            .get_arr_i32_or_type()
+            .map(Some)
+            .map_err(|ty| {
+                format_err!(
+                    "`Edges` has wrong type attribute: expected `[i32]` but got {:?}`",
+                    ty
+                )
+            })
+    }
+
     /// Returns polygon vertices (control point indices) and control points.
     pub fn polygon_vertices(&self) -> Result<PolygonVertices<'a>, Error> {
         let control_points = self.control_points()?;
         let raw_polygon_vertices = self.raw_polygon_vertices()?;
-        Ok(PolygonVertices::new(control_points, raw_polygon_vertices))
+        let raw_edges = self.raw_edges()?;
+        Ok(PolygonVertices::new(
+            control_points,
+            raw_polygon_vertices,
+            raw_edges,
+        ))
+    }
+
+    /// Returns edges and the edge-to-polygon adjacency.
+    ///
+    /// This fails if the mesh has no `Edges`.
+    pub fn edges(&self) -> Result<Edges<'a>, Error> {
+        self.polygon_vertices()?.edges()
     }
 
     /// Returns layers.
     pub fn layers(&self) -> impl Iterator<Item = LayerHandle<'a>> {
         self.node().children_by_name("Layer").map(LayerHandle::new)
     }
+
+    /// Returns an iterator of layer elements of the given type.
+    ///
+    /// Layer elements are iterated in the order of nodes, regardless of
+    /// whether they are referred from layers.
+    pub fn layer_elements(
+        &self,
+        ty: LayerElementType<'a>,
+    ) -> impl Iterator<Item = TypedLayerElementHandle<'a>> {
+        self.node()
+            .children_by_name(ty.type_name())
+            .map(move |node| TypedLayerElementHandle::new(ty, node))
+    }
+
+    /// Returns UV sets referred from layers.
+    ///
+    /// UV sets are sorted by the layer index and the type-local index.
+    pub fn uv_sets(&self) -> Result<Vec<UvSet<'a>>, Error> {
+        let mut uv_sets = Vec::new();
+        for layer in self.layers() {
+            let layer_index = layer.get_index()?;
+            for entry in layer.layer_element_entries() {
+                if entry.type_()? != LayerElementType::Uv {
+                    continue;
+                }
+                if let TypedLayerElementHandle::Uv(handle) = entry.typed_layer_element()? {
+                    uv_sets.push(UvSet::new(layer_index, handle)?);
+                }
+            }
+        }
+        uv_sets.sort_by_key(|uv_set| (uv_set.layer_index(), uv_set.typed_index()));
+
+        Ok(uv_sets)
+    }
+
+    /// Returns the UV set with the given name.
+    ///
+    /// If no UV sets have the name and the name is `"default"` or empty, the
+    /// first UV set is returned.
+    /// This is consistent with the default value of the `UVSet` property of
+    /// textures.
+    ///
+    /// Returns `Ok(None)` if the UV set is not found.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use fbxcel_dom::v7400::object::{geometry::MeshHandle, texture::TextureHandle};
+    /// # fn get_mesh<'a>() -> MeshHandle<'a> { unimplemented!() }
+    /// # fn get_texture<'a>() -> TextureHandle<'a> { unimplemented!() }
+    /// let mesh: MeshHandle<'_> = get_mesh();
+    /// let texture: TextureHandle<'_> = get_texture();
+    /// let uv_set_name = texture.properties().uv_set_or_default()?;
+    /// match mesh.uv_set_by_name(uv_set_name)? {
+    ///     Some(uv_set) => println!("UV set {:?} is {:?}", uv_set_name, uv_set.typed_index()),
+    ///     None => println!("UV set {:?} is not found", uv_set_name),
+    /// }
+    /// # Ok::<_, anyhow::Error>(())
+    /// ```
+    pub fn uv_set_by_name(&self, name: &str) -> Result<Option<UvSet<'a>>, Error> {
+        let uv_sets = self.uv_sets()?;
+        if let Some(uv_set) = uv_sets.iter().find(|uv_set| uv_set.name() == name) {
+            return Ok(Some(*uv_set));
+        }
+        if name.is_empty() || name == "default" {
+            return Ok(uv_sets.first().copied());
+        }
+
+        Ok(None)
+    }
+
+    /// Returns a builder of an indexed mesh.
+    pub fn indexed_mesh_builder(&self) -> IndexedMeshBuilder<'a> {
+        IndexedMeshBuilder::new(self)
+    }
+
+    /// Returns a smooth normal generator.
+    ///
+    /// The first `LayerElementSmoothing` is used if available.
+    pub fn normal_generator(&self) -> Result<NormalGenerator<'a>, Error> {
+        let smoothing = self
+            .layer_elements(LayerElementType::Smoothing)
+            .find_map(|elem| match elem {
+                TypedLayerElementHandle::Smoothing(v) => Some(v),
+                _ => None,
+            })
+            .map(|elem| elem.smoothing())
+            .transpose()?;
+        let mut generator = self.polygon_vertices()?.normal_generator();
+        generator.smoothing(smoothing);
+
+        Ok(generator)
+    }
 }
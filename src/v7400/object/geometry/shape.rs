@@ -1,8 +1,12 @@
 //! `Geometry` object (shape).
 
-use anyhow::{format_err, Error};
+use anyhow::{bail, format_err, Error};
+use mint::Vector3;
 
-use crate::v7400::object::{deformer, geometry::GeometryHandle, TypedObjectHandle};
+use crate::v7400::{
+    data::mesh::{control_point_indices, ControlPointIndex},
+    object::{deformer, geometry::GeometryHandle, TypedObjectHandle},
+};
 
 define_object_subtype! {
     /// `Geometry` node handle (shape).
@@ -30,4 +34,83 @@ impl<'a> ShapeHandle<'a> {
                 )
             })
     }
+
+    /// Returns control point indices (`Indexes`).
+    ///
+    /// Returns an empty slice if the shape has no indices.
+    pub fn indexes(&self) -> Result<&'a [i32], Error> {
+        match self.child_attribute("Indexes") {
+            Some(attr) => attr
+                .get_arr_i32_or_type()
+                .map_err(|ty| format_err!("Expected `[i32]` as `Indexes`, but got {:?}", ty)),
+            None => Ok(&[]),
+        }
+    }
+
+    /// Returns raw vertex deltas (`Vertices`).
+    ///
+    /// Returns an empty slice if the shape has no vertices.
+    pub fn vertices(&self) -> Result<&'a [f64], Error> {
+        match self.child_attribute("Vertices") {
+            Some(attr) => attr
+                .get_arr_f64_or_type()
+                .map_err(|ty| format_err!("Expected `[f64]` as `Vertices`, but got {:?}", ty)),
+            None => Ok(&[]),
+        }
+    }
+
+    /// Returns raw normal deltas (`Normals`), if available.
+    pub fn normals(&self) -> Result<Option<&'a [f64]>, Error> {
+        self.child_attribute("Normals")
+            .map(|attr| {
+                attr.get_arr_f64_or_type()
+                    .map_err(|ty| format_err!("Expected `[f64]` as `Normals`, but got {:?}", ty))
+            })
+            .transpose()
+    }
+
+    /// Returns an iterator of control point indices.
+    pub fn control_point_indices(
+        &self,
+    ) -> Result<impl Iterator<Item = ControlPointIndex> + 'a, Error> {
+        control_point_indices(self.indexes()?, "shape")
+    }
+
+    /// Returns an iterator of control point indices and vertex deltas.
+    pub fn vertex_deltas(
+        &self,
+    ) -> Result<impl Iterator<Item = (ControlPointIndex, Vector3<f64>)> + 'a, Error> {
+        self.deltas("Vertices", self.vertices()?)
+    }
+
+    /// Returns an iterator of control point indices and normal deltas, if
+    /// available.
+    pub fn normal_deltas(
+        &self,
+    ) -> Result<Option<impl Iterator<Item = (ControlPointIndex, Vector3<f64>)> + 'a>, Error> {
+        self.normals()?
+            .map(|normals| self.deltas("Normals", normals))
+            .transpose()
+    }
+
+    /// Returns an iterator of control point indices and the given deltas.
+    fn deltas(
+        &self,
+        name: &str,
+        values: &'a [f64],
+    ) -> Result<impl Iterator<Item = (ControlPointIndex, Vector3<f64>)> + 'a, Error> {
+        let count = self.indexes()?.len();
+        if values.len() != count * 3 {
+            bail!(
+                "Shape `{}` count mismatch: expected {} elements for {} indexes, but got {}",
+                name,
+                count * 3,
+                count,
+                values.len()
+            );
+        }
+        Ok(self
+            .control_point_indices()?
+            .zip(values.chunks_exact(3).map(Vector3::from_slice)))
+    }
 }
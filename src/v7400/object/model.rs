@@ -1,10 +1,23 @@
 //! `Model` object.
 
-use crate::v7400::object::{ObjectHandle, TypedObjectHandle};
+use anyhow::Error;
+use mint::{ColumnMatrix4, Point3, Vector3};
+
+use crate::v7400::{
+    data::model::{InheritType, InheritTypeLoader, RotationOrder, RotationOrderLoader},
+    math::Mat4,
+    object::{
+        property::{
+            loaders::{MintLoader, PrimitiveLoader},
+            ObjectProperties,
+        },
+        ObjectHandle, TypedObjectHandle,
+    },
+};
 
 pub use self::{
     camera::CameraHandle, light::LightHandle, limbnode::LimbNodeHandle, mesh::MeshHandle,
-    null::NullHandle,
+    null::NullHandle, transform::TransformEvaluator,
 };
 
 mod camera;
@@ -12,6 +25,7 @@ mod light;
 mod limbnode;
 mod mesh;
 mod null;
+mod transform;
 
 define_typed_handle! {
     /// Typed model handle.
@@ -57,4 +71,365 @@ impl<'a> ModelHandle<'a> {
                 _ => None,
             })
     }
+
+    /// Returns properties.
+    pub fn properties(&self) -> ModelProperties<'a> {
+        ModelProperties {
+            properties: self.properties_by_native_typename("FbxNode"),
+        }
+    }
+
+    /// Returns the local transform matrix.
+    ///
+    /// This is a shorthand for `self.properties().local_transform()`.
+    pub fn local_transform(&self) -> Result<ColumnMatrix4<f64>, Error> {
+        self.properties().local_transform()
+    }
+
+    /// Returns the world transform matrix.
+    ///
+    /// This evaluates all ancestor models without caching.
+    /// To evaluate transforms of many models in the same hierarchy, use
+    /// [`TransformEvaluator`] instead.
+    ///
+    /// [`TransformEvaluator`]: struct.TransformEvaluator.html
+    pub fn world_transform(&self) -> Result<ColumnMatrix4<f64>, Error> {
+        TransformEvaluator::new(self.document()).world_transform(self)
+    }
+}
+
+/// Proxy type to model properties.
+#[derive(Debug, Clone, Copy)]
+pub struct ModelProperties<'a> {
+    /// Properties.
+    properties: ObjectProperties<'a>,
+}
+
+impl<'a> ModelProperties<'a> {
+    impl_prop_proxy_getters! {
+        /// Returns local translation.
+        local_translation -> Vector3<f64> {
+            name = "Lcl Translation",
+            loader = MintLoader::<Vector3<f64>>::new(),
+            description = "local translation",
+            default: {
+                /// Returns local translation.
+                ///
+                /// Returns default if the value is not set.
+                local_translation_or_default = Vector3 { x: 0.0, y: 0.0, z: 0.0 }
+            }
+        }
+
+        /// Returns local rotation (euler angles in degrees).
+        local_rotation -> Vector3<f64> {
+            name = "Lcl Rotation",
+            loader = MintLoader::<Vector3<f64>>::new(),
+            description = "local rotation",
+            default: {
+                /// Returns local rotation (euler angles in degrees).
+                ///
+                /// Returns default if the value is not set.
+                local_rotation_or_default = Vector3 { x: 0.0, y: 0.0, z: 0.0 }
+            }
+        }
+
+        /// Returns local scaling.
+        local_scaling -> Vector3<f64> {
+            name = "Lcl Scaling",
+            loader = MintLoader::<Vector3<f64>>::new(),
+            description = "local scaling",
+            default: {
+                /// Returns local scaling.
+                ///
+                /// Returns default if the value is not set.
+                local_scaling_or_default = Vector3 { x: 1.0, y: 1.0, z: 1.0 }
+            }
+        }
+
+        /// Returns rotation order.
+        rotation_order -> RotationOrder {
+            name = "RotationOrder",
+            loader = RotationOrderLoader::default(),
+            description = "rotation order",
+            default: {
+                /// Returns rotation order.
+                ///
+                /// Returns default if the value is not set.
+                rotation_order_or_default = RotationOrder::Xyz
+            }
+        }
+
+        /// Returns rotation active flag.
+        ///
+        /// If this is `false`, rotation order, pre-rotation, and post-rotation
+        /// are ignored.
+        rotation_active -> bool {
+            name = "RotationActive",
+            loader = PrimitiveLoader::<bool>::new(),
+            description = "rotation active flag",
+            default: {
+                /// Returns rotation active flag.
+                ///
+                /// Returns default if the value is not set.
+                rotation_active_or_default = false
+            }
+        }
+
+        /// Returns pre-rotation (euler angles in degrees).
+        pre_rotation -> Vector3<f64> {
+            name = "PreRotation",
+            loader = MintLoader::<Vector3<f64>>::new(),
+            description = "pre-rotation",
+            default: {
+                /// Returns pre-rotation (euler angles in degrees).
+                ///
+                /// Returns default if the value is not set.
+                pre_rotation_or_default = Vector3 { x: 0.0, y: 0.0, z: 0.0 }
+            }
+        }
+
+        /// Returns post-rotation (euler angles in degrees).
+        post_rotation -> Vector3<f64> {
+            name = "PostRotation",
+            loader = MintLoader::<Vector3<f64>>::new(),
+            description = "post-rotation",
+            default: {
+                /// Returns post-rotation (euler angles in degrees).
+                ///
+                /// Returns default if the value is not set.
+                post_rotation_or_default = Vector3 { x: 0.0, y: 0.0, z: 0.0 }
+            }
+        }
+
+        /// Returns rotation offset.
+        rotation_offset -> Vector3<f64> {
+            name = "RotationOffset",
+            loader = MintLoader::<Vector3<f64>>::new(),
+            description = "rotation offset",
+            default: {
+                /// Returns rotation offset.
+                ///
+                /// Returns default if the value is not set.
+                rotation_offset_or_default = Vector3 { x: 0.0, y: 0.0, z: 0.0 }
+            }
+        }
+
+        /// Returns rotation pivot.
+        rotation_pivot -> Point3<f64> {
+            name = "RotationPivot",
+            loader = MintLoader::<Point3<f64>>::new(),
+            description = "rotation pivot",
+            default: {
+                /// Returns rotation pivot.
+                ///
+                /// Returns default if the value is not set.
+                rotation_pivot_or_default = Point3 { x: 0.0, y: 0.0, z: 0.0 }
+            }
+        }
+
+        /// Returns scaling offset.
+        scaling_offset -> Vector3<f64> {
+            name = "ScalingOffset",
+            loader = MintLoader::<Vector3<f64>>::new(),
+            description = "scaling offset",
+            default: {
+                /// Returns scaling offset.
+                ///
+                /// Returns default if the value is not set.
+                scaling_offset_or_default = Vector3 { x: 0.0, y: 0.0, z: 0.0 }
+            }
+        }
+
+        /// Returns scaling pivot.
+        scaling_pivot -> Point3<f64> {
+            name = "ScalingPivot",
+            loader = MintLoader::<Point3<f64>>::new(),
+            description = "scaling pivot",
+            default: {
+                /// Returns scaling pivot.
+                ///
+                /// Returns default if the value is not set.
+                scaling_pivot_or_default = Point3 { x: 0.0, y: 0.0, z: 0.0 }
+            }
+        }
+
+        /// Returns transform inheritance type.
+        inherit_type -> InheritType {
+            name = "InheritType",
+            loader = InheritTypeLoader::default(),
+            description = "inherit type",
+            default: {
+                /// Returns transform inheritance type.
+                ///
+                /// Returns default if the value is not set.
+                inherit_type_or_default = InheritType::RrSs
+            }
+        }
+
+        /// Returns geometric translation.
+        ///
+        /// Geometric transform affects only the attached node attribute or
+        /// geometry, and is not inherited by the child models.
+        geometric_translation -> Vector3<f64> {
+            name = "GeometricTranslation",
+            loader = MintLoader::<Vector3<f64>>::new(),
+            description = "geometric translation",
+            default: {
+                /// Returns geometric translation.
+                ///
+                /// Returns default if the value is not set.
+                geometric_translation_or_default = Vector3 { x: 0.0, y: 0.0, z: 0.0 }
+            }
+        }
+
+        /// Returns geometric rotation (euler angles in degrees).
+        ///
+        /// Geometric transform affects only the attached node attribute or
+        /// geometry, and is not inherited by the child models.
+        geometric_rotation -> Vector3<f64> {
+            name = "GeometricRotation",
+            loader = MintLoader::<Vector3<f64>>::new(),
+            description = "geometric rotation",
+            default: {
+                /// Returns geometric rotation (euler angles in degrees).
+                ///
+                /// Returns default if the value is not set.
+                geometric_rotation_or_default = Vector3 { x: 0.0, y: 0.0, z: 0.0 }
+            }
+        }
+
+        /// Returns geometric scaling.
+        ///
+        /// Geometric transform affects only the attached node attribute or
+        /// geometry, and is not inherited by the child models.
+        geometric_scaling -> Vector3<f64> {
+            name = "GeometricScaling",
+            loader = MintLoader::<Vector3<f64>>::new(),
+            description = "geometric scaling",
+            default: {
+                /// Returns geometric scaling.
+                ///
+                /// Returns default if the value is not set.
+                geometric_scaling_or_default = Vector3 { x: 1.0, y: 1.0, z: 1.0 }
+            }
+        }
+    }
+
+    /// Returns the local transform matrix.
+    ///
+    /// The matrix is calculated as below:
+    ///
+    /// ```text
+    /// T * Roff * Rp * Rpre * R * Rpost^-1 * Rp^-1 * Soff * Sp * S * Sp^-1
+    /// ```
+    ///
+    /// where `T` is local translation, `Roff` and `Rp` are rotation offset and
+    /// pivot, `Rpre`, `R`, and `Rpost` are pre-rotation, local rotation, and
+    /// post-rotation, `Soff` and `Sp` are scaling offset and pivot, and `S` is
+    /// local scaling.
+    ///
+    /// The returned matrix does not contain geometric transform.
+    pub fn local_transform(&self) -> Result<ColumnMatrix4<f64>, Error> {
+        LocalTransform::from_properties(self).map(|t| t.matrix().into())
+    }
+
+    /// Returns the geometric transform matrix.
+    ///
+    /// The matrix is calculated as `Tg * Rg * Sg`, where `Tg`, `Rg`, and `Sg`
+    /// are geometric translation, rotation, and scaling.
+    pub fn geometric_transform(&self) -> Result<ColumnMatrix4<f64>, Error> {
+        let translation = Mat4::translation(self.geometric_translation_or_default()?);
+        let rotation = Mat4::euler(self.geometric_rotation_or_default()?, RotationOrder::Xyz);
+        let scaling = Mat4::scaling(self.geometric_scaling_or_default()?);
+        Ok((translation * rotation * scaling).into())
+    }
+}
+
+impl<'a> std::ops::Deref for ModelProperties<'a> {
+    type Target = ObjectProperties<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.properties
+    }
+}
+
+/// Decomposed local transform of a model.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LocalTransform {
+    /// Local translation (`T`).
+    translation: Mat4,
+    /// Rotation offset and rotation pivot (`Roff * Rp`).
+    rotation_offset_pivot: Mat4,
+    /// Rotation (`Rpre * R * Rpost^-1`).
+    rotation: Mat4,
+    /// Inverse of rotation pivot (`Rp^-1`).
+    rotation_pivot_inv: Mat4,
+    /// Scaling offset and scaling pivot (`Soff * Sp`).
+    scaling_offset_pivot: Mat4,
+    /// Scaling (`S`).
+    scaling: Mat4,
+    /// Inverse of scaling pivot (`Sp^-1`).
+    scaling_pivot_inv: Mat4,
+}
+
+impl LocalTransform {
+    /// Loads the local transform from the given properties.
+    pub(crate) fn from_properties(props: &ModelProperties<'_>) -> Result<Self, Error> {
+        let rotation_active = props.rotation_active_or_default()?;
+        let (rotation_order, pre_rotation, post_rotation) = if rotation_active {
+            (
+                props.rotation_order_or_default()?,
+                Mat4::euler(props.pre_rotation_or_default()?, RotationOrder::Xyz),
+                Mat4::euler(props.post_rotation_or_default()?, RotationOrder::Xyz),
+            )
+        } else {
+            (RotationOrder::Xyz, Mat4::IDENTITY, Mat4::IDENTITY)
+        };
+        let post_rotation_inv = post_rotation.transpose();
+        let rotation = Mat4::euler(props.local_rotation_or_default()?, rotation_order);
+
+        let rotation_pivot = point_to_vector(props.rotation_pivot_or_default()?);
+        let scaling_pivot = point_to_vector(props.scaling_pivot_or_default()?);
+
+        Ok(Self {
+            translation: Mat4::translation(props.local_translation_or_default()?),
+            rotation_offset_pivot: Mat4::translation(props.rotation_offset_or_default()?)
+                * Mat4::translation(rotation_pivot),
+            rotation: pre_rotation * rotation * post_rotation_inv,
+            rotation_pivot_inv: Mat4::translation(negate(rotation_pivot)),
+            scaling_offset_pivot: Mat4::translation(props.scaling_offset_or_default()?)
+                * Mat4::translation(scaling_pivot),
+            scaling: Mat4::scaling(props.local_scaling_or_default()?),
+            scaling_pivot_inv: Mat4::translation(negate(scaling_pivot)),
+        })
+    }
+
+    /// Returns the local transform matrix.
+    pub(crate) fn matrix(&self) -> Mat4 {
+        self.translation
+            * self.rotation_offset_pivot
+            * self.rotation
+            * self.rotation_pivot_inv
+            * self.scaling_offset_pivot
+            * self.scaling
+            * self.scaling_pivot_inv
+    }
+}
+
+/// Converts a point into a vector.
+fn point_to_vector(p: Point3<f64>) -> Vector3<f64> {
+    Vector3 {
+        x: p.x,
+        y: p.y,
+        z: p.z,
+    }
+}
+
+/// Negates the given vector.
+fn negate(v: Vector3<f64>) -> Vector3<f64> {
+    Vector3 {
+        x: -v.x,
+        y: -v.y,
+        z: -v.z,
+    }
 }
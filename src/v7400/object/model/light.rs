@@ -1,8 +1,23 @@
 //! `Model` object (light).
 
-use crate::v7400::object::model::ModelHandle;
+use crate::v7400::object::{model::ModelHandle, nodeattribute, TypedObjectHandle};
 
 define_object_subtype! {
     /// `Model` node handle (light).
     LightHandle: ModelHandle
 }
+
+impl<'a> LightHandle<'a> {
+    /// Returns the light node attribute, if available.
+    pub fn attribute(&self) -> Option<nodeattribute::LightHandle<'a>> {
+        self.source_objects()
+            .filter(|obj| obj.label().is_none())
+            .filter_map(|obj| obj.object_handle())
+            .find_map(|obj| match obj.get_typed() {
+                TypedObjectHandle::NodeAttribute(
+                    nodeattribute::TypedNodeAttributeHandle::Light(o),
+                ) => Some(o),
+                _ => None,
+            })
+    }
+}
@@ -2,7 +2,16 @@
 
 use anyhow::{format_err, Error};
 
-use crate::v7400::object::{geometry, material, model::ModelHandle, TypedObjectHandle};
+use crate::v7400::{
+    data::mesh::{
+        layer::{
+            material::{MaterialIndex, Materials},
+            LayerElementType, TypedLayerElementHandle,
+        },
+        PolygonSubmesh, Submesh, TriangleVertices,
+    },
+    object::{geometry, material, model::ModelHandle, TypedObjectHandle},
+};
 
 define_object_subtype! {
     /// `Model` node handle (mesh).
@@ -29,6 +38,9 @@ impl<'a> MeshHandle<'a> {
     }
 
     /// Returns an iterator of child material objects.
+    ///
+    /// Materials are iterated in the connection order, which is the order
+    /// referred by material indices of `LayerElementMaterial`.
     pub fn materials(&self) -> impl Iterator<Item = material::MaterialHandle<'a>> {
         self.source_objects()
             .filter(|obj| obj.label().is_none())
@@ -38,4 +50,62 @@ impl<'a> MeshHandle<'a> {
                 _ => None,
             })
     }
+
+    /// Returns the child material object with the given material index.
+    pub fn material(&self, index: MaterialIndex) -> Option<material::MaterialHandle<'a>> {
+        self.materials().nth(index.to_u32() as usize)
+    }
+
+    /// Splits the triangles into per-material submeshes.
+    ///
+    /// `tris` should be created from the child geometry of the model.
+    /// Material indices are taken from the first `LayerElementMaterial` of
+    /// the geometry, and if it is not available, all triangles are treated
+    /// as using the material 0.
+    ///
+    /// Submeshes are sorted by the material index, and submeshes without
+    /// triangles are not returned.
+    pub fn submeshes(&self, tris: &TriangleVertices<'a>) -> Result<Vec<Submesh<'a>>, Error> {
+        let material_indices = material_indices(&self.geometry()?)?;
+        let materials = self.materials().collect::<Vec<_>>();
+
+        Submesh::split(tris, material_indices.as_ref(), &materials)
+    }
+
+    /// Splits the polygons of the child geometry into per-material
+    /// submeshes.
+    ///
+    /// Material indices are taken in the same way as [`submeshes`], and this
+    /// fails if their mapping mode is neither `ByPolygon` nor `AllSame`.
+    ///
+    /// Submeshes are sorted by the material index, and submeshes without
+    /// polygons are not returned.
+    ///
+    /// [`submeshes`]: #method.submeshes
+    pub fn polygon_submeshes(&self) -> Result<Vec<PolygonSubmesh<'a>>, Error> {
+        let geometry = self.geometry()?;
+        let material_indices = material_indices(&geometry)?;
+        let materials = self.materials().collect::<Vec<_>>();
+
+        PolygonSubmesh::split(
+            &geometry.polygon_vertices()?,
+            material_indices.as_ref(),
+            &materials,
+        )
+    }
+}
+
+/// Returns material indices of the first `LayerElementMaterial` of the
+/// geometry, if available.
+fn material_indices<'a>(
+    geometry: &geometry::MeshHandle<'a>,
+) -> Result<Option<Materials<'a>>, Error> {
+    geometry
+        .layer_elements(LayerElementType::Material)
+        .find_map(|elem| match elem {
+            TypedLayerElementHandle::Material(v) => Some(v),
+            _ => None,
+        })
+        .map(|elem| elem.materials())
+        .transpose()
 }
@@ -0,0 +1,299 @@
+//! World transform evaluation.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{bail, Error};
+use mint::{ColumnMatrix4, Point3, Vector3};
+
+use crate::v7400::{
+    data::{
+        axis::{AxisConversion, TransformComponent},
+        model::InheritType,
+        time::FbxTime,
+        unit::UnitConversion,
+    },
+    math::Mat4,
+    object::{
+        animationcurvenode::AnimationCurveNodeHandle,
+        model::{LocalTransform, ModelHandle},
+        ObjectId,
+    },
+    Document,
+};
+
+/// World transform evaluator.
+///
+/// This walks model hierarchy (by [`ModelHandle::parent_model()`]) and
+/// composes local transforms, respecting the inherit type of each model.
+/// Calculated world transforms are cached per object, so evaluating many
+/// models in the same hierarchy (such as bones of a rig) is cheap.
+///
+/// If an axis conversion (see [`set_axis_conversion()`]) or a unit
+/// conversion (see [`set_unit_conversion()`]) is set, all returned
+/// transforms and animated values are converted to the target axis system
+/// and unit.
+///
+/// [`ModelHandle::parent_model()`]: struct.ModelHandle.html#method.parent_model
+/// [`set_axis_conversion()`]: #method.set_axis_conversion
+/// [`set_unit_conversion()`]: #method.set_unit_conversion
+#[derive(Debug, Clone)]
+pub struct TransformEvaluator<'a> {
+    /// Document.
+    doc: &'a Document,
+    /// World transforms cache, in the axis system and the unit of the
+    /// document.
+    world_transforms: HashMap<ObjectId, Mat4>,
+    /// Axis conversion.
+    axis_conversion: Option<AxisConversion>,
+    /// Unit conversion.
+    unit_conversion: Option<UnitConversion>,
+}
+
+impl<'a> TransformEvaluator<'a> {
+    /// Creates a new `TransformEvaluator` for the given document.
+    pub fn new(doc: &'a Document) -> Self {
+        Self {
+            doc,
+            world_transforms: HashMap::new(),
+            axis_conversion: None,
+            unit_conversion: None,
+        }
+    }
+
+    /// Returns a reference to the document.
+    pub fn document(&self) -> &'a Document {
+        self.doc
+    }
+
+    /// Returns the axis conversion, if set.
+    pub fn axis_conversion(&self) -> Option<AxisConversion> {
+        self.axis_conversion
+    }
+
+    /// Sets the axis conversion applied to the evaluated transforms and
+    /// animated values.
+    ///
+    /// The conversion is usually created by
+    /// [`GlobalSettings::axis_conversion_to()`].
+    ///
+    /// [`GlobalSettings::axis_conversion_to()`]:
+    /// ../../global_settings/struct.GlobalSettings.html#method.axis_conversion_to
+    pub fn set_axis_conversion(&mut self, conversion: Option<AxisConversion>) -> &mut Self {
+        self.axis_conversion = conversion;
+        self
+    }
+
+    /// Returns the unit conversion, if set.
+    pub fn unit_conversion(&self) -> Option<UnitConversion> {
+        self.unit_conversion
+    }
+
+    /// Sets the unit conversion applied to the evaluated transforms and
+    /// animated values.
+    ///
+    /// The conversion is usually created by
+    /// [`GlobalSettings::unit_conversion_to()`].
+    ///
+    /// [`GlobalSettings::unit_conversion_to()`]:
+    /// ../../global_settings/struct.GlobalSettings.html#method.unit_conversion_to
+    pub fn set_unit_conversion(&mut self, conversion: Option<UnitConversion>) -> &mut Self {
+        self.unit_conversion = conversion;
+        self
+    }
+
+    /// Clears the cache.
+    pub fn clear_cache(&mut self) {
+        self.world_transforms.clear();
+    }
+
+    /// Returns the local transform matrix of the given model.
+    ///
+    /// This is [`ModelHandle::local_transform()`] with the axis and unit
+    /// conversions applied.
+    ///
+    /// [`ModelHandle::local_transform()`]: struct.ModelHandle.html#method.local_transform
+    pub fn local_transform(&self, model: &ModelHandle<'a>) -> Result<ColumnMatrix4<f64>, Error> {
+        model
+            .local_transform()
+            .map(|local| self.convert_matrix(local.into()).into())
+    }
+
+    /// Returns the world transform matrix of the given model.
+    pub fn world_transform(
+        &mut self,
+        model: &ModelHandle<'a>,
+    ) -> Result<ColumnMatrix4<f64>, Error> {
+        self.world_transform_mat(model).map(Into::into)
+    }
+
+    /// Evaluates `d|X`, `d|Y`, and `d|Z` channels of the animation curve node
+    /// at the given time.
+    ///
+    /// If the curve node animates `Lcl Translation`, `Lcl Rotation`, or
+    /// `Lcl Scaling`, the value is converted by the axis conversion (see
+    /// [`AxisConversion::convert_animated()`]).
+    /// Translations are also converted by the unit conversion.
+    /// Note that converted rotations should be applied in the converted
+    /// rotation order (see [`AxisConversion::convert_euler()`]).
+    ///
+    /// [`AxisConversion::convert_animated()`]:
+    /// ../../data/axis/struct.AxisConversion.html#method.convert_animated
+    /// [`AxisConversion::convert_euler()`]:
+    /// ../../data/axis/struct.AxisConversion.html#method.convert_euler
+    pub fn evaluate_curve_node(
+        &self,
+        curve_node: &AnimationCurveNodeHandle<'a>,
+        time: FbxTime,
+    ) -> Result<Vector3<f64>, Error> {
+        let mut value = curve_node.evaluate_vector3(time)?;
+        let component = match curve_node
+            .target()
+            .and_then(|target| transform_component(target.property_name()))
+        {
+            Some(v) => v,
+            None => return Ok(value),
+        };
+        if let (Some(conversion), TransformComponent::Translation) =
+            (self.unit_conversion, component)
+        {
+            value = conversion.convert_vector(value);
+        }
+        if let Some(conversion) = self.axis_conversion {
+            value = conversion.convert_animated(component, value);
+        }
+        Ok(value)
+    }
+
+    /// Converts the transform matrix in the axis system and the unit of the
+    /// document.
+    pub(crate) fn convert_matrix(&self, m: Mat4) -> Mat4 {
+        let mut m = ColumnMatrix4::from(m);
+        if let Some(conversion) = &self.unit_conversion {
+            m = conversion.convert_matrix(m);
+        }
+        if let Some(conversion) = &self.axis_conversion {
+            m = conversion.convert_matrix(m);
+        }
+        m.into()
+    }
+
+    /// Returns the world transform matrix of the given model.
+    pub(crate) fn world_transform_mat(&mut self, model: &ModelHandle<'a>) -> Result<Mat4, Error> {
+        self.source_world_transform(model)
+            .map(|world| self.convert_matrix(world))
+    }
+
+    /// Returns the world transform matrix of the given model, in the axis
+    /// system and the unit of the document.
+    fn source_world_transform(&mut self, model: &ModelHandle<'a>) -> Result<Mat4, Error> {
+        if let Some(world) = self.world_transforms.get(&model.object_id()) {
+            return Ok(*world);
+        }
+
+        // Collect uncached ancestors, from the given model to the root.
+        let mut chain = vec![*model];
+        let mut visited = HashSet::new();
+        visited.insert(model.object_id());
+        let mut parent_world = Mat4::IDENTITY;
+        while let Some(parent) = chain.last().and_then(ModelHandle::parent_model) {
+            if let Some(world) = self.world_transforms.get(&parent.object_id()) {
+                parent_world = *world;
+                break;
+            }
+            if !visited.insert(parent.object_id()) {
+                bail!(
+                    "Cyclic model hierarchy detected: object_id={:?}",
+                    parent.object_id()
+                );
+            }
+            chain.push(*parent);
+        }
+
+        // Evaluate from the topmost uncached model.
+        let mut world = parent_world;
+        for (i, current) in chain.iter().enumerate().rev() {
+            let props = current.properties();
+            let local = LocalTransform::from_properties(&props)?;
+            let inherit_type = props.inherit_type_or_default()?;
+            let parent_local_scaling = if inherit_type == InheritType::Rrs {
+                let parent = match chain.get(i + 1) {
+                    Some(parent) => Some(*parent),
+                    None => current.parent_model().map(|parent| *parent),
+                };
+                parent
+                    .map(|parent| parent.properties().local_scaling_or_default())
+                    .transpose()?
+            } else {
+                None
+            };
+            world = compose_world(&parent_world, &local, inherit_type, parent_local_scaling);
+            self.world_transforms.insert(current.object_id(), world);
+            parent_world = world;
+        }
+
+        Ok(world)
+    }
+}
+
+/// Returns the transform component animated by the given property.
+fn transform_component(property_name: &str) -> Option<TransformComponent> {
+    match property_name {
+        "Lcl Translation" => Some(TransformComponent::Translation),
+        "Lcl Rotation" => Some(TransformComponent::Rotation),
+        "Lcl Scaling" => Some(TransformComponent::Scaling),
+        _ => None,
+    }
+}
+
+/// Calculates the world transform from the parent world transform and the
+/// local transform.
+///
+/// `parent_local_scaling` is used only when the inherit type is `Rrs`.
+fn compose_world(
+    parent_world: &Mat4,
+    local: &LocalTransform,
+    inherit_type: InheritType,
+    parent_local_scaling: Option<Vector3<f64>>,
+) -> Mat4 {
+    // Split the parent world transform into rotation and scaling (with
+    // shear, if any).
+    let parent_rotation = parent_world.rotation_part();
+    let parent_scaling = parent_rotation.transpose() * parent_world.linear_part();
+
+    let global_rotation_scaling = match inherit_type {
+        InheritType::RrSs => parent_rotation * local.rotation * parent_scaling * local.scaling,
+        InheritType::RSrs => parent_rotation * parent_scaling * local.rotation * local.scaling,
+        InheritType::Rrs => {
+            let parent_scaling_no_local = match parent_local_scaling {
+                Some(s) => parent_scaling * Mat4::scaling(reciprocal(s)),
+                None => parent_scaling,
+            };
+            parent_rotation * local.rotation * parent_scaling_no_local * local.scaling
+        }
+    };
+
+    let local_translation = local.matrix().translation_part();
+    let global_translation = parent_world.transform_point(Point3 {
+        x: local_translation.x,
+        y: local_translation.y,
+        z: local_translation.z,
+    });
+
+    Mat4::translation(Vector3 {
+        x: global_translation.x,
+        y: global_translation.y,
+        z: global_translation.z,
+    }) * global_rotation_scaling
+}
+
+/// Returns the component-wise reciprocal.
+///
+/// Zero components are left as is, to avoid producing infinity.
+fn reciprocal(v: Vector3<f64>) -> Vector3<f64> {
+    let recip = |v: f64| if v == 0.0 { 0.0 } else { 1.0 / v };
+    Vector3 {
+        x: recip(v.x),
+        y: recip(v.y),
+        z: recip(v.z),
+    }
+}
@@ -3,7 +3,10 @@
 use crate::v7400::object::ObjectHandle;
 
 pub use self::{
-    camera::CameraHandle, light::LightHandle, limbnode::LimbNodeHandle, null::NullHandle,
+    camera::{CameraHandle, CameraProperties},
+    light::{LightDistances, LightHandle, LightProperties},
+    limbnode::LimbNodeHandle,
+    null::NullHandle,
 };
 
 mod camera;
@@ -1,8 +1,82 @@
 //! `NodeAttribute` object (camera).
 
-use crate::v7400::object::nodeattribute::NodeAttributeHandle;
+use anyhow::Error;
+
+use crate::v7400::{
+    data::unit::UnitConversion,
+    object::{
+        nodeattribute::NodeAttributeHandle,
+        property::{loaders::PrimitiveLoader, ObjectProperties},
+    },
+};
 
 define_object_subtype! {
     /// `NodeAttribute` node handle (camera).
     CameraHandle: NodeAttributeHandle
 }
+
+impl<'a> CameraHandle<'a> {
+    /// Returns properties.
+    pub fn properties(&self) -> CameraProperties<'a> {
+        CameraProperties {
+            properties: self.properties_by_native_typename("FbxCamera"),
+        }
+    }
+}
+
+/// Proxy type to camera properties.
+#[derive(Debug, Clone, Copy)]
+pub struct CameraProperties<'a> {
+    /// Properties.
+    properties: ObjectProperties<'a>,
+}
+
+impl<'a> CameraProperties<'a> {
+    impl_prop_proxy_getters! {
+        /// Returns near plane distance.
+        near_plane -> f64 {
+            name = "NearPlane",
+            loader = PrimitiveLoader::<f64>::new(),
+            description = "near plane distance",
+            default: {
+                /// Returns near plane distance.
+                ///
+                /// Returns default if the value is not set.
+                near_plane_or_default = 10.0
+            }
+        }
+
+        /// Returns far plane distance.
+        far_plane -> f64 {
+            name = "FarPlane",
+            loader = PrimitiveLoader::<f64>::new(),
+            description = "far plane distance",
+            default: {
+                /// Returns far plane distance.
+                ///
+                /// Returns default if the value is not set.
+                far_plane_or_default = 4000.0
+            }
+        }
+    }
+
+    /// Returns near and far plane distances, converted by the unit
+    /// conversion if given.
+    ///
+    /// Returns defaults if the values are not set.
+    pub fn clip_planes(&self, conversion: Option<&UnitConversion>) -> Result<(f64, f64), Error> {
+        let convert = |v| conversion.map_or(v, |c| c.convert_length(v));
+        Ok((
+            convert(self.near_plane_or_default()?),
+            convert(self.far_plane_or_default()?),
+        ))
+    }
+}
+
+impl<'a> std::ops::Deref for CameraProperties<'a> {
+    type Target = ObjectProperties<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.properties
+    }
+}
@@ -1,8 +1,156 @@
 //! `NodeAttribute` object (light).
 
-use crate::v7400::object::nodeattribute::NodeAttributeHandle;
+use anyhow::Error;
+
+use crate::v7400::{
+    data::unit::UnitConversion,
+    object::{
+        nodeattribute::NodeAttributeHandle,
+        property::{loaders::PrimitiveLoader, ObjectProperties},
+    },
+};
 
 define_object_subtype! {
     /// `NodeAttribute` node handle (light).
     LightHandle: NodeAttributeHandle
 }
+
+impl<'a> LightHandle<'a> {
+    /// Returns properties.
+    pub fn properties(&self) -> LightProperties<'a> {
+        LightProperties {
+            properties: self.properties_by_native_typename("FbxLight"),
+        }
+    }
+}
+
+/// Proxy type to light properties.
+#[derive(Debug, Clone, Copy)]
+pub struct LightProperties<'a> {
+    /// Properties.
+    properties: ObjectProperties<'a>,
+}
+
+impl<'a> LightProperties<'a> {
+    impl_prop_proxy_getters! {
+        /// Returns decay start distance.
+        decay_start -> f64 {
+            name = "DecayStart",
+            loader = PrimitiveLoader::<f64>::new(),
+            description = "decay start distance",
+            default: {
+                /// Returns decay start distance.
+                ///
+                /// Returns default if the value is not set.
+                decay_start_or_default = 0.0
+            }
+        }
+
+        /// Returns near attenuation start distance.
+        near_attenuation_start -> f64 {
+            name = "NearAttenuationStart",
+            loader = PrimitiveLoader::<f64>::new(),
+            description = "near attenuation start distance",
+            default: {
+                /// Returns near attenuation start distance.
+                ///
+                /// Returns default if the value is not set.
+                near_attenuation_start_or_default = 0.0
+            }
+        }
+
+        /// Returns near attenuation end distance.
+        near_attenuation_end -> f64 {
+            name = "NearAttenuationEnd",
+            loader = PrimitiveLoader::<f64>::new(),
+            description = "near attenuation end distance",
+            default: {
+                /// Returns near attenuation end distance.
+                ///
+                /// Returns default if the value is not set.
+                near_attenuation_end_or_default = 0.0
+            }
+        }
+
+        /// Returns far attenuation start distance.
+        far_attenuation_start -> f64 {
+            name = "FarAttenuationStart",
+            loader = PrimitiveLoader::<f64>::new(),
+            description = "far attenuation start distance",
+            default: {
+                /// Returns far attenuation start distance.
+                ///
+                /// Returns default if the value is not set.
+                far_attenuation_start_or_default = 0.0
+            }
+        }
+
+        /// Returns far attenuation end distance.
+        far_attenuation_end -> f64 {
+            name = "FarAttenuationEnd",
+            loader = PrimitiveLoader::<f64>::new(),
+            description = "far attenuation end distance",
+            default: {
+                /// Returns far attenuation end distance.
+                ///
+                /// Returns default if the value is not set.
+                far_attenuation_end_or_default = 0.0
+            }
+        }
+    }
+
+    /// Returns decay and attenuation distances, converted by the unit
+    /// conversion if given.
+    ///
+    /// Defaults are used for values not set.
+    pub fn distances(&self, conversion: Option<&UnitConversion>) -> Result<LightDistances, Error> {
+        let convert = |v| conversion.map_or(v, |c| c.convert_length(v));
+        Ok(LightDistances {
+            decay_start: convert(self.decay_start_or_default()?),
+            near_attenuation: (
+                convert(self.near_attenuation_start_or_default()?),
+                convert(self.near_attenuation_end_or_default()?),
+            ),
+            far_attenuation: (
+                convert(self.far_attenuation_start_or_default()?),
+                convert(self.far_attenuation_end_or_default()?),
+            ),
+        })
+    }
+}
+
+impl<'a> std::ops::Deref for LightProperties<'a> {
+    type Target = ObjectProperties<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.properties
+    }
+}
+
+/// Decay and attenuation distances of a light.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LightDistances {
+    /// Decay start distance.
+    decay_start: f64,
+    /// Near attenuation start and end distances.
+    near_attenuation: (f64, f64),
+    /// Far attenuation start and end distances.
+    far_attenuation: (f64, f64),
+}
+
+impl LightDistances {
+    /// Returns decay start distance.
+    pub fn decay_start(&self) -> f64 {
+        self.decay_start
+    }
+
+    /// Returns near attenuation start and end distances.
+    pub fn near_attenuation(&self) -> (f64, f64) {
+        self.near_attenuation
+    }
+
+    /// Returns far attenuation start and end distances.
+    pub fn far_attenuation(&self) -> (f64, f64) {
+        self.far_attenuation
+    }
+}
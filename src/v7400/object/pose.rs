@@ -0,0 +1,129 @@
+//! `Pose` object.
+
+use std::convert::TryFrom;
+
+use anyhow::{format_err, Error};
+use fbxcel::tree::v7400::NodeHandle;
+use mint::ColumnMatrix4;
+
+use crate::v7400::{
+    data::pose::PoseType,
+    object::{model, ObjectHandle, ObjectId, TypedObjectHandle},
+    Document,
+};
+
+define_object_subtype! {
+    /// `Pose` node handle.
+    PoseHandle: ObjectHandle
+}
+
+impl<'a> PoseHandle<'a> {
+    /// Returns the pose type.
+    ///
+    /// This is read from `Type` child node, or the subclass if the node is
+    /// not available.
+    pub fn pose_type(&self) -> Result<PoseType, Error> {
+        let ty = match self.node().first_child_by_name("Type") {
+            Some(node) => node
+                .attributes()
+                .get(0)
+                .ok_or_else(|| format_err!("`Type` node has no attributes"))?
+                .get_string_or_type()
+                .map_err(|ty| format_err!("Expected string as `Type`, but got {:?}", ty))?,
+            None => self.subclass(),
+        };
+        ty.parse()
+    }
+
+    /// Returns whether the pose is a bind pose.
+    pub fn is_bind_pose(&self) -> bool {
+        matches!(self.pose_type(), Ok(PoseType::BindPose))
+    }
+
+    /// Returns the number of pose nodes.
+    pub fn node_count(&self) -> usize {
+        self.pose_nodes().count()
+    }
+
+    /// Returns an iterator of pose nodes.
+    pub fn pose_nodes(&self) -> impl Iterator<Item = PoseNodeHandle<'a>> {
+        let doc = self.document();
+        self.node()
+            .children_by_name("PoseNode")
+            .map(move |node| PoseNodeHandle { node, doc })
+    }
+
+    /// Returns the pose node for the given object.
+    pub fn pose_node(&self, object_id: ObjectId) -> Option<PoseNodeHandle<'a>> {
+        self.pose_nodes()
+            .find(|pose_node| pose_node.object_id().ok() == Some(object_id))
+    }
+
+    /// Returns the pose node for the given model.
+    pub fn pose_node_for_model(
+        &self,
+        model: &model::ModelHandle<'a>,
+    ) -> Option<PoseNodeHandle<'a>> {
+        self.pose_node(model.object_id())
+    }
+
+    /// Returns the matrix for the given object, if available.
+    pub fn matrix(&self, object_id: ObjectId) -> Result<Option<ColumnMatrix4<f64>>, Error> {
+        self.pose_node(object_id)
+            .map(|pose_node| pose_node.matrix())
+            .transpose()
+    }
+}
+
+/// `PoseNode` node handle.
+#[derive(Debug, Clone, Copy)]
+pub struct PoseNodeHandle<'a> {
+    /// `PoseNode` node.
+    node: NodeHandle<'a>,
+    /// Document.
+    doc: &'a Document,
+}
+
+impl<'a> PoseNodeHandle<'a> {
+    /// Returns the raw node handle.
+    pub fn node(&self) -> NodeHandle<'a> {
+        self.node
+    }
+
+    /// Returns the ID of the target object (`Node`).
+    pub fn object_id(&self) -> Result<ObjectId, Error> {
+        self.node
+            .first_child_by_name("Node")
+            .and_then(|node| node.attributes().get(0))
+            .ok_or_else(|| format_err!("`Node` not found for pose node"))?
+            .get_i64_or_type()
+            .map(ObjectId::new)
+            .map_err(|ty| format_err!("Expected `i64` as `Node`, but got {:?}", ty))
+    }
+
+    /// Returns the target model, if available.
+    pub fn model(&self) -> Result<Option<model::ModelHandle<'a>>, Error> {
+        let obj = match self.object_id()?.to_object_handle(self.doc) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        match obj.get_typed() {
+            TypedObjectHandle::Model(o) => Ok(Some(*o)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the matrix (`Matrix`).
+    pub fn matrix(&self) -> Result<ColumnMatrix4<f64>, Error> {
+        let values = self
+            .node
+            .first_child_by_name("Matrix")
+            .and_then(|node| node.attributes().get(0))
+            .ok_or_else(|| format_err!("`Matrix` not found for pose node"))?
+            .get_arr_f64_or_type()
+            .map_err(|ty| format_err!("Expected `[f64]` as `Matrix`, but got {:?}", ty))?;
+        <[f64; 16]>::try_from(values)
+            .map(Into::into)
+            .map_err(|_| format_err!("Expected 16 elements as `Matrix`, but got {}", values.len()))
+    }
+}
@@ -12,6 +12,7 @@ pub use self::{
     primitive::PrimitiveLoader,
     rgb::RgbLoader,
     strict_primitive::{StrictF32Loader, StrictF64Loader},
+    time::FbxTimeLoader,
 };
 
 /// Returns an object node property type error.
@@ -33,6 +34,7 @@ mod mint;
 mod primitive;
 mod rgb;
 mod strict_primitive;
+mod time;
 
 /// Returns `Ok(value_part)` if the value part has expected length.
 fn check_attrs_len<'a>(
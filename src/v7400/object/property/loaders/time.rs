@@ -0,0 +1,41 @@
+//! Time property loaders.
+
+use anyhow::{bail, Error};
+
+use crate::v7400::{
+    data::time::FbxTime,
+    object::property::{loaders::PrimitiveLoader, LoadProperty, PropertyHandle},
+};
+
+/// `KTime` property loader.
+///
+/// This checks that the data type is `KTime`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FbxTimeLoader;
+
+impl FbxTimeLoader {
+    /// Creates a new `FbxTimeLoader`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<'a> LoadProperty<'a> for FbxTimeLoader {
+    type Value = FbxTime;
+    type Error = Error;
+
+    fn expecting(&self) -> String {
+        "`i64` value as `KTime`".into()
+    }
+
+    fn load(self, node: &PropertyHandle<'a>) -> Result<Self::Value, Self::Error> {
+        if node.data_type()? != "KTime" {
+            bail!(
+                "Unexpected data type: expected \"KTime\", but got {:?}",
+                node.data_type()
+            );
+        }
+        node.load_value(PrimitiveLoader::<i64>::new())
+            .map(FbxTime::new)
+    }
+}
@@ -1,13 +1,22 @@
 //! Node types.
 
 use crate::v7400::object::{
-    deformer, geometry, material, model, nodeattribute, texture, video, ObjectHandle,
+    animationcurve, animationcurvenode, animationlayer, animationstack, deformer, geometry,
+    material, model, nodeattribute, pose, texture, video, ObjectHandle,
 };
 
 /// Typed object handle.
 #[derive(Debug, Clone, Copy)]
 #[non_exhaustive]
 pub enum TypedObjectHandle<'a> {
+    /// AnimationCurve.
+    AnimationCurve(animationcurve::AnimationCurveHandle<'a>),
+    /// AnimationCurveNode.
+    AnimationCurveNode(animationcurvenode::AnimationCurveNodeHandle<'a>),
+    /// AnimationLayer.
+    AnimationLayer(animationlayer::AnimationLayerHandle<'a>),
+    /// AnimationStack.
+    AnimationStack(animationstack::AnimationStackHandle<'a>),
     /// Deformer.
     Deformer(deformer::TypedDeformerHandle<'a>),
     /// Geometry.
@@ -18,6 +27,8 @@ pub enum TypedObjectHandle<'a> {
     Model(model::TypedModelHandle<'a>),
     /// NodeAttribute.
     NodeAttribute(nodeattribute::TypedNodeAttributeHandle<'a>),
+    /// Pose.
+    Pose(pose::PoseHandle<'a>),
     /// SubDeformer.
     SubDeformer(deformer::TypedSubDeformerHandle<'a>),
     /// Texture.
@@ -32,6 +43,18 @@ impl<'a> TypedObjectHandle<'a> {
     /// Creates a new handle from the given object handle.
     pub(crate) fn new(obj: ObjectHandle<'a>) -> Self {
         match obj.node().name() {
+            "AnimationCurve" => {
+                TypedObjectHandle::AnimationCurve(animationcurve::AnimationCurveHandle::new(obj))
+            }
+            "AnimationCurveNode" => TypedObjectHandle::AnimationCurveNode(
+                animationcurvenode::AnimationCurveNodeHandle::new(obj),
+            ),
+            "AnimationLayer" => {
+                TypedObjectHandle::AnimationLayer(animationlayer::AnimationLayerHandle::new(obj))
+            }
+            "AnimationStack" => {
+                TypedObjectHandle::AnimationStack(animationstack::AnimationStackHandle::new(obj))
+            }
             "Deformer" => match obj.class() {
                 "Deformer" => TypedObjectHandle::Deformer(deformer::TypedDeformerHandle::new(
                     deformer::DeformerHandle::new(obj),
@@ -53,6 +76,7 @@ impl<'a> TypedObjectHandle<'a> {
                     nodeattribute::NodeAttributeHandle::new(obj),
                 ))
             }
+            "Pose" => TypedObjectHandle::Pose(pose::PoseHandle::new(obj)),
             "Texture" => TypedObjectHandle::Texture(texture::TextureHandle::new(obj)),
             "Video" => {
                 TypedObjectHandle::Video(video::TypedVideoHandle::new(video::VideoHandle::new(obj)))
@@ -67,11 +91,16 @@ impl<'a> std::ops::Deref for TypedObjectHandle<'a> {
 
     fn deref(&self) -> &Self::Target {
         match self {
+            TypedObjectHandle::AnimationCurve(o) => o,
+            TypedObjectHandle::AnimationCurveNode(o) => o,
+            TypedObjectHandle::AnimationLayer(o) => o,
+            TypedObjectHandle::AnimationStack(o) => o,
             TypedObjectHandle::Deformer(o) => o,
             TypedObjectHandle::Geometry(o) => o,
             TypedObjectHandle::Material(o) => o,
             TypedObjectHandle::Model(o) => o,
             TypedObjectHandle::NodeAttribute(o) => o,
+            TypedObjectHandle::Pose(o) => o,
             TypedObjectHandle::SubDeformer(o) => o,
             TypedObjectHandle::Texture(o) => o,
             TypedObjectHandle::Video(o) => o,
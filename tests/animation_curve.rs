@@ -0,0 +1,166 @@
+//! Tests for animation curve evaluation.
+//!
+//! Expected values are calculated by hand from the cubic Bezier segment
+//! definition of FBX SDK (`KFCurve`), using the slopes and the weights stored
+//! in `KeyAttrDataFloat`, and from the clamp and velocity rules documented on
+//! `AnimationCurveData::evaluate()`.
+
+use fbxcel_dom::v7400::data::{
+    animation::{AnimationCurveData, KeyAttributes},
+    time::FbxTime,
+};
+
+const SECOND: i64 = FbxTime::TICKS_PER_SECOND;
+
+const CUBIC: i32 = 0x0000_0008;
+const TANGENT_AUTO: i32 = 0x0000_0100;
+const TANGENT_USER: i32 = 0x0000_0400;
+const TANGENT_BREAK: i32 = 0x0000_0800 | TANGENT_USER;
+const WEIGHTED_RIGHT: i32 = 0x0100_0000;
+const WEIGHTED_NEXT_LEFT: i32 = 0x0200_0000;
+const VELOCITY_RIGHT: i32 = 0x1000_0000;
+const VELOCITY_NEXT_LEFT: i32 = 0x2000_0000;
+
+/// Packs two fixed point values (in 1/10000 unit) into bits of `f32`.
+fn pack(low: i16, high: i16) -> f32 {
+    f32::from_bits(u32::from(low as u16) | (u32::from(high as u16) << 16))
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-9,
+        "actual={}, expected={}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn clamp_progressive_flag() {
+    // `eTangentGenericClampProgressive` is `0x4000 | eTangentGenericTimeIndependent`.
+    let attrs = KeyAttributes::new(CUBIC | 0x0000_4000, [0.0; 4]);
+    assert!(!attrs.is_clamped_progressive());
+
+    let attrs = KeyAttributes::new(CUBIC | 0x0000_6000, [0.0; 4]);
+    assert!(attrs.is_clamped_progressive());
+    assert!(attrs.is_time_independent());
+}
+
+#[test]
+fn stored_auto_slopes() {
+    let times = [0, SECOND, 2 * SECOND];
+    let values = [0.0, 1.0, 3.0];
+    let flags = [CUBIC | TANGENT_AUTO; 3];
+    #[rustfmt::skip]
+    let data = [
+        2.0, 0.5, 0.0, 0.0,
+        3.0, 1.0, 0.0, 0.0,
+        0.0, 0.0, 0.0, 0.0,
+    ];
+    let curve = AnimationCurveData::new(&times, &values, &flags, &data, &[1, 1, 1]).unwrap();
+
+    // Hermite basis at u=1/4: (27/32, 9/64, 5/32, -3/64).
+    // 0 * 27/32 + 2 * 9/64 + 1 * 5/32 + 0.5 * -3/64
+    let v = curve.evaluate(FbxTime::new(SECOND / 4)).unwrap();
+    assert_close(v, 0.4140625);
+
+    // Hermite basis at u=1/2: (1/2, 1/8, 1/2, -1/8).
+    // 1 * 1/2 + 3 * 1/8 + 3 * 1/2 + 1 * -1/8
+    let v = curve.evaluate(FbxTime::new(3 * SECOND / 2)).unwrap();
+    assert_close(v, 2.25);
+}
+
+#[test]
+fn broken_user_slopes() {
+    let times = [0, 2 * SECOND];
+    let values = [1.0, 1.0];
+    let flags = [CUBIC | TANGENT_BREAK];
+    // Right slope of the first key is 1, and the left slope of the second key
+    // is -1 (value per second).
+    let data = [1.0, -1.0, 0.0, 0.0];
+    let curve = AnimationCurveData::new(&times, &values, &flags, &data, &[2]).unwrap();
+
+    // Control points: (1, 1 + 1 * 2/3, 1 - (-1) * 2/3, 1), at u=1/2.
+    let v = curve.evaluate(FbxTime::new(SECOND)).unwrap();
+    assert_close(v, 1.0 + 2.0 / 3.0 * 3.0 / 4.0);
+}
+
+#[test]
+fn weighted_tangents() {
+    let times = [0, SECOND];
+    let values = [0.0, 1.0];
+    let flags = [CUBIC | TANGENT_USER | WEIGHTED_RIGHT | WEIGHTED_NEXT_LEFT];
+    // Right weight 0.5, next left weight 0.1.
+    let data = [1.0, 2.0, pack(5000, 1000), 0.0];
+    let curve = AnimationCurveData::new(&times, &values, &flags, &data, &[2]).unwrap();
+
+    // Time control points: (0, 0.5, 0.9, 1), so u=1/2 at t=0.65.
+    // Value control points: (0, 0.5, 0.8, 1), so the value is 4.9/8 at u=1/2.
+    let v = curve.evaluate(FbxTime::new(SECOND / 100 * 65)).unwrap();
+    assert_close(v, 0.6125);
+
+    // Time control points are symmetric for the weights 0.25 and 0.25,
+    // so u=1/2 at t=1/2.
+    let data = [0.0, 0.0, pack(2500, 2500), 0.0];
+    let curve = AnimationCurveData::new(&times, &values, &flags, &data, &[2]).unwrap();
+    let v = curve.evaluate(FbxTime::new(SECOND / 2)).unwrap();
+    assert_close(v, 0.5);
+}
+
+#[test]
+fn velocities_remap_segment_time() {
+    let times = [0, SECOND];
+    let values = [0.0, 1.0];
+    // Slope 1 at both keys: linear without velocities.
+    let data = [1.0, 1.0, 0.0, pack(5000, 0)];
+    let plain =
+        AnimationCurveData::new(&times, &values, &[CUBIC | TANGENT_USER], &data, &[2]).unwrap();
+    let flags = [CUBIC | TANGENT_USER | VELOCITY_RIGHT | VELOCITY_NEXT_LEFT];
+    let with_velocity = AnimationCurveData::new(&times, &values, &flags, &data, &[2]).unwrap();
+
+    assert_close(plain.evaluate(FbxTime::new(SECOND / 2)).unwrap(), 0.5);
+    // Right velocity 0.5 and next left velocity 0: the time is remapped by
+    // the Bezier curve `(0, 0.5, 2/3, 1)`, which is 0.5625 at 1/2.
+    assert_close(
+        with_velocity.evaluate(FbxTime::new(SECOND / 2)).unwrap(),
+        0.5625,
+    );
+    // Values at the keys are kept.
+    assert_close(with_velocity.evaluate(FbxTime::new(0)).unwrap(), 0.0);
+    assert_close(with_velocity.evaluate(FbxTime::new(SECOND)).unwrap(), 1.0);
+}
+
+#[test]
+fn clamped_tangents() {
+    const CLAMP: i32 = 0x0000_1000;
+    const CLAMP_PROGRESSIVE: i32 = 0x0000_6000;
+
+    let times = [0, SECOND, 2 * SECOND];
+    // The second key is a local maximum, and has the same value as the third
+    // key in the second curve.
+    #[rustfmt::skip]
+    let data = [
+        0.0, 1.0, 0.0, 0.0,
+        1.0, 0.0, 0.0, 0.0,
+        0.0, 0.0, 0.0, 0.0,
+    ];
+    let values = [0.0, 1.0, 0.0];
+    // Hermite basis at u=1/2: (1/2, 1/8, 1/2, -1/8).
+    // 0 * 1/2 + 0 * 1/8 + 1 * 1/2 + 1 * -1/8 without flattening, and
+    // 0 * 1/2 + 0 * 1/8 + 1 * 1/2 + 0 * -1/8 with flattening.
+    // The clamp flag does not flatten extrema, but the progressive clamp flag
+    // does.
+    for &(flag, expected) in &[(0, 0.375), (CLAMP, 0.375), (CLAMP_PROGRESSIVE, 0.5)] {
+        let flags = [CUBIC | TANGENT_AUTO | flag; 3];
+        let curve = AnimationCurveData::new(&times, &values, &flags, &data, &[1, 1, 1]).unwrap();
+        let v = curve.evaluate(FbxTime::new(SECOND / 2)).unwrap();
+        assert_close(v, expected);
+    }
+
+    // The clamp flag flattens the tangent if a neighbor has the same value.
+    let values = [0.0, 1.0, 1.0];
+    let flags = [CUBIC | TANGENT_AUTO | CLAMP; 3];
+    let curve = AnimationCurveData::new(&times, &values, &flags, &data, &[1, 1, 1]).unwrap();
+    let v = curve.evaluate(FbxTime::new(SECOND / 2)).unwrap();
+    assert_close(v, 0.5);
+}
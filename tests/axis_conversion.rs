@@ -0,0 +1,235 @@
+//! Tests for axis conversion applied by evaluators and builders.
+
+mod common;
+
+use fbxcel_dom::{
+    fbxcel::tree_v7400,
+    v7400::{
+        data::{
+            axis::{AxisConversion, AxisSystem, SignedAxis},
+            deformer::SkinDeformationBuilder,
+            mesh::triangulator,
+        },
+        object::{
+            model::{TransformEvaluator, TypedModelHandle},
+            TypedObjectHandle,
+        },
+        Document, Loader,
+    },
+};
+use mint::{ColumnMatrix4, Vector3};
+
+use crate::common::{assert_matrix_close, mesh, model};
+
+fn load_document() -> Document {
+    let tree = tree_v7400! {
+        Documents: {}
+        Objects: {
+            Model: [1i64, "parent\u{0}\u{1}Model", "Null"] {
+                Properties70: {
+                    P: ["Lcl Translation", "Lcl Translation", "", "A", 1.0f64, 2.0f64, 3.0f64] {}
+                    P: ["Lcl Rotation", "Lcl Rotation", "", "A", 90.0f64, 0.0f64, 0.0f64] {}
+                }
+            }
+            Model: [2i64, "child\u{0}\u{1}Model", "Mesh"] {
+                Properties70: {
+                    P: ["Lcl Translation", "Lcl Translation", "", "A", 0.0f64, 1.0f64, 0.0f64] {}
+                }
+            }
+            Geometry: [3i64, "\u{0}\u{1}Geometry", "Mesh"] {
+                Vertices: [vec![0.0f64, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0]] {}
+                PolygonVertexIndex: [vec![0i32, 1, -3]] {}
+                LayerElementNormal: [0i32] {
+                    MappingInformationType: ["ByControlPoint"] {}
+                    ReferenceInformationType: ["Direct"] {}
+                    Normals: [vec![0.0f64, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0]] {}
+                }
+            }
+        }
+        Connections: {
+            C: ["OO", 1i64, 0i64] {}
+            C: ["OO", 2i64, 1i64] {}
+            C: ["OO", 3i64, 2i64] {}
+        }
+    };
+    Loader::new()
+        .load_from_tree(tree)
+        .expect("Should never fail: the document is valid")
+}
+
+/// Loads a mesh skinned to a single joint, bound at the identity.
+fn load_skinned_document() -> Document {
+    let identity = vec![
+        1.0f64, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+    ];
+    let tree = tree_v7400! {
+        Documents: {}
+        Objects: {
+            Model: [1i64, "mesh\u{0}\u{1}Model", "Mesh"] {}
+            Geometry: [2i64, "\u{0}\u{1}Geometry", "Mesh"] {
+                Vertices: [vec![1.0f64, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]] {}
+                PolygonVertexIndex: [vec![0i32, 1, -3]] {}
+            }
+            Model: [3i64, "joint\u{0}\u{1}Model", "LimbNode"] {}
+            Deformer: [4i64, "\u{0}\u{1}Deformer", "Skin"] {}
+            Deformer: [5i64, "\u{0}\u{1}SubDeformer", "Cluster"] {
+                Mode: ["Normalize"] {}
+                Indexes: [vec![0i32, 1, 2]] {}
+                Weights: [vec![1.0f64, 1.0, 1.0]] {}
+                Transform: [identity.clone()] {}
+                TransformLink: [identity] {}
+            }
+        }
+        Connections: {
+            C: ["OO", 1i64, 0i64] {}
+            C: ["OO", 2i64, 1i64] {}
+            C: ["OO", 3i64, 0i64] {}
+            C: ["OO", 4i64, 2i64] {}
+            C: ["OO", 5i64, 4i64] {}
+            C: ["OO", 3i64, 5i64] {}
+        }
+    };
+    Loader::new()
+        .load_from_tree(tree)
+        .expect("Should never fail: the document is valid")
+}
+
+/// Z-up right-handed to Y-up right-handed.
+fn z_up_to_y_up() -> AxisConversion {
+    AxisSystem::new(
+        SignedAxis::POSITIVE_Z,
+        SignedAxis::NEGATIVE_Y,
+        SignedAxis::POSITIVE_X,
+    )
+    .unwrap()
+    .conversion_to(&AxisSystem::default())
+}
+
+/// Y-up left-handed to Y-up right-handed.
+fn mirror_x() -> AxisConversion {
+    AxisSystem::new(
+        SignedAxis::POSITIVE_Y,
+        SignedAxis::POSITIVE_Z,
+        SignedAxis::NEGATIVE_X,
+    )
+    .unwrap()
+    .conversion_to(&AxisSystem::default())
+}
+
+#[test]
+fn evaluator_converts_transforms() {
+    let doc = load_document();
+    let conversion = z_up_to_y_up();
+    let parent = model(&doc, "parent");
+    let child = model(&doc, "child");
+
+    let mut source = TransformEvaluator::new(&doc);
+    let mut converted = TransformEvaluator::new(&doc);
+    converted.set_axis_conversion(Some(conversion));
+
+    for model in &[parent, child] {
+        assert_matrix_close(
+            converted.world_transform(model).unwrap(),
+            conversion.convert_matrix(source.world_transform(model).unwrap()),
+        );
+        assert_matrix_close(
+            converted.local_transform(model).unwrap(),
+            conversion.convert_matrix(model.local_transform().unwrap()),
+        );
+    }
+
+    // The child is at (0, 1, 0) in the parent rotated by 90 degrees around
+    // X, i.e. (1, 2, 4) in Z-up, and (1, 4, -2) in Y-up.
+    let world = converted.world_transform(&child).unwrap();
+    assert!((world.w.x - 1.0).abs() < 1e-9);
+    assert!((world.w.y - 4.0).abs() < 1e-9);
+    assert!((world.w.z + 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn indexed_mesh_flips_winding() {
+    let doc = load_document();
+    let mesh = match model(&doc, "child").get_typed() {
+        TypedObjectHandle::Model(TypedModelHandle::Mesh(mesh)) => mesh.geometry().unwrap(),
+        _ => unreachable!("Should never fail: the model is a mesh"),
+    };
+    let tris = mesh
+        .polygon_vertices()
+        .unwrap()
+        .triangulate_each(triangulator::fan)
+        .unwrap();
+
+    let indexed = mesh.indexed_mesh_builder().build(&tris).unwrap();
+    assert_eq!(indexed.indices(), &[0, 1, 2]);
+
+    let conversion = mirror_x();
+    assert!(conversion.flips_winding());
+    let indexed = mesh
+        .indexed_mesh_builder()
+        .axis_conversion(Some(conversion))
+        .normals(true)
+        .build(&tris)
+        .unwrap();
+    assert_eq!(indexed.indices(), &[0, 2, 1]);
+    assert_eq!(
+        indexed.positions(),
+        &[[0.0, 0.0, 0.0], [-1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]
+    );
+    // The normal is +Z, and the converted triangle is still front facing
+    // with the converted normal.
+    for normal in indexed.normals().unwrap() {
+        assert_eq!(*normal, [0.0, 0.0, 1.0]);
+    }
+}
+
+#[test]
+fn skin_deformation_converts_normals() {
+    let doc = load_skinned_document();
+    let mesh = mesh(&doc);
+    let identity: ColumnMatrix4<f64> = [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]
+    .into();
+    // The joint is rotated by 90 degrees around Z in the source axis system.
+    let rotation: ColumnMatrix4<f64> = [
+        [0.0, 1.0, 0.0, 0.0],
+        [-1.0, 0.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]
+    .into();
+
+    let conversion = mirror_x();
+    assert!(conversion.flips_winding());
+    let deformation = SkinDeformationBuilder::new()
+        .axis_conversion(Some(conversion))
+        .build(&mesh, identity, |_| Ok(conversion.convert_matrix(rotation)))
+        .unwrap();
+
+    // The control point (1, 0, 0) is rotated to (0, 1, 0) in the source axis
+    // system, which is (0, 1, 0) in the target axis system.
+    let tris = mesh
+        .polygon_vertices()
+        .unwrap()
+        .triangulate_each(triangulator::fan)
+        .unwrap();
+    let cpi = tris
+        .control_point_index(tris.triangle_vertex_indices().next().unwrap())
+        .unwrap();
+    let point = deformation.control_point(cpi).unwrap();
+    assert!((point.x - 0.0).abs() < 1e-9);
+    assert!((point.y - 1.0).abs() < 1e-9);
+    assert!((point.z - 0.0).abs() < 1e-9);
+    // The source normal (1, 0, 0) is converted to (-1, 0, 0) before the
+    // converted rotation (-90 degrees around Z), so it is deformed to
+    // (0, 1, 0) in the same way as the control point.
+    let normal = deformation
+        .deform_normal(cpi, Vector3::from([1.0, 0.0, 0.0]))
+        .unwrap();
+    assert!((normal.x - 0.0).abs() < 1e-9);
+    assert!((normal.y - 1.0).abs() < 1e-9);
+    assert!((normal.z - 0.0).abs() < 1e-9);
+}
@@ -0,0 +1,130 @@
+//! Tests for blend shape evaluation.
+
+use fbxcel_dom::{
+    fbxcel::tree_v7400,
+    v7400::{
+        data::deformer::BlendShapeDeformation,
+        object::{
+            deformer::{BlendShapeHandle, TypedDeformerHandle},
+            TypedObjectHandle,
+        },
+        Document, Loader,
+    },
+};
+
+/// Loads a quad with a blendshape of two channels.
+///
+/// The first channel has an in-between target shape at 50 and a target shape
+/// at 100, and the second channel has a target shape at 100.
+/// Target shapes have sparse control point indices in arbitrary order.
+fn load_document() -> Document {
+    let tree = tree_v7400! {
+        Documents: {}
+        Objects: {
+            Model: [1i64, "quad\u{0}\u{1}Model", "Mesh"] {}
+            Geometry: [2i64, "\u{0}\u{1}Geometry", "Mesh"] {
+                Vertices: [vec![0.0f64, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0]] {}
+                PolygonVertexIndex: [vec![0i32, 1, 2, -4]] {}
+            }
+            Deformer: [3i64, "\u{0}\u{1}Deformer", "BlendShape"] {}
+            Deformer: [4i64, "first\u{0}\u{1}SubDeformer", "BlendShapeChannel"] {
+                FullWeights: [vec![50.0f64, 100.0]] {}
+            }
+            Deformer: [5i64, "second\u{0}\u{1}SubDeformer", "BlendShapeChannel"] {
+                FullWeights: [vec![100.0f64]] {}
+            }
+            Geometry: [6i64, "inbetween\u{0}\u{1}Geometry", "Shape"] {
+                Indexes: [vec![3i32, 1]] {}
+                Vertices: [vec![0.0f64, 0.0, 2.0, 0.0, 0.0, 1.0]] {}
+            }
+            Geometry: [7i64, "full\u{0}\u{1}Geometry", "Shape"] {
+                Indexes: [vec![2i32, 3]] {}
+                Vertices: [vec![0.0f64, 0.0, 4.0, 0.0, 0.0, 4.0]] {}
+            }
+            Geometry: [8i64, "second\u{0}\u{1}Geometry", "Shape"] {
+                Indexes: [vec![0i32]] {}
+                Vertices: [vec![1.0f64, 0.0, 0.0]] {}
+            }
+        }
+        Connections: {
+            C: ["OO", 1i64, 0i64] {}
+            C: ["OO", 2i64, 1i64] {}
+            C: ["OO", 3i64, 2i64] {}
+            C: ["OO", 4i64, 3i64] {}
+            C: ["OO", 5i64, 3i64] {}
+            C: ["OO", 6i64, 4i64] {}
+            C: ["OO", 7i64, 4i64] {}
+            C: ["OO", 8i64, 5i64] {}
+        }
+    };
+    Loader::new()
+        .load_from_tree(tree)
+        .expect("Should never fail: the document is valid")
+}
+
+fn blendshape(doc: &Document) -> BlendShapeHandle<'_> {
+    doc.objects()
+        .find_map(|obj| match obj.get_typed() {
+            TypedObjectHandle::Deformer(TypedDeformerHandle::BlendShape(o)) => Some(o),
+            _ => None,
+        })
+        .expect("Should never fail: the blendshape exists")
+}
+
+fn deformed_points(weight: f64) -> Vec<[f64; 3]> {
+    let doc = load_document();
+    let deformation =
+        BlendShapeDeformation::from_blendshape(&blendshape(&doc), &[weight, weight]).unwrap();
+    deformation
+        .control_points()
+        .iter()
+        .map(|p| [p.x, p.y, p.z])
+        .collect()
+}
+
+#[test]
+fn below_inbetween_target() {
+    // The first channel interpolates between the base geometry and the
+    // in-between target shape by 0.5, and the second channel applies its
+    // target shape by 0.25.
+    assert_eq!(
+        deformed_points(25.0),
+        vec![
+            [0.25, 0.0, 0.0],
+            [1.0, 0.0, 0.5],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 1.0],
+        ]
+    );
+}
+
+#[test]
+fn between_inbetween_and_full_targets() {
+    // The first channel interpolates between the in-between and the full
+    // target shapes by 0.5.
+    assert_eq!(
+        deformed_points(75.0),
+        vec![
+            [0.75, 0.0, 0.0],
+            [1.0, 0.0, 0.5],
+            [1.0, 1.0, 2.0],
+            [0.0, 1.0, 3.0],
+        ]
+    );
+}
+
+#[test]
+fn above_full_target() {
+    // The first channel extrapolates from the in-between and the full target
+    // shapes with the weights -1 and 2, and the second channel applies its
+    // target shape by 1.5.
+    assert_eq!(
+        deformed_points(150.0),
+        vec![
+            [1.5, 0.0, 0.0],
+            [1.0, 0.0, -1.0],
+            [1.0, 1.0, 8.0],
+            [0.0, 1.0, 6.0],
+        ]
+    );
+}
@@ -0,0 +1,48 @@
+//! Helpers shared by integration tests.
+
+// Each test crate uses only some of the helpers.
+#![allow(dead_code)]
+
+use fbxcel_dom::v7400::{
+    object::{
+        geometry::MeshHandle,
+        model::{ModelHandle, TypedModelHandle},
+        TypedObjectHandle,
+    },
+    Document,
+};
+use mint::ColumnMatrix4;
+
+/// Returns the model with the given name.
+pub fn model<'a>(doc: &'a Document, name: &str) -> ModelHandle<'a> {
+    doc.objects()
+        .find_map(|obj| match obj.get_typed() {
+            TypedObjectHandle::Model(o) if o.name() == Some(name) => Some(*o),
+            _ => None,
+        })
+        .expect("Should never fail: the model exists")
+}
+
+/// Returns the geometry of the first mesh model.
+pub fn mesh(doc: &Document) -> MeshHandle<'_> {
+    doc.objects()
+        .find_map(|obj| match obj.get_typed() {
+            TypedObjectHandle::Model(TypedModelHandle::Mesh(o)) => Some(o.geometry().unwrap()),
+            _ => None,
+        })
+        .expect("Should never fail: the mesh exists")
+}
+
+/// Asserts that the matrices are equal except for rounding errors.
+pub fn assert_matrix_close(actual: ColumnMatrix4<f64>, expected: impl Into<[[f64; 4]; 4]>) {
+    let actual: [[f64; 4]; 4] = actual.into();
+    let expected: [[f64; 4]; 4] = expected.into();
+    for (a, e) in actual.iter().flatten().zip(expected.iter().flatten()) {
+        assert!(
+            (a - e).abs() < 1e-9,
+            "actual={:?}, expected={:?}",
+            actual,
+            expected
+        );
+    }
+}
@@ -0,0 +1,150 @@
+//! Tests for edges and `ByEdge` mapping mode.
+
+mod common;
+
+use fbxcel_dom::{
+    fbxcel::tree_v7400,
+    v7400::{
+        data::mesh::{
+            layer::{color::Colors, LayerElementType, TypedLayerElementHandle},
+            triangulator,
+        },
+        object::geometry::MeshHandle,
+        Document, Loader,
+    },
+};
+
+use crate::common::mesh;
+
+/// Loads a quad split into two triangles, with a color for each edge.
+fn load_document(edges: Vec<i32>) -> Document {
+    let tree = tree_v7400! {
+        Documents: {}
+        Objects: {
+            Model: [1i64, "quad\u{0}\u{1}Model", "Mesh"] {}
+            Geometry: [2i64, "\u{0}\u{1}Geometry", "Mesh"] {
+                Vertices: [vec![0.0f64, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0]] {}
+                PolygonVertexIndex: [vec![0i32, 1, -3, 0, 2, -4]] {}
+                // The edge between the control points 0 and 2 is shared, and
+                // appears only once as the polygon vertex 2.
+                Edges: [edges] {}
+                LayerElementColor: [0i32] {
+                    MappingInformationType: ["ByEdge"] {}
+                    ReferenceInformationType: ["Direct"] {}
+                    Colors: [vec![
+                        0.0f64, 0.0, 0.0, 1.0,
+                        1.0, 0.0, 0.0, 1.0,
+                        2.0, 0.0, 0.0, 1.0,
+                        3.0, 0.0, 0.0, 1.0,
+                        4.0, 0.0, 0.0, 1.0,
+                    ]] {}
+                }
+            }
+        }
+        Connections: {
+            C: ["OO", 1i64, 0i64] {}
+            C: ["OO", 2i64, 1i64] {}
+        }
+    };
+    Loader::new()
+        .load_from_tree(tree)
+        .expect("Should never fail: the document is valid")
+}
+
+fn colors<'a>(mesh: &MeshHandle<'a>) -> Colors<'a> {
+    match mesh.layer_elements(LayerElementType::Color).next() {
+        Some(TypedLayerElementHandle::Color(handle)) => handle.color().unwrap(),
+        _ => unreachable!("Should never fail: the color layer element exists"),
+    }
+}
+
+#[test]
+fn colors_by_edge_for_polygon_vertices() {
+    let doc = load_document(vec![0, 1, 2, 4, 5]);
+    let mesh = mesh(&doc);
+    let colors = colors(&mesh);
+    let pvs = mesh.polygon_vertices().unwrap();
+    let edges = pvs.edges().unwrap();
+
+    let reds = pvs
+        .polygons()
+        .unwrap()
+        .flat_map(|polygon| {
+            polygon
+                .iter()
+                .map(|pvi| {
+                    colors
+                        .color_by_polygon_vertex(&pvs, Some(&edges), &polygon, pvi)
+                        .unwrap()[0]
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(reds, vec![0.0, 1.0, 2.0, 2.0, 3.0, 4.0]);
+
+    // Edges are required for `ByEdge` mapping mode.
+    let polygon = pvs.polygons().unwrap().next().unwrap();
+    assert!(colors
+        .color_by_polygon_vertex(&pvs, None, &polygon, polygon.start())
+        .is_err());
+}
+
+#[test]
+fn polygon_vertex_out_of_polygon_is_rejected() {
+    let doc = load_document(vec![0, 1, 2, 4, 5]);
+    let mesh = mesh(&doc);
+    let colors = colors(&mesh);
+    let pvs = mesh.polygon_vertices().unwrap();
+    let edges = pvs.edges().unwrap();
+
+    // The polygon vertex 3 is the first vertex of the second polygon.
+    let polygon = pvs.polygons().unwrap().next().unwrap();
+    let err = colors
+        .color_by_polygon_vertex(&pvs, Some(&edges), &polygon, polygon.end())
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("not in the polygon"),
+        "err={}",
+        err
+    );
+}
+
+#[test]
+fn colors_by_edge_for_triangle_vertices() {
+    let doc = load_document(vec![0, 1, 2, 4, 5]);
+    let mesh = mesh(&doc);
+    let colors = colors(&mesh);
+    let tris = mesh
+        .polygon_vertices()
+        .unwrap()
+        .triangulate_each(triangulator::fan)
+        .unwrap();
+
+    let reds = tris
+        .triangle_vertex_indices()
+        .map(|tri_vi| colors.color(&tris, tri_vi).unwrap()[0])
+        .collect::<Vec<_>>();
+    assert_eq!(reds, vec![0.0, 1.0, 2.0, 2.0, 3.0, 4.0]);
+}
+
+#[test]
+fn invalid_edges_are_reported_on_use() {
+    // The polygon vertex index 9 is out of range.
+    let doc = load_document(vec![0, 1, 2, 4, 9]);
+    let mesh = mesh(&doc);
+    let colors = colors(&mesh);
+    // Edges are not required for triangulation.
+    let tris = mesh
+        .polygon_vertices()
+        .unwrap()
+        .triangulate_each(triangulator::fan)
+        .unwrap();
+
+    let tri_vi = tris.triangle_vertex_indices().next().unwrap();
+    let err = colors.color(&tris, tri_vi).unwrap_err();
+    assert!(
+        err.to_string().contains("out of range in `Edges`"),
+        "err={}",
+        err
+    );
+}
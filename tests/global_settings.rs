@@ -0,0 +1,51 @@
+//! Tests for global settings.
+
+use fbxcel_dom::{fbxcel::tree_v7400, v7400::Loader};
+
+/// Returns the frame rate of a document with the given global settings
+/// properties.
+fn frame_rate(time_mode: i32, custom_frame_rate: Option<f64>) -> anyhow::Result<f64> {
+    let tree = match custom_frame_rate {
+        Some(rate) => tree_v7400! {
+            GlobalSettings: {
+                Properties70: {
+                    P: ["TimeMode", "enum", "", "", time_mode] {}
+                    P: ["CustomFrameRate", "double", "Number", "", rate] {}
+                }
+            }
+            Documents: {}
+            Objects: {}
+            Connections: {}
+        },
+        None => tree_v7400! {
+            GlobalSettings: {
+                Properties70: {
+                    P: ["TimeMode", "enum", "", "", time_mode] {}
+                }
+            }
+            Documents: {}
+            Objects: {}
+            Connections: {}
+        },
+    };
+    let doc = Loader::new().load_from_tree(tree).unwrap();
+    doc.global_settings()
+        .expect("Should never fail: the global settings exist")
+        .frame_rate()
+}
+
+#[test]
+fn custom_frame_rate() {
+    // `TimeMode` 14 is `Custom`.
+    assert_eq!(frame_rate(14, Some(12.5)).unwrap(), 12.5);
+    // `CustomFrameRate` is ignored for other time modes.
+    assert_eq!(frame_rate(11, Some(12.5)).unwrap(), 24.0);
+    assert_eq!(frame_rate(11, None).unwrap(), 24.0);
+}
+
+#[test]
+fn custom_frame_rate_should_be_positive() {
+    assert!(frame_rate(14, None).is_err());
+    assert!(frame_rate(14, Some(-1.0)).is_err());
+    assert!(frame_rate(14, Some(0.0)).is_err());
+}
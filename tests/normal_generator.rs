@@ -0,0 +1,43 @@
+//! Tests for smooth normal generation.
+
+mod common;
+
+use fbxcel_dom::{fbxcel::tree_v7400, v7400::Loader};
+
+use crate::common::mesh;
+
+#[test]
+fn unsupported_smoothing_is_ignored() {
+    let tree = tree_v7400! {
+        Documents: {}
+        Objects: {
+            Model: [1i64, "quad\u{0}\u{1}Model", "Mesh"] {}
+            Geometry: [2i64, "\u{0}\u{1}Geometry", "Mesh"] {
+                Vertices: [vec![0.0f64, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0]] {}
+                PolygonVertexIndex: [vec![0i32, 1, -3, 0, 2, -4]] {}
+                LayerElementSmoothing: [0i32] {
+                    MappingInformationType: ["ByPolygonVertex"] {}
+                    ReferenceInformationType: ["Direct"] {}
+                    Smoothing: [vec![0i32, 0, 0, 1, 1, 1]] {}
+                }
+            }
+        }
+        Connections: {
+            C: ["OO", 1i64, 0i64] {}
+            C: ["OO", 2i64, 1i64] {}
+        }
+    };
+    let doc = Loader::new().load_from_tree(tree).unwrap();
+    let mesh = mesh(&doc);
+
+    // Smoothing with `ByPolygonVertex` mapping mode is not supported, so the
+    // crease angle is used.
+    let normals = mesh.normal_generator().unwrap().generate().unwrap();
+    let pvs = mesh.polygon_vertices().unwrap();
+    for polygon in pvs.polygons().unwrap() {
+        for pvi in polygon.iter() {
+            let normal = normals.normal(pvi).unwrap();
+            assert_eq!((normal.x, normal.y, normal.z), (0.0, 0.0, 1.0));
+        }
+    }
+}
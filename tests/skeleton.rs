@@ -0,0 +1,87 @@
+//! Tests for skeleton extraction.
+
+mod common;
+
+use fbxcel_dom::{
+    fbxcel::tree_v7400,
+    v7400::{data::skeleton::SkeletonBuilder, Loader},
+};
+
+use crate::common::{assert_matrix_close, model};
+
+#[test]
+fn local_transform_relative_to_parent_joint() {
+    let tree = tree_v7400! {
+        Documents: {}
+        Objects: {
+            Model: [1i64, "root\u{0}\u{1}Model", "LimbNode"] {
+                Properties70: {
+                    P: ["Lcl Translation", "Lcl Translation", "", "A", 1.0f64, 0.0f64, 0.0f64] {}
+                }
+            }
+            Model: [2i64, "offset\u{0}\u{1}Model", "Mesh"] {
+                Properties70: {
+                    P: ["Lcl Translation", "Lcl Translation", "", "A", 0.0f64, 2.0f64, 0.0f64] {}
+                    P: ["Lcl Rotation", "Lcl Rotation", "", "A", 0.0f64, 0.0f64, 90.0f64] {}
+                }
+            }
+            Model: [3i64, "joint\u{0}\u{1}Model", "LimbNode"] {
+                Properties70: {
+                    P: ["Lcl Translation", "Lcl Translation", "", "A", 3.0f64, 0.0f64, 0.0f64] {}
+                }
+            }
+        }
+        Connections: {
+            C: ["OO", 1i64, 0i64] {}
+            C: ["OO", 2i64, 1i64] {}
+            C: ["OO", 3i64, 2i64] {}
+        }
+    };
+    let doc = Loader::new().load_from_tree(tree).unwrap();
+    let root = model(&doc, "root");
+    let joint = model(&doc, "joint");
+
+    let skeleton = SkeletonBuilder::new(&doc)
+        .add_root(&root)
+        .unwrap()
+        .add_root(&joint)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(skeleton.len(), 2);
+    assert_eq!(skeleton.parent_indices(), vec![None, Some(0)]);
+
+    // The root joint has no parent joint, so the local transform is the world
+    // transform.
+    assert_matrix_close(
+        skeleton.joints()[0].local_transform(),
+        [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [1.0, 0.0, 0.0, 1.0],
+        ],
+    );
+    // The non-joint `offset` model is folded into the local transform:
+    // `T(0, 2, 0) * Rz(90) * T(3, 0, 0)`.
+    assert_matrix_close(
+        skeleton.joints()[1].local_transform(),
+        [
+            [0.0, 1.0, 0.0, 0.0],
+            [-1.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 5.0, 0.0, 1.0],
+        ],
+    );
+    // No clusters and no bind poses, so the bind matrix is the world
+    // transform at rest.
+    assert_matrix_close(
+        skeleton.joints()[1].bind_matrix(),
+        [
+            [0.0, 1.0, 0.0, 0.0],
+            [-1.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [1.0, 5.0, 0.0, 1.0],
+        ],
+    );
+}
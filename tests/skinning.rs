@@ -0,0 +1,278 @@
+//! Tests for CPU skinning.
+
+mod common;
+
+use fbxcel_dom::{
+    fbxcel::tree_v7400,
+    v7400::{
+        data::{
+            deformer::SkinDeformation,
+            mesh::{
+                layer::{LayerElementType, TypedLayerElementHandle},
+                triangulator,
+            },
+        },
+        Document, Loader,
+    },
+};
+use mint::{ColumnMatrix4, Point3, Vector3};
+
+use crate::common::mesh;
+
+/// Returns the column-major translation matrix.
+fn translation(x: f64, y: f64, z: f64) -> Vec<f64> {
+    vec![
+        1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, x, y, z, 1.0,
+    ]
+}
+
+/// Loads a mesh with a geometric translation, skinned to a single joint.
+fn load_document(link_mode: &str) -> Document {
+    let tree = tree_v7400! {
+        Documents: {}
+        Objects: {
+            Model: [1i64, "mesh\u{0}\u{1}Model", "Mesh"] {
+                Properties70: {
+                    P: ["Lcl Translation", "Lcl Translation", "", "A", 0.0f64, 0.0f64, 5.0f64] {}
+                    P: ["GeometricTranslation", "Vector3D", "Vector", "", 1.0f64, 0.0f64, 0.0f64] {}
+                }
+            }
+            Geometry: [2i64, "\u{0}\u{1}Geometry", "Mesh"] {
+                Vertices: [vec![0.0f64, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0]] {}
+                PolygonVertexIndex: [vec![0i32, 1, -3]] {}
+            }
+            Model: [3i64, "joint\u{0}\u{1}Model", "LimbNode"] {
+                Properties70: {
+                    P: ["Lcl Translation", "Lcl Translation", "", "A", 0.0f64, 2.0f64, 0.0f64] {}
+                }
+            }
+            Deformer: [4i64, "\u{0}\u{1}Deformer", "Skin"] {}
+            Deformer: [5i64, "\u{0}\u{1}SubDeformer", "Cluster"] {
+                Mode: [link_mode] {}
+                Indexes: [vec![0i32, 1, 2]] {}
+                Weights: [vec![1.0f64, 1.0, 1.0]] {}
+                // World transforms of the mesh model and the joint at
+                // binding time.
+                Transform: [translation(0.0, 0.0, 5.0)] {}
+                TransformLink: [translation(0.0, 2.0, 0.0)] {}
+            }
+        }
+        Connections: {
+            C: ["OO", 1i64, 0i64] {}
+            C: ["OO", 2i64, 1i64] {}
+            C: ["OO", 3i64, 0i64] {}
+            C: ["OO", 4i64, 2i64] {}
+            C: ["OO", 5i64, 4i64] {}
+            C: ["OO", 3i64, 5i64] {}
+        }
+    };
+    Loader::new()
+        .load_from_tree(tree)
+        .expect("Should never fail: the document is valid")
+}
+
+/// Loads a mesh skinned to two joints at the origin.
+///
+/// The control point 0 is influenced by both joints equally, the control point
+/// 1 only by `joint0`, and the control point 2 only by `joint1`.
+/// The skin has the blend weight 0.5 for the control point 0.
+fn load_two_joint_document(skinning_type: &str) -> Document {
+    let identity = translation(0.0, 0.0, 0.0);
+    let tree = tree_v7400! {
+        Documents: {}
+        Objects: {
+            Model: [1i64, "mesh\u{0}\u{1}Model", "Mesh"] {}
+            Geometry: [2i64, "\u{0}\u{1}Geometry", "Mesh"] {
+                Vertices: [vec![0.0f64, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 1.0]] {}
+                PolygonVertexIndex: [vec![0i32, 1, -3]] {}
+                LayerElementNormal: [0i32] {
+                    MappingInformationType: ["ByControlPoint"] {}
+                    ReferenceInformationType: ["Direct"] {}
+                    Normals: [vec![0.0f64, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 0.0]] {}
+                }
+            }
+            Model: [3i64, "joint0\u{0}\u{1}Model", "LimbNode"] {}
+            Model: [4i64, "joint1\u{0}\u{1}Model", "LimbNode"] {}
+            Deformer: [5i64, "\u{0}\u{1}Deformer", "Skin"] {
+                SkinningType: [skinning_type] {}
+                BlendIndexes: [vec![0i32]] {}
+                BlendWeights: [vec![0.5f64]] {}
+            }
+            Deformer: [6i64, "\u{0}\u{1}SubDeformer", "Cluster"] {
+                Mode: ["Normalize"] {}
+                Indexes: [vec![0i32, 1]] {}
+                Weights: [vec![0.5f64, 1.0]] {}
+                Transform: [identity.clone()] {}
+                TransformLink: [identity.clone()] {}
+            }
+            Deformer: [7i64, "\u{0}\u{1}SubDeformer", "Cluster"] {
+                Mode: ["Normalize"] {}
+                Indexes: [vec![0i32, 2]] {}
+                Weights: [vec![0.5f64, 1.0]] {}
+                Transform: [identity.clone()] {}
+                TransformLink: [identity] {}
+            }
+        }
+        Connections: {
+            C: ["OO", 1i64, 0i64] {}
+            C: ["OO", 2i64, 1i64] {}
+            C: ["OO", 3i64, 0i64] {}
+            C: ["OO", 4i64, 0i64] {}
+            C: ["OO", 5i64, 2i64] {}
+            C: ["OO", 6i64, 5i64] {}
+            C: ["OO", 7i64, 5i64] {}
+            C: ["OO", 3i64, 6i64] {}
+            C: ["OO", 4i64, 7i64] {}
+        }
+    };
+    Loader::new()
+        .load_from_tree(tree)
+        .expect("Should never fail: the document is valid")
+}
+
+fn translation_matrix(x: f64, y: f64, z: f64) -> ColumnMatrix4<f64> {
+    let mut m = [0.0; 16];
+    m.copy_from_slice(&translation(x, y, z));
+    m.into()
+}
+
+#[test]
+fn bind_pose_keeps_control_points() {
+    let doc = load_document("TotalOne");
+    let mesh = mesh(&doc);
+    let mesh_world = mesh.models().next().unwrap().world_transform().unwrap();
+
+    let deformation =
+        SkinDeformation::from_mesh(&mesh, mesh_world, |joint| joint.world_transform()).unwrap();
+    assert_eq!(
+        deformation.control_points(),
+        &[
+            Point3::from([0.0, 0.0, 0.0]),
+            Point3::from([1.0, 0.0, 0.0]),
+            Point3::from([0.0, 1.0, 0.0]),
+        ]
+    );
+}
+
+#[test]
+fn moved_joint() {
+    let doc = load_document("TotalOne");
+    let mesh = mesh(&doc);
+    let mesh_world = mesh.models().next().unwrap().world_transform().unwrap();
+
+    // The joint is moved by (0, 1, 0) from the bind pose.
+    let deformation =
+        SkinDeformation::from_mesh(&mesh, mesh_world, |_| Ok(translation_matrix(0.0, 3.0, 0.0)))
+            .unwrap();
+    assert_eq!(
+        deformation.control_points(),
+        &[
+            Point3::from([0.0, 1.0, 0.0]),
+            Point3::from([1.0, 1.0, 0.0]),
+            Point3::from([0.0, 2.0, 0.0]),
+        ]
+    );
+}
+
+#[test]
+fn additive_link_mode_is_rejected() {
+    let doc = load_document("Additive");
+    let mesh = mesh(&doc);
+    let mesh_world = mesh.models().next().unwrap().world_transform().unwrap();
+    assert!(
+        SkinDeformation::from_mesh(&mesh, mesh_world, |joint| joint.world_transform()).is_err()
+    );
+}
+
+/// Deforms the two joint mesh with `joint1` rotated by 90 degrees around X.
+fn deform_two_joint_mesh(skinning_type: &str) -> (SkinDeformation, Vec<Vector3<f64>>) {
+    let doc = load_two_joint_document(skinning_type);
+    let mesh = mesh(&doc);
+    let identity = translation_matrix(0.0, 0.0, 0.0);
+    let rotation: ColumnMatrix4<f64> = [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, -1.0, 0.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]
+    .into();
+    let deformation = SkinDeformation::from_mesh(&mesh, identity, |joint| {
+        Ok(match joint.name() {
+            Some("joint1") => rotation,
+            _ => identity,
+        })
+    })
+    .unwrap();
+
+    let tris = mesh
+        .polygon_vertices()
+        .unwrap()
+        .triangulate_each(triangulator::fan)
+        .unwrap();
+    let normals = match mesh.layer_elements(LayerElementType::Normal).next() {
+        Some(TypedLayerElementHandle::Normal(v)) => v.normals().unwrap(),
+        _ => unreachable!("Should never fail: the normal layer element exists"),
+    };
+    let normals = deformation.deform_normals(&tris, &normals).unwrap();
+    (deformation, normals)
+}
+
+fn assert_vector_close(actual: [f64; 3], expected: [f64; 3]) {
+    for (a, e) in actual.iter().zip(&expected) {
+        assert!(
+            (a - e).abs() < 1e-9,
+            "actual={:?}, expected={:?}",
+            actual,
+            expected
+        );
+    }
+}
+
+#[test]
+fn linear_blend_loses_volume() {
+    let (deformation, normals) = deform_two_joint_mesh("Linear");
+    let points = deformation.control_points();
+
+    // The average of (0, 1, 0) and (0, 0, 1) is shrunk to the length of
+    // `sqrt(1/2)`.
+    assert_vector_close(points[0].into(), [0.0, 0.5, 0.5]);
+    assert_vector_close(points[1].into(), [1.0, 0.0, 0.0]);
+    assert_vector_close(points[2].into(), [0.0, -1.0, 1.0]);
+
+    // Normals are normalized after deformation.
+    let half = 0.5f64.sqrt();
+    assert_vector_close(normals[0].into(), [0.0, half, half]);
+    assert_vector_close(normals[1].into(), [0.0, 0.0, 1.0]);
+    assert_vector_close(normals[2].into(), [0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn dual_quaternion_keeps_volume() {
+    let (deformation, normals) = deform_two_joint_mesh("DualQuaternion");
+    let points = deformation.control_points();
+
+    // Rotated by 45 degrees around X, keeping the length.
+    let half = 0.5f64.sqrt();
+    assert_vector_close(points[0].into(), [0.0, half, half]);
+    assert_vector_close(points[1].into(), [1.0, 0.0, 0.0]);
+    assert_vector_close(points[2].into(), [0.0, -1.0, 1.0]);
+
+    assert_vector_close(normals[0].into(), [0.0, half, half]);
+    assert_vector_close(normals[1].into(), [0.0, 0.0, 1.0]);
+    assert_vector_close(normals[2].into(), [0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn blend_weights_interpolate_linear_and_dual_quaternion() {
+    let (deformation, _) = deform_two_joint_mesh("Blend");
+    let points = deformation.control_points();
+
+    // The blend weight 0.5 is the average of the linear blend and the dual
+    // quaternion results.
+    let half = 0.5f64.sqrt();
+    let expected = 0.5 * 0.5 + 0.5 * half;
+    assert_vector_close(points[0].into(), [0.0, expected, expected]);
+    // Control points without blend weights use the linear blend.
+    assert_vector_close(points[1].into(), [1.0, 0.0, 0.0]);
+    assert_vector_close(points[2].into(), [0.0, -1.0, 1.0]);
+}
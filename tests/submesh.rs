@@ -0,0 +1,92 @@
+//! Tests for per-material submeshes.
+
+use fbxcel_dom::{
+    fbxcel::tree_v7400,
+    v7400::{
+        data::mesh::triangulator,
+        object::{model::TypedModelHandle, TypedObjectHandle},
+        Loader,
+    },
+};
+
+#[test]
+fn polygons_and_triangles_by_material() {
+    let tree = tree_v7400! {
+        Documents: {}
+        Objects: {
+            Model: [1i64, "mesh\u{0}\u{1}Model", "Mesh"] {}
+            Geometry: [2i64, "\u{0}\u{1}Geometry", "Mesh"] {
+                Vertices: [vec![0.0f64, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0]] {}
+                // A triangle, a quad, and a triangle.
+                PolygonVertexIndex: [vec![0i32, 1, -3, 0, 1, 2, -4, 0, 2, -4]] {}
+                LayerElementMaterial: [0i32] {
+                    MappingInformationType: ["ByPolygon"] {}
+                    ReferenceInformationType: ["IndexToDirect"] {}
+                    Materials: [vec![1i32, 0, 1]] {}
+                }
+            }
+            Material: [3i64, "first\u{0}\u{1}Material", ""] {}
+            Material: [4i64, "second\u{0}\u{1}Material", ""] {}
+        }
+        Connections: {
+            C: ["OO", 1i64, 0i64] {}
+            C: ["OO", 2i64, 1i64] {}
+            // Material indices refer to the connection order, not the object
+            // order.
+            C: ["OO", 4i64, 1i64] {}
+            C: ["OO", 3i64, 1i64] {}
+        }
+    };
+    let doc = Loader::new().load_from_tree(tree).unwrap();
+    let mesh = doc
+        .objects()
+        .find_map(|obj| match obj.get_typed() {
+            TypedObjectHandle::Model(TypedModelHandle::Mesh(o)) => Some(o),
+            _ => None,
+        })
+        .expect("Should never fail: the mesh exists");
+
+    let submeshes = mesh.polygon_submeshes().unwrap();
+    let summary = submeshes
+        .iter()
+        .map(|submesh| {
+            (
+                submesh.material_index().to_u32(),
+                submesh.material().and_then(|material| material.name()),
+                submesh
+                    .polygons()
+                    .iter()
+                    .map(|poly_i| poly_i.to_usize())
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![(0, Some("second"), vec![1]), (1, Some("first"), vec![0, 2])]
+    );
+
+    // The quad is split into the triangles 1 and 2.
+    let tris = mesh
+        .geometry()
+        .unwrap()
+        .polygon_vertices()
+        .unwrap()
+        .triangulate_each(triangulator::fan)
+        .unwrap();
+    let submeshes = mesh.submeshes(&tris).unwrap();
+    let summary = submeshes
+        .iter()
+        .map(|submesh| {
+            (
+                submesh.material_index().to_u32(),
+                submesh
+                    .triangles()
+                    .iter()
+                    .map(|tri_i| tri_i.to_usize())
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(summary, vec![(0, vec![1, 2]), (1, vec![0, 3])]);
+}
@@ -0,0 +1,236 @@
+//! Tests for MikkTSpace-compatible tangent generation.
+//!
+//! Expected values of the mirrored quad are calculated by hand following the
+//! reference C implementation of MikkTSpace (`mikktspace.c`): the
+//! per-triangle tangent `t31.y * d1 - t21.y * d2` flipped for orientation
+//! reversing UVs, the angle-weighted average in each vertex group, and copying
+//! tangent spaces to degenerate triangles from the same vertex of other
+//! triangles.
+//!
+//! Expected values of the curved strip are the output of a port of the
+//! reference implementation for the same triangles.
+
+mod common;
+
+use fbxcel_dom::{
+    fbxcel::tree_v7400,
+    v7400::{
+        data::mesh::{
+            layer::{LayerElementType, TypedLayerElementHandle},
+            triangulator, GeneratedTangents,
+        },
+        Document, Loader,
+    },
+};
+
+use crate::common::mesh;
+
+#[test]
+fn mirrored_quad_with_degenerate_triangle() {
+    let tree = tree_v7400! {
+        Documents: {}
+        Objects: {
+            Model: [1i64, "quad\u{0}\u{1}Model", "Mesh"] {}
+            Geometry: [2i64, "\u{0}\u{1}Geometry", "Mesh"] {
+                Vertices: [vec![0.0f64, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0]] {}
+                // A quad, and a degenerate triangle at the control points 0
+                // and 1.
+                PolygonVertexIndex: [vec![0i32, 1, 2, -4, 0, 1, -2]] {}
+                LayerElementNormal: [0i32] {
+                    MappingInformationType: ["ByControlPoint"] {}
+                    ReferenceInformationType: ["Direct"] {}
+                    Normals: [vec![0.0f64, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0]] {}
+                }
+                // Mirrored in U: `(u, v) = (1 - x, y)`.
+                LayerElementUV: [0i32] {
+                    MappingInformationType: ["ByControlPoint"] {}
+                    ReferenceInformationType: ["Direct"] {}
+                    UV: [vec![1.0f64, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0]] {}
+                }
+            }
+        }
+        Connections: {
+            C: ["OO", 1i64, 0i64] {}
+            C: ["OO", 2i64, 1i64] {}
+        }
+    };
+    let doc = Loader::new().load_from_tree(tree).unwrap();
+    let mesh = mesh(&doc);
+    let normals = match mesh.layer_elements(LayerElementType::Normal).next() {
+        Some(TypedLayerElementHandle::Normal(v)) => v.normals().unwrap(),
+        _ => unreachable!("Should never fail: the normal layer element exists"),
+    };
+    let uv = match mesh.layer_elements(LayerElementType::Uv).next() {
+        Some(TypedLayerElementHandle::Uv(v)) => v.uv().unwrap(),
+        _ => unreachable!("Should never fail: the UV layer element exists"),
+    };
+    let tris = mesh
+        .polygon_vertices()
+        .unwrap()
+        .triangulate_each(triangulator::fan)
+        .unwrap();
+    assert_eq!(tris.len(), 9);
+
+    let generated = GeneratedTangents::from_triangle_vertices(&tris, &normals, &uv).unwrap();
+
+    // Both triangles of the quad have the tangent `(1, 0, 0)` and the signed
+    // UV area `-1`, so the tangent is flipped to `(-1, 0, 0)` (direction of
+    // increasing U) and the sign is `-1`.
+    // The binormal is `-1 * cross((0, 0, 1), (-1, 0, 0)) = (0, 1, 0)`
+    // (direction of increasing V).
+    // The degenerate triangle copies them from the quad.
+    let binormals = generated.binormals();
+    for tri_vi in tris.triangle_vertex_indices() {
+        let pvi = tris.polygon_vertex_index(tri_vi).unwrap();
+        let tangent = generated.tangent(pvi).unwrap();
+        assert_eq!(
+            (tangent.x, tangent.y, tangent.z, tangent.w),
+            (-1.0, 0.0, 0.0, -1.0),
+            "pvi={:?}",
+            pvi
+        );
+        let binormal = binormals.binormal(&tris, tri_vi).unwrap();
+        assert_eq!(
+            (binormal.x, binormal.y, binormal.z),
+            (0.0, 1.0, 0.0),
+            "pvi={:?}",
+            pvi
+        );
+    }
+}
+
+/// Number of columns of control points of the curved strip.
+const COLUMNS: usize = 5;
+
+/// Loads a strip of four quads on a cylinder of radius 1 around the Y axis.
+///
+/// The first two quads and the last two quads are separate UV islands, with a
+/// seam at the middle column. The second island is mirrored in U, and V is
+/// scaled per column so that tangents are not orthogonal to binormals.
+fn load_curved_strip() -> Document {
+    let angle = |col: usize| (col as f64 - 2.0) * 30f64.to_radians();
+    let vertices = (0..2)
+        .flat_map(|row| (0..COLUMNS).map(move |col| (col, row)))
+        .flat_map(|(col, row)| vec![angle(col).sin(), row as f64, angle(col).cos()])
+        .collect::<Vec<_>>();
+    let normals = (0..2)
+        .flat_map(|_| 0..COLUMNS)
+        .flat_map(|col| vec![angle(col).sin(), 0.0, angle(col).cos()])
+        .collect::<Vec<_>>();
+    let quads = (0..4)
+        .map(|k| [k, k + 1, COLUMNS + k + 1, COLUMNS + k])
+        .collect::<Vec<_>>();
+    let polygon_vertex_index = quads
+        .iter()
+        .flat_map(|quad| {
+            vec![
+                quad[0] as i32,
+                quad[1] as i32,
+                quad[2] as i32,
+                !(quad[3] as i32),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let uv = quads
+        .iter()
+        .enumerate()
+        .flat_map(|(quad_i, quad)| quad.iter().map(move |&cpi| (quad_i, cpi)))
+        .flat_map(|(quad_i, cpi)| {
+            let (col, row) = ((cpi % COLUMNS) as f64, (cpi / COLUMNS) as f64);
+            let u = if quad_i < 2 {
+                col / 2.0
+            } else {
+                1.0 + (4.0 - col) / 2.0
+            };
+            vec![u, row * (1.0 + 0.1 * col)]
+        })
+        .collect::<Vec<_>>();
+
+    let tree = tree_v7400! {
+        Documents: {}
+        Objects: {
+            Model: [1i64, "strip\u{0}\u{1}Model", "Mesh"] {}
+            Geometry: [2i64, "\u{0}\u{1}Geometry", "Mesh"] {
+                Vertices: [vertices] {}
+                PolygonVertexIndex: [polygon_vertex_index] {}
+                LayerElementNormal: [0i32] {
+                    MappingInformationType: ["ByControlPoint"] {}
+                    ReferenceInformationType: ["Direct"] {}
+                    Normals: [normals] {}
+                }
+                LayerElementUV: [0i32] {
+                    MappingInformationType: ["ByPolygonVertex"] {}
+                    ReferenceInformationType: ["Direct"] {}
+                    UV: [uv] {}
+                }
+            }
+        }
+        Connections: {
+            C: ["OO", 1i64, 0i64] {}
+            C: ["OO", 2i64, 1i64] {}
+        }
+    };
+    Loader::new()
+        .load_from_tree(tree)
+        .expect("Should never fail: the document is valid")
+}
+
+/// Tangents generated by the reference implementation for each polygon
+/// vertex of the curved strip.
+///
+/// These are the output of `bevy_mikktspace` 0.15.3 (a port of
+/// `mikktspace.c`), given the triangles `(0, 1, 2)` and `(0, 2, 3)` of each
+/// quad.
+#[rustfmt::skip]
+const CURVED_STRIP_TANGENTS: [[f64; 4]; 16] = [
+    [0.49915472, -0.05812236, 0.8645613, 1.0],
+    [0.86572236, -0.0264543, 0.49982503, 1.0],
+    [0.85502064, -0.15891163, 0.49364638, 1.0],
+    [0.4902903, -0.19611616, 0.84920776, 1.0],
+    [0.86572236, -0.0264543, 0.49982503, 1.0],
+    [1.0, 0.0, 0.0, 1.0],
+    [0.99196535, -0.12650959, 0.0, 1.0],
+    [0.85502064, -0.15891163, 0.49364638, 1.0],
+    [-0.9988153, 0.04866309, 0.0, -1.0],
+    [-0.8658067, 0.022474058, 0.49987373, -1.0],
+    [-0.85819423, 0.13417755, 0.49547863, -1.0],
+    [-0.9863939, 0.16439882, 0.0, -1.0],
+    [-0.8658067, 0.022474058, 0.49987373, -1.0],
+    [-0.5, 0.0, 0.8660254, -1.0],
+    [-0.49710605, 0.10743514, 0.8610129, -1.0],
+    [-0.85819423, 0.13417755, 0.49547863, -1.0],
+];
+
+#[test]
+fn curved_strip_with_seam_and_mirrored_island() {
+    let doc = load_curved_strip();
+    let mesh = mesh(&doc);
+    let normals = match mesh.layer_elements(LayerElementType::Normal).next() {
+        Some(TypedLayerElementHandle::Normal(v)) => v.normals().unwrap(),
+        _ => unreachable!("Should never fail: the normal layer element exists"),
+    };
+    let uv = match mesh.layer_elements(LayerElementType::Uv).next() {
+        Some(TypedLayerElementHandle::Uv(v)) => v.uv().unwrap(),
+        _ => unreachable!("Should never fail: the UV layer element exists"),
+    };
+    let pvs = mesh.polygon_vertices().unwrap();
+    let tris = pvs.triangulate_each(triangulator::fan).unwrap();
+    assert_eq!(tris.len(), 24);
+
+    let generated = GeneratedTangents::from_triangle_vertices(&tris, &normals, &uv).unwrap();
+    let pvis = pvs.polygons().unwrap().flat_map(|polygon| polygon.iter());
+    for (pvi, expected) in pvis.zip(&CURVED_STRIP_TANGENTS) {
+        let tangent = generated.tangent(pvi).unwrap();
+        let actual = [tangent.x, tangent.y, tangent.z, tangent.w];
+        for (a, e) in actual.iter().zip(expected) {
+            // The reference implementation calculates in `f32`.
+            assert!(
+                (a - e).abs() < 1e-5,
+                "pvi={:?}, actual={:?}, expected={:?}",
+                pvi,
+                actual,
+                expected
+            );
+        }
+    }
+}
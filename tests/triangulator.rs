@@ -0,0 +1,181 @@
+//! Tests for triangulators.
+
+mod common;
+
+use fbxcel_dom::{
+    fbxcel::tree_v7400,
+    v7400::{data::mesh::triangulator, Loader},
+};
+
+use crate::common::mesh;
+
+/// Polygons to triangulate, as lists of points.
+fn polygons() -> Vec<Vec<[f64; 3]>> {
+    vec![
+        // An L-shaped concave polygon, starting next to the reflex vertex
+        // `(1, 1)` so that a triangle fan would cover the notch.
+        vec![
+            [2.0, 1.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [1.0, 2.0, 0.0],
+            [0.0, 2.0, 0.0],
+            [0.0, 0.0, 0.0],
+            [2.0, 0.0, 0.0],
+        ],
+        // A square with a collinear vertex at the middle of the first edge.
+        vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [2.0, 0.0, 0.0],
+            [2.0, 2.0, 0.0],
+            [0.0, 2.0, 0.0],
+        ],
+        // A non-planar quad.
+        vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.3],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.3],
+        ],
+        // A zero-area polygon.
+        vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [2.0, 0.0, 0.0],
+            [3.0, 0.0, 0.0],
+        ],
+    ]
+}
+
+/// Returns twice the signed area of the triangle projected onto the XY plane.
+fn signed_area2(tri: &[[f64; 3]; 3]) -> f64 {
+    let [a, b, c] = tri;
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Returns whether the point is strictly inside the triangle on the XY plane.
+fn strictly_contains(tri: &[[f64; 3]; 3], p: [f64; 3]) -> bool {
+    let [a, b, c] = *tri;
+    [[a, b, p], [b, c, p], [c, a, p]]
+        .iter()
+        .all(|sub| signed_area2(sub) > 1e-9)
+}
+
+/// Triangulates the polygons by ear clipping, and returns the points of the
+/// triangles for each polygon.
+fn triangulate_polygons(polygons: &[Vec<[f64; 3]>]) -> Vec<Vec<[[f64; 3]; 3]>> {
+    let vertices = polygons
+        .iter()
+        .flatten()
+        .flatten()
+        .copied()
+        .collect::<Vec<_>>();
+    let mut polygon_vertex_index = Vec::new();
+    for polygon in polygons {
+        let start = polygon_vertex_index.len() as i32;
+        polygon_vertex_index.extend(start..(start + polygon.len() as i32));
+        let last = polygon_vertex_index
+            .last_mut()
+            .expect("Should never fail: the polygon is not empty");
+        *last = !*last;
+    }
+    let tree = tree_v7400! {
+        Documents: {}
+        Objects: {
+            Model: [1i64, "mesh\u{0}\u{1}Model", "Mesh"] {}
+            Geometry: [2i64, "\u{0}\u{1}Geometry", "Mesh"] {
+                Vertices: [vertices] {}
+                PolygonVertexIndex: [polygon_vertex_index] {}
+            }
+        }
+        Connections: {
+            C: ["OO", 1i64, 0i64] {}
+            C: ["OO", 2i64, 1i64] {}
+        }
+    };
+    let doc = Loader::new().load_from_tree(tree).unwrap();
+    let mesh = mesh(&doc);
+    let tris = mesh
+        .polygon_vertices()
+        .unwrap()
+        .triangulate_each(triangulator::ear_clipping)
+        .unwrap();
+
+    let mut results = vec![Vec::new(); polygons.len()];
+    let tri_vis = tris.triangle_vertex_indices().collect::<Vec<_>>();
+    for tri in tri_vis.chunks(3) {
+        let poly_i = tris.polygon_index(tri[0].triangle_index()).unwrap();
+        let mut points = [[0.0; 3]; 3];
+        for (point, &tri_vi) in points.iter_mut().zip(tri) {
+            let p = tris.control_point(tri_vi).unwrap();
+            *point = [p.x, p.y, p.z];
+        }
+        results[poly_i.to_usize()].push(points);
+    }
+    results
+}
+
+#[test]
+fn ear_clipping_concave_polygon() {
+    let polygons = polygons();
+    let triangles = &triangulate_polygons(&polygons)[0];
+    assert_eq!(triangles.len(), polygons[0].len() - 2);
+
+    let reflex = polygons[0][1];
+    let mut area2 = 0.0;
+    for tri in triangles {
+        // Same winding order as the counterclockwise polygon.
+        assert!(signed_area2(tri) > 0.0, "tri={:?}", tri);
+        // No triangle covers the reflex vertex, or goes out of the polygon.
+        assert!(!strictly_contains(tri, reflex), "tri={:?}", tri);
+        let centroid = [0, 1].map(|i| tri.iter().map(|p| p[i]).sum::<f64>() / 3.0);
+        assert!(
+            !(centroid[0] > 1.0 && centroid[1] > 1.0),
+            "tri={:?}, centroid={:?}",
+            tri,
+            centroid
+        );
+        area2 += signed_area2(tri);
+    }
+    assert!((area2 - 6.0).abs() < 1e-9, "area2={}", area2);
+}
+
+#[test]
+fn ear_clipping_collinear_vertices() {
+    let polygons = polygons();
+    let triangles = &triangulate_polygons(&polygons)[1];
+    assert_eq!(triangles.len(), polygons[1].len() - 2);
+
+    let mut area2 = 0.0;
+    for tri in triangles {
+        // A triangle of the three collinear vertices may have zero area, but
+        // no triangle has the reversed winding order.
+        assert!(signed_area2(tri) >= 0.0, "tri={:?}", tri);
+        area2 += signed_area2(tri);
+    }
+    assert!((area2 - 8.0).abs() < 1e-9, "area2={}", area2);
+}
+
+#[test]
+fn ear_clipping_non_planar_quad() {
+    let polygons = polygons();
+    let triangles = &triangulate_polygons(&polygons)[2];
+    assert_eq!(triangles.len(), polygons[2].len() - 2);
+
+    // The best-fit plane of the quad faces `+Z`, and so do the triangles.
+    for tri in triangles {
+        assert!(signed_area2(tri) > 0.0, "tri={:?}", tri);
+    }
+}
+
+#[test]
+fn ear_clipping_zero_area_polygon() {
+    let polygons = polygons();
+    let triangles = &triangulate_polygons(&polygons)[3];
+    assert_eq!(triangles.len(), polygons[3].len() - 2);
+
+    for tri in triangles {
+        assert_eq!(signed_area2(tri), 0.0, "tri={:?}", tri);
+        assert!(tri.iter().all(|p| polygons[3].contains(p)), "tri={:?}", tri);
+    }
+}
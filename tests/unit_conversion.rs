@@ -0,0 +1,103 @@
+//! Tests for unit conversion applied by evaluators and builders.
+
+mod common;
+
+use fbxcel_dom::{
+    fbxcel::tree_v7400,
+    v7400::{
+        data::{mesh::triangulator, unit::Unit},
+        object::{
+            model::{TransformEvaluator, TypedModelHandle},
+            TypedObjectHandle,
+        },
+        Document, Loader,
+    },
+};
+
+use crate::common::{mesh, model};
+
+fn load_document() -> Document {
+    let tree = tree_v7400! {
+        Documents: {}
+        Objects: {
+            Model: [1i64, "mesh\u{0}\u{1}Model", "Mesh"] {
+                Properties70: {
+                    P: ["Lcl Translation", "Lcl Translation", "", "A", 100.0f64, 0.0f64, 50.0f64] {}
+                    P: ["Lcl Scaling", "Lcl Scaling", "", "A", 2.0f64, 2.0f64, 2.0f64] {}
+                }
+            }
+            Geometry: [2i64, "\u{0}\u{1}Geometry", "Mesh"] {
+                Vertices: [vec![0.0f64, 0.0, 0.0, 100.0, 0.0, 0.0, 0.0, 100.0, 0.0]] {}
+                PolygonVertexIndex: [vec![0i32, 1, -3]] {}
+            }
+            Model: [3i64, "camera\u{0}\u{1}Model", "Camera"] {}
+            NodeAttribute: [4i64, "\u{0}\u{1}NodeAttribute", "Camera"] {
+                Properties70: {
+                    P: ["NearPlane", "double", "Number", "", 20.0f64] {}
+                }
+            }
+        }
+        Connections: {
+            C: ["OO", 1i64, 0i64] {}
+            C: ["OO", 2i64, 1i64] {}
+            C: ["OO", 3i64, 0i64] {}
+            C: ["OO", 4i64, 3i64] {}
+        }
+    };
+    Loader::new()
+        .load_from_tree(tree)
+        .expect("Should never fail: the document is valid")
+}
+
+#[test]
+fn evaluator_scales_translations() {
+    let doc = load_document();
+    let conversion = Unit::Centimeter.conversion_to(Unit::Meter);
+    let mesh = model(&doc, "mesh");
+
+    let mut evaluator = TransformEvaluator::new(&doc);
+    evaluator.set_unit_conversion(Some(conversion));
+    let world = evaluator.world_transform(&mesh).unwrap();
+    let local = evaluator.local_transform(&mesh).unwrap();
+    for m in &[world, local] {
+        // Scaling is left as is.
+        assert_eq!(m.x.x, 2.0);
+        assert_eq!(m.y.y, 2.0);
+        assert_eq!(m.z.z, 2.0);
+        assert_eq!((m.w.x, m.w.y, m.w.z), (1.0, 0.0, 0.5));
+    }
+}
+
+#[test]
+fn indexed_mesh_scales_positions() {
+    let doc = load_document();
+    let mesh = mesh(&doc);
+    let tris = mesh
+        .polygon_vertices()
+        .unwrap()
+        .triangulate_each(triangulator::fan)
+        .unwrap();
+    let indexed = mesh
+        .indexed_mesh_builder()
+        .unit_conversion(Some(Unit::Centimeter.conversion_to(Unit::Meter)))
+        .build(&tris)
+        .unwrap();
+    assert_eq!(indexed.indices(), &[0, 1, 2]);
+    assert_eq!(
+        indexed.positions(),
+        &[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]
+    );
+}
+
+#[test]
+fn camera_clip_planes() {
+    let doc = load_document();
+    let camera = match model(&doc, "camera").get_typed() {
+        TypedObjectHandle::Model(TypedModelHandle::Camera(camera)) => camera.attribute().unwrap(),
+        _ => unreachable!("Should never fail: the model is a camera"),
+    };
+    let conversion = Unit::Centimeter.conversion_to(Unit::Meter);
+    let props = camera.properties();
+    assert_eq!(props.clip_planes(None).unwrap(), (20.0, 4000.0));
+    assert_eq!(props.clip_planes(Some(&conversion)).unwrap(), (0.2, 40.0));
+}